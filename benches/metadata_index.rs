@@ -0,0 +1,78 @@
+//! Project listing over 10k Claude sessions: full re-parse vs. warm metadata index
+//!
+//! Run with `cargo bench --features server --bench metadata_index`
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use cli_session_hub_viewer::domain::models::AiTool;
+use cli_session_hub_viewer::history::metadata_index::MetadataIndex;
+use cli_session_hub_viewer::history::parsers::parse_claude_summaries;
+
+const PROJECTS: usize = 50;
+const SESSIONS_PER_PROJECT: usize = 200;
+const LINES_PER_SESSION: usize = 40;
+
+fn session_content(session: usize) -> String {
+    (0..LINES_PER_SESSION)
+        .map(|line| {
+            let role = if line % 2 == 0 { "user" } else { "assistant" };
+            format!(
+                "{{\"type\":\"{role}\",\"uuid\":\"u-{session}-{line}\",\"sessionId\":\"s-{session}\",\"timestamp\":\"2025-01-01T10:{:02}:00Z\",\"message\":{{\"id\":\"m-{session}-{line}\",\"role\":\"{role}\",\"content\":\"message {line} of session {session} with some text to parse\"}}}}\n",
+                line % 60
+            )
+        })
+        .collect()
+}
+
+fn build_tree() -> (PathBuf, Vec<PathBuf>) {
+    let root = std::env::temp_dir().join(format!("metadata-index-bench-{}", std::process::id()));
+    let projects: Vec<PathBuf> = (0..PROJECTS)
+        .map(|p| root.join(format!("-Users-bench-project-{}", p)))
+        .collect();
+    for (p, dir) in projects.iter().enumerate() {
+        fs::create_dir_all(dir).unwrap();
+        for s in 0..SESSIONS_PER_PROJECT {
+            let session = p * SESSIONS_PER_PROJECT + s;
+            fs::write(dir.join(format!("session-{}.jsonl", session)), session_content(session)).unwrap();
+        }
+    }
+    (root, projects)
+}
+
+fn list_with_parser(projects: &[PathBuf]) -> usize {
+    projects
+        .iter()
+        .map(|dir| parse_claude_summaries(dir).unwrap().len())
+        .sum()
+}
+
+fn list_with_index(index: &MetadataIndex, projects: &[PathBuf]) -> usize {
+    projects
+        .iter()
+        .map(|dir| index.project_summaries(&AiTool::ClaudeCode, dir).unwrap().len())
+        .sum()
+}
+
+fn bench_listing(c: &mut Criterion) {
+    let (root, projects) = build_tree();
+    let index = MetadataIndex::in_memory();
+    assert_eq!(list_with_index(&index, &projects), PROJECTS * SESSIONS_PER_PROJECT);
+
+    let mut group = c.benchmark_group("list_10k_sessions");
+    group.sample_size(10);
+    group.bench_function("parse_every_file", |b| b.iter(|| list_with_parser(&projects)));
+    group.bench_function("metadata_index_warm", |b| b.iter(|| list_with_index(&index, &projects)));
+    group.finish();
+
+    cleanup(&root);
+}
+
+fn cleanup(root: &Path) {
+    fs::remove_dir_all(root).ok();
+}
+
+criterion_group!(benches, bench_listing);
+criterion_main!(benches);
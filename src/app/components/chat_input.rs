@@ -14,6 +14,9 @@ use crate::shared::hooks::ImageUploadState;
 #[cfg(target_arch = "wasm32")]
 use crate::shared::hooks::{ImageUploadState, upload_file_to_server};
 use crate::app::components::ImagePreviewGrid;
+use crate::app::components::CliProviderOption;
+use crate::app::components::run_settings_panel::RunSettingsPanel;
+use crate::domain::models::RunSettings;
 
 /// Setup the JavaScript bridge for file handling (WASM only)
 #[cfg(target_arch = "wasm32")]
@@ -91,6 +94,12 @@ pub fn ChatInput(
     upload_state: ImageUploadState,
     on_submit: EventHandler<()>,
     on_abort: EventHandler<()>,
+    /// Run settings edited from the input ("⚙️ Réglages"); hidden if None
+    #[props(default)]
+    run_settings: Option<Signal<RunSettings>>,
+    /// Provider the settings are checked against
+    #[props(default)]
+    provider: CliProviderOption,
 ) -> Element {
     let mut is_composing = use_signal(|| false);
 
@@ -178,8 +187,12 @@ pub fn ChatInput(
 
             // Actions row: send button right only
             div { class: "chat-input__actions-row",
-                // Left side empty (removed upload button)
-                div { class: "chat-input__left-actions" }
+                // Left side: run settings (upload button removed)
+                div { class: "chat-input__left-actions",
+                    if let Some(settings) = run_settings {
+                        RunSettingsPanel { settings, provider, disabled: *is_loading.read() }
+                    }
+                }
 
                 div { class: "chat-input__right-actions",
                     if *is_loading.read() {
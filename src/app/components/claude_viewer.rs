@@ -1,28 +1,11 @@
 use dioxus::prelude::*;
-use crate::domain::models::{ApiProject, Session, Message};
+use crate::domain::models::{group_by_period, ApiProject, ConversationPage, Message, PageCursor, Session};
 use crate::server_fns::{get_projects, get_sessions_summaries, delete_session};
 use super::common::SessionsLoading;
-use super::message_item::MessageItem;
+use super::message_list::{MessageWindow, ScrollIntent, PAGE_SIZE};
 use super::ai_tool_to_slug;
 use chrono::{DateTime, Utc, Duration, Datelike};
 
-fn get_group(updated_at: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
-    let date = updated_at.date_naive();
-    let today = now.date_naive();
-    let yesterday = today - Duration::days(1);
-    if date == today {
-        "Aujourd'hui".to_string()
-    } else if date == yesterday {
-        "Hier".to_string()
-    } else if *updated_at >= *now - Duration::days(7) {
-        "7 derniers jours".to_string()
-    } else if *updated_at >= *now - Duration::days(30) {
-        "30 derniers jours".to_string()
-    } else {
-        "Plus ancien".to_string()
-    }
-}
-
 /// Format time as relative string like Claude.ai (e.g., "2h", "Yesterday", "Nov 15")
 fn format_relative_time(timestamp: &DateTime<Utc>, now: &DateTime<Utc>) -> String {
     let diff = *now - *timestamp;
@@ -53,41 +36,10 @@ fn format_relative_time(timestamp: &DateTime<Utc>, now: &DateTime<Utc>) -> Strin
 }
 
 fn group_sessions(session_list: Vec<Session>, now: &DateTime<Utc>) -> Vec<(String, Vec<Session>)> {
-    let mut groups: Vec<(String, Vec<Session>)> = vec![
-        ("Aujourd'hui".to_string(), vec![]),
-        ("Hier".to_string(), vec![]),
-        ("7 derniers jours".to_string(), vec![]),
-        ("30 derniers jours".to_string(), vec![]),
-        ("Plus ancien".to_string(), vec![]),
-    ];
-    for session in session_list {
-        let group = get_group(&session.updated_at, now);
-        let index = match group.as_str() {
-            "Aujourd'hui" => 0,
-            "Hier" => 1,
-            "7 derniers jours" => 2,
-            "30 derniers jours" => 3,
-            _ => 4,
-        };
-        groups[index].1.push(session);
-    }
-    groups
-}
-
-/// Check if a message has no meaningful content to display
-fn is_message_empty(message: &Message) -> bool {
-    match message {
-        Message::User { content, .. } => content.trim().is_empty(),
-        Message::Assistant { content, .. } => content.trim().is_empty(),
-        Message::System { content, .. } => content.trim().is_empty(),
-        Message::Thinking { content, .. } => content.trim().is_empty(),
-        Message::Plan { content, .. } => content.trim().is_empty(),
-        Message::Tool { output, .. } => {
-            // Tool messages are empty only if they have no output
-            output.as_ref().map(|o| o.trim().is_empty()).unwrap_or(true)
-        }
-        Message::Todo { items, .. } => items.is_empty(),
-    }
+    group_by_period(session_list, |session| session.updated_at, now)
+        .into_iter()
+        .map(|(period, sessions)| (period.label().to_string(), sessions))
+        .collect()
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -126,6 +78,7 @@ pub fn ProjectsList(#[props(default)] tool_filter: Option<String>) -> Element {
                     session_count: p.session_count,
                     ai_tool: p.ai_tool.clone(),
                     encoded_name: p.encoded_name.clone(),
+                    roots: p.roots.clone(),
                 })
                 .collect();
 
@@ -410,7 +363,8 @@ fn SessionCard(session: Session, encoded_name: String, refresh_trigger: Signal<i
                 to: crate::app::pages::claude_routes::Route::Session {
                     tool: tool_slug.clone(),
                     project_name: encoded_name.clone(),
-                    session_id: session.id.clone()
+                    session_id: session.id.clone(),
+                    message: String::new()
                 },
 
                 // Icon
@@ -571,8 +525,13 @@ fn SessionCardClickable(session: Session, on_click: EventHandler<String>) -> Ele
 
 // Conversation View Component with Interactive Chat
 // Uses SuspenseBoundary pattern for proper SSR hydration
+// Opens on the latest page of messages, or around `jump_to` (message index, 0 = first)
 #[component]
-pub fn ConversationView(project_name: String, session_id: String) -> Element {
+pub fn ConversationView(
+    project_name: String,
+    session_id: String,
+    #[props(default)] jump_to: Option<usize>,
+) -> Element {
     rsx! {
         div { class: "c-conversation-view",
             SuspenseBoundary {
@@ -581,7 +540,8 @@ pub fn ConversationView(project_name: String, session_id: String) -> Element {
                 },
                 ConversationViewInner {
                     project_name: project_name.clone(),
-                    session_id: session_id.clone()
+                    session_id: session_id.clone(),
+                    jump_to
                 }
             }
         }
@@ -590,26 +550,30 @@ pub fn ConversationView(project_name: String, session_id: String) -> Element {
 
 // Inner component that uses the `?` pattern for proper Suspense integration
 #[component]
-fn ConversationViewInner(project_name: String, session_id: String) -> Element {
+fn ConversationViewInner(project_name: String, session_id: String, jump_to: Option<usize>) -> Element {
     // Clone for closures
     let project_clone = project_name.clone();
     let session_clone = session_id.clone();
 
+    // Only one page is loaded up front: huge sessions are fetched as the user scrolls
+    let cursor = jump_to.map(PageCursor::Around).unwrap_or(PageCursor::Latest);
+
     // Use server function with use_server_future and `?` for Suspense
     let conversation_result = use_server_future(move || {
         let p = project_clone.clone();
         let s = session_clone.clone();
-        async move { crate::server_fns::get_conversation(p, s).await }
+        async move { crate::server_fns::get_conversation_page(p, s, cursor, Some(PAGE_SIZE)).await }
     })?;  // The `?` here integrates with SuspenseBoundary
 
     // Now we have the actual result (after loading is complete)
     // Save the result in a local variable to avoid lifetime issues with the temporary borrow
     let result = match &*conversation_result.read() {
-        Some(Ok(Some(conv))) => rsx! {
+        Some(Ok(Some(page))) => rsx! {
             ConversationChat {
                 project_name: project_name.clone(),
                 session_id: session_id.clone(),
-                initial_messages: conv.messages.clone()
+                initial_page: page.clone(),
+                jump_to
             }
         },
         Some(Ok(None)) => rsx! {
@@ -631,17 +595,23 @@ fn ConversationViewInner(project_name: String, session_id: String) -> Element {
 fn ConversationChat(
     project_name: String,
     session_id: String,
-    initial_messages: Vec<Message>,
+    initial_page: ConversationPage,
+    jump_to: Option<usize>,
 ) -> Element {
     // Chat state signals
+    // messages holds a window of the conversation: messages[0] is message number first_index of total
+    #[allow(unused_mut)]
+    let mut messages = use_signal(|| initial_page.messages.clone());
+    #[allow(unused_mut)]
+    let mut first_index = use_signal(|| initial_page.start);
     #[allow(unused_mut)]
-    let mut messages = use_signal(|| initial_messages.clone());
+    let mut total = use_signal(|| initial_page.total);
     #[allow(unused_mut)]
     let mut input = use_signal(String::new);
     #[allow(unused_mut)]
     let mut is_loading = use_signal(|| false);
-    #[allow(unused_variables)]
-    let current_session_id = use_signal(|| Some(session_id.clone()));
+    #[allow(unused_mut, unused_variables)]
+    let mut current_session_id = use_signal(|| Some(session_id.clone()));
     #[allow(unused_mut, unused_variables)]
     let mut current_request_id = use_signal(|| None::<String>);
     #[allow(unused_variables)]
@@ -657,9 +627,23 @@ fn ConversationChat(
     // CLI provider selection state
     let mut cli_provider = use_signal(|| super::CliProviderOption::Claude);
 
-    // Working directory from project name (decode path)
-    #[allow(unused_variables)]
-    let working_directory = project_name.replace("-", "/");
+    // Handoff: the next message opens a new session of this provider on a transcript of this one
+    let mut handoff_target = use_signal(|| None::<super::CliProviderOption>);
+
+    // Fork: the next message opens a new session holding this one up to the chosen message
+    let mut fork_point = use_signal(|| None::<usize>);
+
+    // Run settings (model, max turns, ...) sent with every message
+    #[allow(unused_mut)]
+    let mut run_settings = use_signal(crate::domain::models::RunSettings::default);
+
+    // Real project path, read server-side from the session files (never decoded from the name)
+    let project_for_cwd = project_name.clone();
+    let working_directory = use_resource(move || {
+        let project = project_for_cwd.clone();
+        async move { crate::server_fns::get_project_working_directory(project, None).await }
+    });
+    let working_directory_unknown = matches!(&*working_directory.read(), Some(Ok(None)) | Some(Err(_)));
 
     // SSE connection for real-time CLI → Web sync (WASM only)
     // Uses EventSource for instant updates when CLI writes new messages
@@ -708,6 +692,8 @@ fn ConversationChat(
 
             // Handle "message" events (new messages from CLI)
             let messages_clone = messages.clone();
+            let first_index_clone = first_index.clone();
+            let total_clone = total.clone();
             let project_for_fetch = project.clone();
             let session_for_fetch = session.clone();
             let onmessage = Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
@@ -717,29 +703,34 @@ fn ConversationChat(
                     // Parse SSE message to get new message count
                     if let Ok(sse_msg) = serde_json::from_str::<serde_json::Value>(&data) {
                         if sse_msg.get("event_type").and_then(|v| v.as_str()) == Some("new_messages") {
-                            // Fetch full conversation to get properly parsed messages
+                            // Fetch only the messages after the window (properly parsed)
                             let mut messages_inner = messages_clone.clone();
+                            let first_index_inner = first_index_clone.clone();
+                            let mut total_inner = total_clone.clone();
                             let project_inner = project_for_fetch.clone();
                             let session_inner = session_for_fetch.clone();
 
                             wasm_bindgen_futures::spawn_local(async move {
-                                let api_url = format!("/api/projects/{}/histories/{}", project_inner, session_inner);
+                                // Window on older messages: only refresh the total
+                                let window_end = *first_index_inner.peek() + messages_inner.peek().len();
+                                let at_live_end = window_end >= *total_inner.peek();
+                                let after = if at_live_end { window_end } else { *total_inner.peek() };
+                                let api_url = format!(
+                                    "/api/projects/{}/histories/{}/messages?after={}&limit=1000",
+                                    project_inner, session_inner, after
+                                );
 
                                 if let Some(window) = web_sys::window() {
                                     if let Ok(response) = wasm_bindgen_futures::JsFuture::from(window.fetch_with_str(&api_url)).await {
                                         if let Ok(response) = response.dyn_into::<web_sys::Response>() {
                                             if response.ok() {
                                                 if let Ok(json) = wasm_bindgen_futures::JsFuture::from(response.json().unwrap()).await {
-                                                    if let Ok(conv) = serde_wasm_bindgen::from_value::<crate::domain::models::Conversation>(json) {
-                                                        let current_count = messages_inner.read().len();
-                                                        let new_count = conv.messages.len();
-
-                                                        if new_count > current_count {
-                                                            tracing::info!("SSE: Adding {} new messages", new_count - current_count);
-                                                            for msg in conv.messages.into_iter().skip(current_count) {
-                                                                messages_inner.write().push(msg);
-                                                            }
+                                                    if let Ok(page) = serde_wasm_bindgen::from_value::<ConversationPage>(json) {
+                                                        if at_live_end && !page.messages.is_empty() {
+                                                            tracing::info!("SSE: Adding {} new messages", page.messages.len());
+                                                            messages_inner.write().extend(page.messages);
                                                         }
+                                                        total_inner.set(page.total.max(*first_index_inner.peek() + messages_inner.peek().len()));
                                                     }
                                                 }
                                             }
@@ -769,7 +760,8 @@ fn ConversationChat(
         use crate::shared::utils::process_stream_line;
         use chrono::Utc;
 
-        let working_dir = working_directory.clone();
+        let project_for_send = project_name.clone();
+        let session_for_handoff = session_id.clone();
         let cli_provider_clone = cli_provider.clone();
         let upload_state_for_send = upload_state.clone();
 
@@ -777,8 +769,12 @@ fn ConversationChat(
             // LOG 1: Confirm closure is called
             web_sys::console::log_1(&"[WASM] 📤 send_message CALLED".into());
 
-            let working_dir = working_dir.clone();
+            let project = project_for_send.clone();
+            let source_session = session_for_handoff.clone();
             let selected_cli = cli_provider_clone();
+            let handoff = handoff_target();
+            let fork_at = fork_point();
+            let settings = run_settings();
             let mut upload_state_clone = upload_state_for_send.clone();
 
             // Use spawn from dioxus::prelude instead of spawn_local (works in Dioxus EventHandler context)
@@ -794,6 +790,29 @@ fn ConversationChat(
                     upload_state_clone.uploaded_images.read().clone();
 
                 // 1. Add user message WITH images
+                let target = handoff.unwrap_or(selected_cli);
+                let notice = match (fork_at, handoff) {
+                    (Some(index), _) => Some(format!(
+                        "Bifurcation après le message {} de {} : nouvelle session {}",
+                        index + 1,
+                        source_session,
+                        target.display_name()
+                    )),
+                    (None, Some(target)) => Some(format!(
+                        "Reprise avec {} : nouvelle session ouverte sur la transcription de {}",
+                        target.display_name(),
+                        source_session
+                    )),
+                    (None, None) => None,
+                };
+                if let Some(content) = notice {
+                    messages.write().push(Message::System {
+                        content,
+                        timestamp: Utc::now(),
+                        level: Some(crate::domain::models::LogLevel::Info),
+                        metadata: None,
+                    });
+                }
                 messages.write().push(Message::User {
                     content: message_content.clone(),
                     timestamp: Utc::now(),
@@ -812,15 +831,37 @@ fn ConversationChat(
 
                 // 4. Build request
                 let mut chat_request = ChatRequest::new(message_content.clone(), request_id.clone())
-                    .with_working_directory(working_dir);
+                    .with_project_name(project);
+
+                if handoff.is_some() || fork_at.is_some() {
+                    // Handoff or fork: new session of the target provider, seeded with this session
+                    chat_request = chat_request
+                        .with_cli_provider(target.slug().to_string())
+                        .with_handoff_from(source_session);
+                    if let Some(index) = fork_at {
+                        chat_request = chat_request.with_fork_at(index);
+                    }
+                    cli_provider.set(target);
+                    handoff_target.set(None);
+                    fork_point.set(None);
+                    current_session_id.set(None);
+                } else {
+                    // Add session_id for continuation
+                    if let Some(sid) = (*current_session_id.read()).clone() {
+                        chat_request = chat_request.with_session_id(sid);
+                    }
 
-                // Add session_id for continuation
-                if let Some(sid) = (*current_session_id.read()).clone() {
-                    chat_request = chat_request.with_session_id(sid);
+                    // Add CLI provider
+                    chat_request = chat_request.with_cli_provider(selected_cli.slug().to_string());
                 }
 
-                // Add CLI provider
-                chat_request = chat_request.with_cli_provider(selected_cli.slug().to_string());
+                // Run settings the target CLI accepts (model names are provider-specific)
+                let mut settings = super::run_settings_panel::settings_for(&settings, target);
+                if target != selected_cli {
+                    settings.model = None;
+                    run_settings.write().model = None;
+                }
+                chat_request = chat_request.with_run_settings(settings);
 
                 // Add images if any
                 if !image_paths.is_empty() {
@@ -1031,37 +1072,68 @@ fn ConversationChat(
         // SSR: no-op, WASM will hydrate with real handler
     };
 
-    // Auto-scroll to bottom when messages change (WASM only)
-    let messages_len = messages.read().len();
-    use_effect(move || {
-        #[cfg(target_arch = "wasm32")]
-        {
-            let script = r#"
-                setTimeout(() => {
-                    // Scroll the messages container to bottom (not the window)
-                    const messagesContainer = document.querySelector('.c-conversation-chat__messages');
-                    if (messagesContainer) {
-                        messagesContainer.scrollTop = messagesContainer.scrollHeight;
+    rsx! {
+        div { class: "c-conversation-chat",
+            // Messages area - windowed, pages fetched while scrolling
+            MessageWindow {
+                project_name: project_name.clone(),
+                session_id: session_id.clone(),
+                messages: messages,
+                first_index: first_index,
+                total: total,
+                initial_scroll: match jump_to {
+                    Some(index) => ScrollIntent::Message { index, block: "center", highlight: true },
+                    None => ScrollIntent::Bottom,
+                },
+                on_fork: move |index: usize| {
+                    if !is_loading() {
+                        fork_point.set(Some(index));
                     }
-                    // Also focus the input
-                    const input = document.getElementById('chat-input');
-                    if (input) {
-                        input.focus();
+                },
+                fork_point: fork_point(),
+            }
+
+            if working_directory_unknown {
+                div { class: "c-conversation-chat__warning",
+                    "⚠️ Dossier du projet introuvable : la CLI sera lancée sans dossier de travail."
+                }
+            }
+
+            // Fork point chosen in the list: the next message starts the branch
+            if let Some(index) = fork_point() {
+                div { class: "c-handoff c-handoff--fork",
+                    span { class: "c-handoff__hint",
+                        "⑂ Le prochain message ouvrira une nouvelle session reprenant celle-ci jusqu'au message {index + 1} ; l'originale reste intacte."
                     }
-                }, 150);
-            "#;
-            let _ = js_sys::eval(script);
-        }
-        let _ = messages_len; // Use the variable to trigger effect on messages change
-    });
+                    button {
+                        class: "c-handoff__button",
+                        r#type: "button",
+                        onclick: move |_| fork_point.set(None),
+                        "Annuler"
+                    }
+                }
+            }
 
-    rsx! {
-        div { class: "c-conversation-chat",
-            // Messages area - scrollable
-            div { class: "c-conversation-chat__messages",
-                ul { class: "c-conversation-list",
-                    for message in messages.read().iter().filter(|m| !is_message_empty(m)) {
-                        MessageItem { message: message.clone() }
+            // Continue this session with another provider (transcript handoff)
+            div { class: "c-handoff",
+                span { class: "c-handoff__label", "Continuer avec" }
+                for option in super::CliProviderOption::ALL {
+                    button {
+                        key: "{option.slug()}",
+                        class: if handoff_target() == Some(option) { "c-handoff__button c-handoff__button--active" } else { "c-handoff__button" },
+                        r#type: "button",
+                        disabled: is_loading(),
+                        title: "Nouvelle session {option.display_name()} ouverte sur une transcription condensée de celle-ci",
+                        onclick: move |_| {
+                            let selected = handoff_target() == Some(option);
+                            handoff_target.set(if selected { None } else { Some(option) });
+                        },
+                        "{option.icon()} {option.display_name()}"
+                    }
+                }
+                if let Some(target) = handoff_target() {
+                    span { class: "c-handoff__hint",
+                        "Le prochain message ouvrira une session {target.display_name()} avec la transcription de celle-ci : écrivez la consigne de reprise."
                     }
                 }
             }
@@ -1074,6 +1146,8 @@ fn ConversationChat(
                     upload_state: upload_state.clone(),
                     on_submit: on_submit_handler,
                     on_abort: on_abort_handler,
+                    run_settings: Some(run_settings),
+                    provider: handoff_target().unwrap_or(cli_provider()),
                 }
             }
         }
@@ -110,6 +110,13 @@ pub fn ProjectCard(
                         "📂 {path_display}"
                     }
                     p { class: "project-sessions", "{project.session_count}" }
+                    if !project.roots.is_empty() {
+                        div { class: "c-root-badges",
+                            for root in project.roots.iter() {
+                                span { class: "c-root-badge", title: "Racine d'historique : {root}", "{root}" }
+                            }
+                        }
+                    }
                 }
             }
 
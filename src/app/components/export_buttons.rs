@@ -0,0 +1,60 @@
+//! Download buttons for the exports of a conversation
+//!
+//! The export endpoint answers with an attachment, so navigating to it
+//! downloads the file without leaving the page. HTML exports take the theme
+//! saved in localStorage.
+
+use dioxus::prelude::*;
+
+/// `format` query values and button labels
+const FORMATS: [(&str, &str); 3] = [("markdown", "Markdown"), ("json", "JSON"), ("html", "HTML")];
+
+/// Start the download of one export (WASM only)
+fn download_export(project_name: &str, session_id: &str, format: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let theme = window
+            .local_storage()
+            .ok()
+            .flatten()
+            .and_then(|storage| storage.get_item("theme").ok().flatten())
+            .unwrap_or_else(|| "dark".to_string());
+        let url = format!(
+            "/api/projects/{}/histories/{}/export?format={}&theme={}",
+            urlencoding::encode(project_name),
+            urlencoding::encode(session_id),
+            format,
+            urlencoding::encode(&theme),
+        );
+        let _ = window.location().set_href(&url);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = (project_name, session_id, format);
+}
+
+// Export buttons (BEM: c-export-buttons)
+#[component]
+pub fn ExportButtons(project_name: String, session_id: String) -> Element {
+    rsx! {
+        div { class: "c-export-buttons",
+            span { class: "c-export-buttons__label", "Exporter" }
+            for (format, label) in FORMATS {
+                button {
+                    key: "{format}",
+                    class: "c-export-buttons__button",
+                    r#type: "button",
+                    title: "Télécharger en {label}",
+                    onclick: {
+                        let project_name = project_name.clone();
+                        let session_id = session_id.clone();
+                        move |_| download_export(&project_name, &session_id, format)
+                    },
+                    "{label}"
+                }
+            }
+        }
+    }
+}
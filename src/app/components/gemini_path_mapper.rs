@@ -0,0 +1,74 @@
+use dioxus::prelude::*;
+use crate::server_fns::{get_gemini_project_path, map_gemini_project};
+use crate::domain::models::is_gemini_hash;
+use super::common::ErrorMessage;
+
+// Manual mapping of an unresolved Gemini hash to a directory (BEM: c-path-mapper)
+#[component]
+pub fn GeminiPathMapper(hash: String) -> Element {
+    let hash_for_resource = hash.clone();
+    let mut known_path = use_resource(move || {
+        let hash = hash_for_resource.clone();
+        async move { get_gemini_project_path(hash).await }
+    });
+    let mut path_input = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut saving = use_signal(|| false);
+
+    if !is_gemini_hash(&hash) {
+        return rsx! {};
+    }
+
+    let short_hash = hash[..12].to_string();
+
+    match &*known_path.read() {
+        Some(Ok(None)) => {}
+        _ => return rsx! {},
+    }
+
+    rsx! {
+        div { class: "c-path-mapper",
+            p { class: "c-path-mapper__text",
+                "Le dossier de ce projet Gemini ({short_hash}…) est inconnu. Indiquez son chemin absolu pour l'associer."
+            }
+            form {
+                class: "c-path-mapper__form",
+                onsubmit: move |evt| {
+                    evt.prevent_default();
+                    let hash = hash.clone();
+                    let path = path_input().trim().to_string();
+                    if path.is_empty() {
+                        return;
+                    }
+                    spawn(async move {
+                        saving.set(true);
+                        match map_gemini_project(hash, path).await {
+                            Ok(_) => {
+                                error.set(None);
+                                known_path.restart();
+                            }
+                            Err(e) => error.set(Some(format!("Association impossible: {}", e))),
+                        }
+                        saving.set(false);
+                    });
+                },
+                input {
+                    class: "c-path-mapper__input",
+                    r#type: "text",
+                    placeholder: "/Users/moi/code/projet",
+                    value: "{path_input}",
+                    oninput: move |evt| path_input.set(evt.value()),
+                }
+                button {
+                    class: "c-path-mapper__submit",
+                    r#type: "submit",
+                    disabled: saving(),
+                    "Associer"
+                }
+            }
+            if let Some(message) = error() {
+                ErrorMessage { message }
+            }
+        }
+    }
+}
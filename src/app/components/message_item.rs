@@ -1,10 +1,15 @@
 //! Message rendering component
 //!
 //! Displays individual messages in the conversation view
+//! When secret masking is on (settings panel), secrets are shown as
+//! `[redacted:<kind>]` until the message is revealed.
 
 use dioxus::prelude::*;
 use pulldown_cmark::{html, Options, Parser};
 use crate::app::components::ImageGallery;
+use crate::domain::models::Message;
+use crate::domain::services::redaction::Redactor;
+use crate::shared::hooks::use_redaction;
 
 /// Helper function to render Markdown to HTML
 pub fn render_markdown(markdown: &str) -> String {
@@ -20,16 +25,65 @@ pub fn render_markdown(markdown: &str) -> String {
     html_output
 }
 
+/// Number of secrets in the displayed texts of a message
+fn secret_count(redactor: &Redactor, message: &Message) -> usize {
+    match message {
+        Message::User { content, .. }
+        | Message::Assistant { content, .. }
+        | Message::System { content, .. }
+        | Message::Thinking { content, .. }
+        | Message::Plan { content, .. } => redactor.find(content).len(),
+        Message::Tool { input, output, .. } => {
+            redactor.find(&serde_json::to_string_pretty(input).unwrap_or_default()).len()
+                + output.as_deref().map_or(0, |o| redactor.find(o).len())
+        }
+        Message::Todo { .. } => 0,
+    }
+}
+
+/// Show/hide button of the secrets of a message (BEM: c-secret-toggle)
+#[component]
+fn SecretToggle(count: usize, revealed: Signal<bool>) -> Element {
+    rsx! {
+        button {
+            class: "c-secret-toggle",
+            title: "Les exports restent toujours masqués",
+            onclick: move |_| revealed.toggle(),
+            if revealed() {
+                "🔒 Masquer les secrets"
+            } else if count == 1 {
+                "👁 1 secret masqué · Afficher"
+            } else {
+                "👁 {count} secrets masqués · Afficher"
+            }
+        }
+    }
+}
+
 /// Renders a single message in the conversation
+/// anchor_id: element id, used to scroll to the message (jump to message)
 #[component]
-pub fn MessageItem(message: crate::domain::models::Message) -> Element {
-    use crate::domain::models::Message;
+pub fn MessageItem(
+    message: Message,
+    #[props(default)] anchor_id: Option<String>,
+) -> Element {
+    let revealed = use_signal(|| false);
+    let redactor = use_redaction().filter(|state| (state.enabled)()).map(|state| state.redactor);
+    let secrets = redactor.map_or(0, |redactor| secret_count(&redactor.read(), &message));
+    // Texts as displayed: redacted while the message holds secrets and is not revealed
+    let mask = move |text: &str| -> String {
+        match redactor {
+            Some(redactor) if secrets > 0 && !revealed() => redactor.read().redact(text).into_owned(),
+            _ => text.to_string(),
+        }
+    };
 
     match message {
         Message::User {
             content, timestamp, images, ..
         } => rsx! {
             li { class: "c-chat-message c-chat-message--user animate-fade-in",
+                id: anchor_id.clone(),
                 div { class: "c-chat-bubble c-chat-bubble--user",
                     // Display images if any
                     if !images.is_empty() {
@@ -41,7 +95,10 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
                         }
                     }
                     div { class: "c-chat-bubble__content",
-                        div { class: "u-whitespace-pre-wrap", {content.clone()} }
+                        div { class: "u-whitespace-pre-wrap", {mask(&content)} }
+                    }
+                    if secrets > 0 {
+                        SecretToggle { count: secrets, revealed }
                     }
                     span { class: "c-chat-bubble__timestamp",
                         {timestamp.format("%H:%M").to_string()}
@@ -52,9 +109,10 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
         Message::Assistant {
             content, timestamp, images, ..
         } => {
-            let html_content = render_markdown(&content);
+            let html_content = render_markdown(&mask(&content));
             rsx! {
                 li { class: "c-chat-message c-chat-message--assistant animate-fade-in",
+                    id: anchor_id.clone(),
                     div { class: "c-chat-bubble c-chat-bubble--assistant",
                         div { class: "c-chat-bubble__content",
                             div {
@@ -71,6 +129,9 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
                                 }
                             }
                         }
+                        if secrets > 0 {
+                            SecretToggle { count: secrets, revealed }
+                        }
                         span { class: "c-chat-bubble__timestamp",
                             {timestamp.format("%H:%M").to_string()}
                         }
@@ -86,11 +147,15 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
             ..
         } => rsx! {
             li { class: "c-conversation-item c-conversation-item--align-start animate-fade-in",
+                id: anchor_id.clone(),
                 div { class: "c-conversation-content",
                     div { class: "card tool-message gap-2 py-3 mb-2 rounded-lg",
                         div { class: "py-0 px-4",
                             div { class: "flex items-center justify-between mb-2",
                                 span { class: "text-sm font-medium", "🛠️ {name}" }
+                                if secrets > 0 {
+                                    SecretToggle { count: secrets, revealed }
+                                }
                                 span { class: "text-xs text-muted-foreground",
                                     {timestamp.format("%H:%M:%S").to_string()}
                                 }
@@ -103,7 +168,7 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
                                 }
                                 div { class: "border rounded p-2 mb-2",
                                     pre { class: "text-xs whitespace-pre-wrap break-all font-mono overflow-x-auto",
-                                        {serde_json::to_string_pretty(&input).unwrap_or_default()}
+                                        {mask(&serde_json::to_string_pretty(&input).unwrap_or_default())}
                                     }
                                 }
                             }
@@ -114,7 +179,7 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
                                     }
                                     div { class: "border rounded p-2",
                                         pre { class: "text-xs whitespace-pre-wrap break-all font-mono overflow-x-auto",
-                                            {o}
+                                            {mask(&o)}
                                         }
                                     }
                                 }
@@ -128,15 +193,19 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
             content, timestamp, ..
         } => rsx! {
             li { class: "c-conversation-item c-conversation-item--align-start animate-fade-in",
+                id: anchor_id.clone(),
                 div { class: "c-conversation-content",
                     div { class: "card system-message px-3 py-3 mb-3 rounded-lg",
                         div { class: "flex items-center justify-between mb-2",
                             span { class: "text-sm font-medium", "⚙️ System" }
+                            if secrets > 0 {
+                                SecretToggle { count: secrets, revealed }
+                            }
                             span { class: "text-xs text-muted-foreground",
                                 {timestamp.format("%H:%M:%S").to_string()}
                             }
                         }
-                        div { class: "whitespace-pre-wrap text-sm", {content.clone()} }
+                        div { class: "whitespace-pre-wrap text-sm", {mask(&content)} }
                     }
                 }
             }
@@ -145,15 +214,19 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
             content, timestamp, ..
         } => rsx! {
             li { class: "c-conversation-item c-conversation-item--align-start animate-fade-in",
+                id: anchor_id.clone(),
                 div { class: "c-conversation-content",
                     div { class: "card thinking-message px-3 py-3 mb-3 rounded-lg",
                         div { class: "flex items-center justify-between mb-2",
                             span { class: "text-sm font-medium", "💭 Thinking" }
+                            if secrets > 0 {
+                                SecretToggle { count: secrets, revealed }
+                            }
                             span { class: "text-xs text-muted-foreground",
                                 {timestamp.format("%H:%M:%S").to_string()}
                             }
                         }
-                        div { class: "whitespace-pre-wrap text-sm", {content.clone()} }
+                        div { class: "whitespace-pre-wrap text-sm", {mask(&content)} }
                     }
                 }
             }
@@ -161,13 +234,17 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
         Message::Plan {
             content, timestamp, ..
         } => {
-            let html_content = render_markdown(&content);
+            let html_content = render_markdown(&mask(&content));
             rsx! {
                 li { class: "c-conversation-item c-conversation-item--align-start animate-fade-in",
+                    id: anchor_id.clone(),
                     div { class: "c-conversation-content",
                         div { class: "card plan-message px-3 py-3 mb-3 rounded-lg",
                             div { class: "flex items-center justify-between mb-2",
                                 span { class: "text-sm font-medium", "📋 Plan Proposed" }
+                                if secrets > 0 {
+                                    SecretToggle { count: secrets, revealed }
+                                }
                                 span { class: "text-xs text-muted-foreground",
                                     {timestamp.format("%H:%M:%S").to_string()}
                                 }
@@ -185,6 +262,7 @@ pub fn MessageItem(message: crate::domain::models::Message) -> Element {
             items, timestamp, ..
         } => rsx! {
             li { class: "c-conversation-item c-conversation-item--align-start animate-fade-in",
+                id: anchor_id.clone(),
                 div { class: "c-conversation-content",
                     div { class: "card todo-message px-3 py-3 mb-3 rounded-lg",
                         div { class: "flex items-center justify-between mb-2",
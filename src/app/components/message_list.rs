@@ -0,0 +1,307 @@
+//! Windowed message list for large conversations
+//!
+//! Only a window of the conversation is kept in memory and in the DOM. Pages are
+//! fetched with `get_conversation_page` as the user scrolls towards either edge,
+//! and the window is trimmed on the opposite side once it exceeds `MAX_LOADED`.
+
+use dioxus::prelude::*;
+use crate::domain::models::{Message, PageCursor};
+use crate::server_fns::get_conversation_page;
+use super::export_buttons::ExportButtons;
+use super::share_button::ShareButton;
+use super::message_item::MessageItem;
+
+/// Messages fetched per page
+pub const PAGE_SIZE: usize = 100;
+
+/// Messages kept in the window before trimming the far edge
+const MAX_LOADED: usize = 300;
+
+/// Distance to an edge (px) that triggers loading the next page
+const EDGE_THRESHOLD: f64 = 200.0;
+
+/// Where to scroll once the list has been re-rendered
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollIntent {
+    /// Stick to the last message (live conversation)
+    Bottom,
+    /// Keep a message in view after the window moved ("start", "center" or "end")
+    Message { index: usize, block: &'static str, highlight: bool },
+}
+
+/// Page request issued by the list
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PageRequest {
+    Older,
+    Newer,
+    Jump(usize),
+}
+
+/// Check if a message has no meaningful content to display
+fn is_message_empty(message: &Message) -> bool {
+    match message {
+        Message::User { content, .. } => content.trim().is_empty(),
+        Message::Assistant { content, .. } => content.trim().is_empty(),
+        Message::System { content, .. } => content.trim().is_empty(),
+        Message::Thinking { content, .. } => content.trim().is_empty(),
+        Message::Plan { content, .. } => content.trim().is_empty(),
+        Message::Tool { output, .. } => {
+            // Tool messages are empty only if they have no output
+            output.as_ref().map(|o| o.trim().is_empty()).unwrap_or(true)
+        }
+        Message::Todo { items, .. } => items.is_empty(),
+    }
+}
+
+/// Anchor id of a message, by its index in the whole conversation
+pub fn message_anchor(index: usize) -> String {
+    format!("message-{}", index)
+}
+
+/// Scroll the messages container (WASM only)
+fn apply_scroll(intent: ScrollIntent) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let script = match intent {
+            ScrollIntent::Bottom => r#"
+                setTimeout(() => {
+                    const messagesContainer = document.querySelector('.c-conversation-chat__messages');
+                    if (messagesContainer) {
+                        messagesContainer.scrollTop = messagesContainer.scrollHeight;
+                    }
+                    const input = document.getElementById('chat-input');
+                    if (input) {
+                        input.focus();
+                    }
+                }, 50);
+            "#
+            .to_string(),
+            ScrollIntent::Message { index, block, highlight } => format!(
+                r#"
+                setTimeout(() => {{
+                    const message = document.getElementById('{anchor}');
+                    if (message) {{
+                        message.scrollIntoView({{ block: '{block}' }});
+                        if ({highlight}) {{
+                            message.classList.add('c-chat-message--highlight');
+                            setTimeout(() => message.classList.remove('c-chat-message--highlight'), 2000);
+                        }}
+                    }}
+                }}, 50);
+                "#,
+                anchor = message_anchor(index),
+                block = block,
+                highlight = highlight,
+            ),
+        };
+        let _ = js_sys::eval(&script);
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = intent;
+}
+
+// Windowed conversation list (BEM: c-message-window)
+// messages[0] is message number `first_index` of `total`; locally sent and
+// streamed messages are appended by the parent.
+// With `on_fork`, each saved message offers "fork from here" (its index is passed).
+#[component]
+pub fn MessageWindow(
+    project_name: String,
+    session_id: String,
+    mut messages: Signal<Vec<Message>>,
+    mut first_index: Signal<usize>,
+    mut total: Signal<usize>,
+    initial_scroll: ScrollIntent,
+    on_fork: Option<EventHandler<usize>>,
+    #[props(default)] fork_point: Option<usize>,
+) -> Element {
+    let mut loading_page = use_signal(|| false);
+    let mut page_error = use_signal(|| None::<String>);
+    let mut scroll_intent = use_signal(|| Some(initial_scroll));
+    let mut following = use_signal(|| initial_scroll == ScrollIntent::Bottom);
+    let mut jump_input = use_signal(String::new);
+    let export_project = project_name.clone();
+    let export_session = session_id.clone();
+    let share_project = project_name.clone();
+    let share_session = session_id.clone();
+
+    let fetch_page = use_callback(move |request: PageRequest| {
+        if *loading_page.peek() {
+            return;
+        }
+        let start = *first_index.peek();
+        let end = start + messages.peek().len();
+        let cursor = match request {
+            PageRequest::Older if start == 0 => return,
+            PageRequest::Older => PageCursor::Before(start),
+            PageRequest::Newer if end >= *total.peek() => return,
+            PageRequest::Newer => PageCursor::After(end),
+            PageRequest::Jump(index) => PageCursor::Around(index),
+        };
+
+        loading_page.set(true);
+        let project = project_name.clone();
+        let session = session_id.clone();
+        spawn(async move {
+            match get_conversation_page(project, session, cursor, Some(PAGE_SIZE)).await {
+                Ok(Some(page)) => {
+                    let intent = {
+                        let mut window = messages.write();
+                        match request {
+                            PageRequest::Older => {
+                                let mut combined = page.messages;
+                                combined.append(&mut *window);
+                                combined.truncate(MAX_LOADED);
+                                *window = combined;
+                                first_index.set(page.start);
+                                ScrollIntent::Message { index: start, block: "start", highlight: false }
+                            }
+                            PageRequest::Newer => {
+                                window.extend(page.messages);
+                                let excess = window.len().saturating_sub(MAX_LOADED);
+                                window.drain(..excess);
+                                first_index.set(start + excess);
+                                ScrollIntent::Message { index: end.saturating_sub(1), block: "end", highlight: false }
+                            }
+                            PageRequest::Jump(index) => {
+                                *window = page.messages;
+                                first_index.set(page.start);
+                                ScrollIntent::Message { index, block: "center", highlight: true }
+                            }
+                        }
+                    };
+                    total.set(page.total);
+                    scroll_intent.set(Some(intent));
+                    page_error.set(None);
+                }
+                Ok(None) => page_error.set(Some("Session introuvable".to_string())),
+                Err(e) => page_error.set(Some(format!("Chargement impossible: {}", e))),
+            }
+            loading_page.set(false);
+        });
+    });
+
+    // Re-position after every change of the window or new live message
+    use_effect(move || {
+        let _ = messages.read().len();
+        let _ = first_index.read();
+        let intent = *scroll_intent.peek();
+        match intent {
+            Some(intent) => {
+                scroll_intent.set(None);
+                apply_scroll(intent);
+            }
+            None if *following.peek() => apply_scroll(ScrollIntent::Bottom),
+            None => {}
+        }
+    });
+
+    let onscroll = move |evt: Event<ScrollData>| {
+        let data = evt.data();
+        let top = data.scroll_top();
+        let bottom_gap = data.scroll_height() as f64 - top - data.client_height() as f64;
+        let end = *first_index.peek() + messages.peek().len();
+        let at_live_end = end >= *total.peek();
+
+        following.set(at_live_end && bottom_gap < EDGE_THRESHOLD / 2.0);
+        if top < EDGE_THRESHOLD {
+            fetch_page.call(PageRequest::Older);
+        } else if bottom_gap < EDGE_THRESHOLD && !at_live_end {
+            fetch_page.call(PageRequest::Newer);
+        }
+    };
+
+    let on_jump = move |evt: Event<FormData>| {
+        evt.prevent_default();
+        let Ok(number) = jump_input.read().trim().parse::<usize>() else {
+            return;
+        };
+        let index = number.clamp(1, (*total.peek()).max(1)) - 1;
+        let start = *first_index.peek();
+        following.set(false);
+        if (start..start + messages.peek().len()).contains(&index) {
+            apply_scroll(ScrollIntent::Message { index, block: "center", highlight: true });
+        } else {
+            fetch_page.call(PageRequest::Jump(index));
+        }
+    };
+
+    let start = first_index();
+    let shown_end = (start + messages.read().len()).min(total());
+
+    rsx! {
+        div { class: "c-message-window",
+            div { class: "c-message-window__toolbar",
+                span { class: "c-message-window__range",
+                    if total() == 0 {
+                        "Aucun message"
+                    } else {
+                        "Messages {start + 1}–{shown_end} sur {total}"
+                    }
+                }
+                if loading_page() {
+                    span { class: "c-message-window__loading", "Chargement…" }
+                }
+                if let Some(error) = page_error() {
+                    span { class: "c-message-window__error", "{error}" }
+                }
+                form { class: "c-message-window__jump",
+                    onsubmit: on_jump,
+                    input {
+                        class: "c-message-window__jump-input",
+                        r#type: "number",
+                        min: "1",
+                        max: "{total}",
+                        placeholder: "N°",
+                        value: "{jump_input}",
+                        oninput: move |evt| jump_input.set(evt.value()),
+                    }
+                    button { class: "c-message-window__jump-button", r#type: "submit", "Aller au message" }
+                }
+                ExportButtons { project_name: export_project, session_id: export_session }
+                ShareButton { project_name: share_project, session_id: share_session, total: total() }
+            }
+
+            // Messages area - scrollable
+            div { class: "c-conversation-chat__messages",
+                onscroll: onscroll,
+                if start > 0 {
+                    button {
+                        class: "c-message-window__more",
+                        disabled: loading_page(),
+                        onclick: move |_| fetch_page.call(PageRequest::Older),
+                        "↑ Messages précédents"
+                    }
+                }
+                ul { class: "c-conversation-list",
+                    for (offset, message) in messages.read().iter().enumerate().filter(|(_, m)| !is_message_empty(m)) {
+                        MessageItem {
+                            key: "{start + offset}",
+                            message: message.clone(),
+                            anchor_id: Some(message_anchor(start + offset)),
+                        }
+                        if let Some(on_fork) = on_fork.filter(|_| start + offset < total()) {
+                            li {
+                                class: if fork_point == Some(start + offset) { "c-fork-point c-fork-point--active" } else { "c-fork-point" },
+                                button {
+                                    class: "c-fork-point__button",
+                                    title: "Nouvelle session reprenant la conversation jusqu'à ce message",
+                                    onclick: move |_| on_fork.call(start + offset),
+                                    "⑂ Bifurquer ici"
+                                }
+                            }
+                        }
+                    }
+                }
+                if shown_end < total() {
+                    button {
+                        class: "c-message-window__more",
+                        disabled: loading_page(),
+                        onclick: move |_| fetch_page.call(PageRequest::Newer),
+                        "↓ Messages suivants"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -11,6 +11,14 @@ pub mod ai_tool_selector;
 pub mod cli_selector;
 pub mod delete_button;
 pub mod image_upload;
+pub mod trash_list;
+pub mod message_list;
+pub mod export_buttons;
+pub mod project_archive;
+pub mod share_button;
+pub mod share_list;
+pub mod gemini_path_mapper;
+pub mod run_settings_panel;
 
 // Chat input - available on all platforms for SSR + hydration
 pub mod chat_input;
@@ -37,4 +45,11 @@ pub use ai_tool_selector::{AiToolLanding, slug_to_ai_tool, ai_tool_to_slug, ai_t
 pub use delete_button::{DeleteButton, InlineDeleteButton};
 pub use common::{LoadingText, SessionsLoading, ConversationLoading, ErrorMessage, ProjectCard, EmptyState};
 pub use image_upload::{ImageGallery, ImageLightbox, ImagePreviewGrid, ImageUploadButton};
+pub use trash_list::TrashList;
+pub use export_buttons::ExportButtons;
+pub use project_archive::{ProjectArchiveLink, ProjectImportButton};
+pub use share_button::ShareButton;
+pub use share_list::ShareList;
+pub use gemini_path_mapper::GeminiPathMapper;
+pub use run_settings_panel::RunSettingsPanel;
 pub use cli_selector::{CliProviderOption, CliSelector, CliSelectorCompact, CliSelectorWithStatus};
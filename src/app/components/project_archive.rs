@@ -0,0 +1,126 @@
+//! Project archive download link and archive import button
+//!
+//! Imported projects land in the read-only "imported" history root and show up
+//! in the sidebar with that badge, next to the local ones.
+
+use dioxus::prelude::*;
+
+/// Id of the hidden file input read by the import script
+const IMPORT_INPUT_ID: &str = "project-archive-input";
+
+// Download link of a whole project (BEM: c-project-archive)
+#[component]
+pub fn ProjectArchiveLink(tool: String, project_name: String) -> Element {
+    let href = format!(
+        "/api/projects/{}/archive?provider={}",
+        urlencoding::encode(&project_name),
+        urlencoding::encode(&tool)
+    );
+
+    rsx! {
+        a {
+            class: "c-project-archive",
+            href: "{href}",
+            download: "",
+            title: "Télécharger les sessions, images et manifeste du projet (.tar.zst)",
+            "Archiver"
+        }
+    }
+}
+
+/// Send the selected archive to the import endpoint (WASM only)
+/// Returns the message to show.
+async fn import_selected_archive() -> Result<String, String> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let script = format!(
+            r#"
+            const input = document.getElementById('{IMPORT_INPUT_ID}');
+            const file = input && input.files[0];
+            if (!file) {{
+                return null;
+            }}
+            const response = await fetch('/api/projects/import', {{
+                method: 'POST',
+                headers: {{ 'Content-Type': 'application/octet-stream' }},
+                body: file,
+            }});
+            input.value = '';
+            return {{ ok: response.ok, body: await response.text() }};
+            "#
+        );
+        let result = document::eval(&script)
+            .await
+            .map_err(|e| format!("Échec de l'import : {:?}", e))?;
+        if result.is_null() {
+            return Err("Aucun fichier sélectionné".to_string());
+        }
+
+        let body = result.get("body").and_then(|b| b.as_str()).unwrap_or_default();
+        if !result.get("ok").and_then(|ok| ok.as_bool()).unwrap_or(false) {
+            return Err(format!("Archive refusée : {}", body));
+        }
+        let report: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+        Ok(format!(
+            "« {} » importé ({} sessions)",
+            report.get("project_name").and_then(|n| n.as_str()).unwrap_or("projet"),
+            report.get("sessions").and_then(|n| n.as_u64()).unwrap_or(0)
+        ))
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    Err("Import disponible uniquement dans le navigateur".to_string())
+}
+
+// Import of a project archive (BEM: c-project-import)
+#[component]
+pub fn ProjectImportButton() -> Element {
+    let mut importing = use_signal(|| false);
+    let mut status = use_signal(|| None::<Result<String, String>>);
+
+    let on_change = move |_| {
+        importing.set(true);
+        status.set(None);
+        spawn(async move {
+            let result = import_selected_archive().await;
+            let imported = result.is_ok();
+            status.set(Some(result));
+            importing.set(false);
+
+            // The sidebar lists projects once: reload it to show the new one
+            #[cfg(target_arch = "wasm32")]
+            if imported {
+                gloo_timers::future::TimeoutFuture::new(1500).await;
+                if let Some(window) = web_sys::window() {
+                    let _ = window.location().reload();
+                }
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            let _ = imported;
+        });
+    };
+
+    rsx! {
+        div { class: "c-project-import",
+            label {
+                class: "c-project-import__button",
+                title: "Importer une archive de projet (.tar.zst ou .zip) en lecture seule",
+                input {
+                    id: IMPORT_INPUT_ID,
+                    class: "c-project-import__input",
+                    r#type: "file",
+                    accept: ".zst,.zip",
+                    disabled: importing(),
+                    onchange: on_change,
+                }
+                if importing() { "Import…" } else { "📦 Importer une archive" }
+            }
+            match status() {
+                Some(Ok(message)) => rsx! { span { class: "c-project-import__status", "{message}" } },
+                Some(Err(error)) => rsx! {
+                    span { class: "c-project-import__status c-project-import__status--error", "{error}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Run settings of the chat input: model, max turns, system prompt, tools, directories, MCP
+//!
+//! Settings apply to every following message of the conversation. Fields the
+//! selected provider's CLI has no flag for are disabled and never sent.
+
+use dioxus::prelude::*;
+
+use crate::domain::models::{RunSetting, RunSettings};
+use super::CliProviderOption;
+
+/// Text field to an optional setting (blank: unset; trimmed by the server)
+fn optional_text(value: String) -> Option<String> {
+    Some(value).filter(|v| !v.trim().is_empty())
+}
+
+/// Separated field to a list (blank items dropped)
+fn list_field(value: &str, separator: char) -> Vec<String> {
+    value
+        .split(separator)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Settings a provider can receive (unsupported ones cleared)
+pub fn settings_for(settings: &RunSettings, provider: CliProviderOption) -> RunSettings {
+    let supported = |setting: RunSetting| setting.supported_by(provider.slug());
+    RunSettings {
+        model: settings.model.clone().filter(|_| supported(RunSetting::Model)),
+        max_turns: settings.max_turns.filter(|_| supported(RunSetting::MaxTurns)),
+        append_system_prompt: settings.append_system_prompt.clone().filter(|_| supported(RunSetting::AppendSystemPrompt)),
+        disallowed_tools: if supported(RunSetting::DisallowedTools) { settings.disallowed_tools.clone() } else { Vec::new() },
+        additional_dirs: if supported(RunSetting::AdditionalDirs) { settings.additional_dirs.clone() } else { Vec::new() },
+        mcp_config: settings.mcp_config.clone().filter(|_| supported(RunSetting::McpConfig)),
+    }
+}
+
+// Run settings toggle and panel (BEM: c-run-settings)
+#[component]
+pub fn RunSettingsPanel(settings: Signal<RunSettings>, provider: CliProviderOption, disabled: bool) -> Element {
+    let mut open = use_signal(|| false);
+    let active = settings_for(&settings(), provider).requested().len();
+    let unsupported = |setting: RunSetting| !setting.supported_by(provider.slug());
+    let hint = |setting: RunSetting| {
+        if unsupported(setting) {
+            format!("Non pris en charge par {}", provider.display_name())
+        } else {
+            String::new()
+        }
+    };
+
+    let current = settings();
+    let max_turns = current.max_turns.map(|n| n.to_string()).unwrap_or_default();
+    let disallowed_tools = current.disallowed_tools.join(", ");
+    let additional_dirs = current.additional_dirs.join("\n");
+
+    rsx! {
+        div { class: "c-run-settings",
+            button {
+                class: if active > 0 { "c-run-settings__toggle c-run-settings__toggle--active" } else { "c-run-settings__toggle" },
+                r#type: "button",
+                title: "Réglages d'exécution de la CLI {provider.display_name()}",
+                onclick: move |_| open.set(!open()),
+                if active > 0 { "⚙️ Réglages ({active})" } else { "⚙️ Réglages" }
+            }
+            if open() {
+                div { class: "c-run-settings__panel",
+                    label { class: "c-run-settings__field", title: hint(RunSetting::Model),
+                        span { class: "c-run-settings__label", {RunSetting::Model.label()} }
+                        input {
+                            class: "c-run-settings__input",
+                            disabled: disabled || unsupported(RunSetting::Model),
+                            placeholder: match provider {
+                                CliProviderOption::Claude => "sonnet, opus…",
+                                CliProviderOption::OpenCode => "fournisseur/modèle",
+                                CliProviderOption::Gemini => "gemini-2.5-pro…",
+                            },
+                            value: current.model.clone().unwrap_or_default(),
+                            oninput: move |evt| settings.write().model = optional_text(evt.value()),
+                        }
+                    }
+                    label { class: "c-run-settings__field", title: hint(RunSetting::MaxTurns),
+                        span { class: "c-run-settings__label", {RunSetting::MaxTurns.label()} }
+                        input {
+                            class: "c-run-settings__input c-run-settings__input--short",
+                            r#type: "number",
+                            min: "1",
+                            disabled: disabled || unsupported(RunSetting::MaxTurns),
+                            value: "{max_turns}",
+                            oninput: move |evt| settings.write().max_turns = evt.value().trim().parse().ok().filter(|n| *n > 0),
+                        }
+                    }
+                    label { class: "c-run-settings__field c-run-settings__field--wide", title: hint(RunSetting::AppendSystemPrompt),
+                        span { class: "c-run-settings__label", {RunSetting::AppendSystemPrompt.label()} }
+                        textarea {
+                            class: "c-run-settings__input",
+                            rows: "2",
+                            disabled: disabled || unsupported(RunSetting::AppendSystemPrompt),
+                            value: current.append_system_prompt.clone().unwrap_or_default(),
+                            oninput: move |evt| settings.write().append_system_prompt = optional_text(evt.value()),
+                        }
+                    }
+                    label { class: "c-run-settings__field", title: hint(RunSetting::DisallowedTools),
+                        span { class: "c-run-settings__label", {RunSetting::DisallowedTools.label()} }
+                        input {
+                            class: "c-run-settings__input",
+                            placeholder: "Bash, WebFetch",
+                            disabled: disabled || unsupported(RunSetting::DisallowedTools),
+                            value: "{disallowed_tools}",
+                            onchange: move |evt| settings.write().disallowed_tools = list_field(&evt.value(), ','),
+                        }
+                    }
+                    label { class: "c-run-settings__field", title: hint(RunSetting::McpConfig),
+                        span { class: "c-run-settings__label", {RunSetting::McpConfig.label()} }
+                        input {
+                            class: "c-run-settings__input",
+                            placeholder: "/chemin/vers/mcp.json",
+                            disabled: disabled || unsupported(RunSetting::McpConfig),
+                            value: current.mcp_config.clone().unwrap_or_default(),
+                            oninput: move |evt| settings.write().mcp_config = optional_text(evt.value()),
+                        }
+                    }
+                    label { class: "c-run-settings__field c-run-settings__field--wide", title: hint(RunSetting::AdditionalDirs),
+                        span { class: "c-run-settings__label", {RunSetting::AdditionalDirs.label()} " (un par ligne)" }
+                        textarea {
+                            class: "c-run-settings__input",
+                            rows: "2",
+                            placeholder: "/chemin/absolu",
+                            disabled: disabled || unsupported(RunSetting::AdditionalDirs),
+                            value: "{additional_dirs}",
+                            onchange: move |evt| settings.write().additional_dirs = list_field(&evt.value(), '\n'),
+                        }
+                    }
+                    div { class: "c-run-settings__footer",
+                        span { class: "c-run-settings__notice",
+                            "Chemins côté serveur. Appliqués à chaque message suivant."
+                        }
+                        button {
+                            class: "c-run-settings__reset",
+                            r#type: "button",
+                            disabled: disabled,
+                            onclick: move |_| settings.set(RunSettings::default()),
+                            "Réinitialiser"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Share link creation for the open conversation
+//!
+//! The link points to a frozen, redacted copy: the page at `/share/<id>` is
+//! public, so the form states it before creating anything. Active links are
+//! listed (and revoked) on the shares page.
+
+use dioxus::prelude::*;
+
+use crate::domain::models::{CreateShareRequest, ShareSummary};
+use crate::server_fns::create_share;
+
+/// Lifetimes offered for a link (hours, label); 0 never expires
+const EXPIRIES: [(u32, &str); 5] = [(0, "Jamais"), (1, "1 h"), (24, "24 h"), (168, "7 jours"), (720, "30 jours")];
+
+/// Absolute URL of a share page (the path alone during SSR)
+pub fn absolute_share_url(url: &str) -> String {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(origin) = web_sys::window().and_then(|window| window.location().origin().ok()) {
+        return format!("{}{}", origin, url);
+    }
+    url.to_string()
+}
+
+/// Copy a share URL to the clipboard (WASM only)
+pub async fn copy_to_clipboard(text: &str) {
+    #[cfg(target_arch = "wasm32")]
+    {
+        let text = serde_json::to_string(text).unwrap_or_default();
+        let _ = document::eval(&format!("await navigator.clipboard.writeText({text});")).await;
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    let _ = text;
+}
+
+/// 1-based form fields to a share request (empty fields: whole conversation)
+fn share_request(from: &str, to: &str, expires_in_hours: u32) -> Result<CreateShareRequest, String> {
+    let parse = |value: &str| -> Result<Option<usize>, String> {
+        match value.trim() {
+            "" => Ok(None),
+            value => value
+                .parse::<usize>()
+                .ok()
+                .filter(|n| *n >= 1)
+                .map(Some)
+                .ok_or_else(|| format!("Numéro de message invalide : {}", value)),
+        }
+    };
+
+    Ok(CreateShareRequest {
+        provider: None,
+        start: parse(from)?.map(|n| n - 1),
+        end: parse(to)?,
+        expires_in_hours: (expires_in_hours > 0).then_some(expires_in_hours),
+    })
+}
+
+// Share link form (BEM: c-share-button)
+#[component]
+pub fn ShareButton(project_name: String, session_id: String, total: usize) -> Element {
+    let mut open = use_signal(|| false);
+    let mut from = use_signal(String::new);
+    let mut to = use_signal(String::new);
+    let mut expires_in_hours = use_signal(|| 0u32);
+    let mut creating = use_signal(|| false);
+    let mut result = use_signal(|| None::<Result<ShareSummary, String>>);
+
+    let on_submit = move |evt: FormEvent| {
+        evt.prevent_default();
+        let request = match share_request(&from(), &to(), expires_in_hours()) {
+            Ok(request) => request,
+            Err(e) => {
+                result.set(Some(Err(e)));
+                return;
+            }
+        };
+        let project_name = project_name.clone();
+        let session_id = session_id.clone();
+        creating.set(true);
+        spawn(async move {
+            let share = create_share(project_name, session_id, request)
+                .await
+                .map_err(|e| format!("Partage impossible : {}", e));
+            result.set(Some(share));
+            creating.set(false);
+        });
+    };
+
+    rsx! {
+        div { class: "c-share-button",
+            button {
+                class: "c-share-button__toggle",
+                r#type: "button",
+                title: "Créer un lien public en lecture seule vers une copie masquée de la conversation",
+                onclick: move |_| {
+                    open.set(!open());
+                    result.set(None);
+                },
+                "🔗 Partager"
+            }
+            if open() {
+                form { class: "c-share-button__form",
+                    onsubmit: on_submit,
+                    label { class: "c-share-button__field",
+                        "Messages de"
+                        input {
+                            class: "c-share-button__input",
+                            r#type: "number",
+                            min: "1",
+                            max: "{total}",
+                            placeholder: "1",
+                            value: "{from}",
+                            oninput: move |evt| from.set(evt.value()),
+                        }
+                    }
+                    label { class: "c-share-button__field",
+                        "à"
+                        input {
+                            class: "c-share-button__input",
+                            r#type: "number",
+                            min: "1",
+                            max: "{total}",
+                            placeholder: "{total}",
+                            value: "{to}",
+                            oninput: move |evt| to.set(evt.value()),
+                        }
+                    }
+                    label { class: "c-share-button__field",
+                        "Expire"
+                        select {
+                            class: "c-share-button__input",
+                            onchange: move |evt| expires_in_hours.set(evt.value().parse().unwrap_or(0)),
+                            for (hours, label) in EXPIRIES {
+                                option { key: "{hours}", value: "{hours}", selected: hours == expires_in_hours(), "{label}" }
+                            }
+                        }
+                    }
+                    button {
+                        class: "c-share-button__submit",
+                        r#type: "submit",
+                        disabled: creating(),
+                        if creating() { "Création…" } else { "Créer le lien" }
+                    }
+                    p { class: "c-share-button__notice",
+                        "Toute personne ayant le lien pourra lire cette copie, secrets masqués."
+                    }
+                }
+                match result() {
+                    Some(Ok(share)) => {
+                        let url = absolute_share_url(&share.url);
+                        let copied_url = url.clone();
+                        rsx! {
+                            div { class: "c-share-button__result",
+                                input { class: "c-share-button__url", readonly: true, value: "{url}" }
+                                button {
+                                    class: "c-share-button__copy",
+                                    r#type: "button",
+                                    onclick: move |_| {
+                                        let url = copied_url.clone();
+                                        spawn(async move { copy_to_clipboard(&url).await });
+                                    },
+                                    "Copier"
+                                }
+                            }
+                        }
+                    }
+                    Some(Err(error)) => rsx! {
+                        span { class: "c-share-button__error", "{error}" }
+                    },
+                    None => rsx! {},
+                }
+            }
+        }
+    }
+}
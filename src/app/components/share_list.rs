@@ -0,0 +1,134 @@
+use dioxus::prelude::*;
+use crate::domain::models::ShareSummary;
+use crate::server_fns::{list_shares, revoke_share};
+use super::{ai_tool_icon, slug_to_ai_tool};
+use super::common::{LoadingText, ErrorMessage, EmptyState};
+use super::share_button::{absolute_share_url, copy_to_clipboard};
+
+/// Short date of a share ("15/11/2025 14:02")
+fn format_date(date: chrono::DateTime<chrono::Utc>) -> String {
+    date.format("%d/%m/%Y %H:%M").to_string()
+}
+
+// Active share links with open / copy / revoke actions (BEM: c-shares)
+#[component]
+pub fn ShareList() -> Element {
+    let mut shares_resource = use_resource(move || async move { list_shares().await });
+    let mut action_error = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+
+    let content = match &*shares_resource.read() {
+        Some(Ok(shares)) if shares.is_empty() => rsx! {
+            EmptyState {
+                icon: "🔗".to_string(),
+                title: "Aucun lien de partage".to_string(),
+                description: "Créez-en un depuis une conversation avec le bouton « Partager ».".to_string(),
+                action_text: None,
+                action_handler: None,
+            }
+        },
+        Some(Ok(shares)) => rsx! {
+            ul { class: "c-shares__list",
+                for share in shares.iter() {
+                    ShareRow {
+                        key: "{share.id}",
+                        share: share.clone(),
+                        busy: busy(),
+                        on_revoke: move |id: String| {
+                            spawn(async move {
+                                busy.set(true);
+                                if let Err(e) = revoke_share(id).await {
+                                    action_error.set(Some(format!("Révocation impossible: {}", e)));
+                                }
+                                busy.set(false);
+                                shares_resource.restart();
+                            });
+                        },
+                    }
+                }
+            }
+        },
+        Some(Err(e)) => rsx! {
+            ErrorMessage { message: format!("Impossible de charger les liens de partage: {}", e) }
+        },
+        None => rsx! {
+            LoadingText { message: "Chargement des liens de partage...".to_string() }
+        },
+    };
+
+    rsx! {
+        div { class: "c-shares",
+            if let Some(error) = action_error() {
+                ErrorMessage { message: error }
+            }
+            {content}
+        }
+    }
+}
+
+#[component]
+fn ShareRow(share: ShareSummary, busy: bool, on_revoke: EventHandler<String>) -> Element {
+    let mut confirm_revoke = use_signal(|| false);
+
+    let tool_icon = slug_to_ai_tool(&share.provider).as_ref().map(ai_tool_icon).unwrap_or("💬");
+    let first = share.start + 1;
+    let last = share.start + share.message_count;
+    let created_at = format_date(share.created_at);
+    let expires_at = share.expires_at.map(format_date);
+    let url = absolute_share_url(&share.url);
+    let copied_url = url.clone();
+    let id_for_revoke = share.id.clone();
+
+    rsx! {
+        li { class: "c-shares__item",
+            div { class: "c-shares__item-main",
+                div { class: "c-shares__item-title",
+                    "{tool_icon} {share.project_name}"
+                    span { class: "c-shares__item-session", " · {share.session_id}" }
+                }
+                div { class: "c-shares__item-meta",
+                    span { "Messages {first}–{last} · créé le {created_at}" }
+                    match expires_at {
+                        Some(expires) => rsx! { span { " · expire le {expires}" } },
+                        None => rsx! { span { " · sans expiration" } },
+                    }
+                }
+            }
+            div { class: "c-shares__item-actions",
+                if confirm_revoke() {
+                    span { class: "c-shares__confirm-text", "Le lien cessera de fonctionner. Révoquer ?" }
+                    button {
+                        class: "c-shares__action c-shares__action--danger",
+                        disabled: busy,
+                        onclick: move |_| {
+                            confirm_revoke.set(false);
+                            on_revoke.call(id_for_revoke.clone());
+                        },
+                        "Oui"
+                    }
+                    button {
+                        class: "c-shares__action",
+                        onclick: move |_| confirm_revoke.set(false),
+                        "Non"
+                    }
+                } else {
+                    a { class: "c-shares__action", href: "{url}", target: "_blank", rel: "noreferrer", "↗️ Ouvrir" }
+                    button {
+                        class: "c-shares__action",
+                        onclick: move |_| {
+                            let url = copied_url.clone();
+                            spawn(async move { copy_to_clipboard(&url).await });
+                        },
+                        "📋 Copier"
+                    }
+                    button {
+                        class: "c-shares__action c-shares__action--danger",
+                        disabled: busy,
+                        onclick: move |_| confirm_revoke.set(true),
+                        "⛔ Révoquer"
+                    }
+                }
+            }
+        }
+    }
+}
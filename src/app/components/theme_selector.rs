@@ -1,11 +1,12 @@
 use dioxus::prelude::*;
-use crate::shared::hooks::{use_theme, Theme, save_theme, save_default_theme};
+use crate::shared::hooks::{use_theme, use_redaction, Theme, save_theme, save_default_theme, save_redaction_enabled};
 
 /// Theme selector panel component
 #[component]
 pub fn ThemeSelector(is_open: Signal<bool>) -> Element {
     // Use the unified theme hook
     let (mut current_theme, mut default_dark, mut default_light) = use_theme();
+    let redaction = use_redaction();
 
     // Helper function to select theme - defined as a regular function to avoid mutable closure issues
     fn do_select_theme(
@@ -141,6 +142,27 @@ pub fn ThemeSelector(is_open: Signal<bool>) -> Element {
                         }
                     }
                 })}
+
+                // Secret masking (exports are always redacted)
+                if let Some(state) = redaction {
+                    div { class: "c-theme-selector__separator" }
+                    div { class: "c-theme-selector__group-label", "🔒 Confidentialité" }
+                    label {
+                        class: "c-theme-selector__toggle",
+                        title: "Clés d'API, tokens, clés privées... remplacés par [redacted:...] jusqu'au clic sur « Afficher »",
+                        input {
+                            r#type: "checkbox",
+                            checked: (state.enabled)(),
+                            onchange: move |_| {
+                                let mut enabled = state.enabled;
+                                let value = !enabled();
+                                enabled.set(value);
+                                spawn(save_redaction_enabled(value));
+                            },
+                        }
+                        span { "Masquer les secrets à l'écran" }
+                    }
+                }
             }
         }
     }
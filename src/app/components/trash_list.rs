@@ -0,0 +1,169 @@
+use dioxus::prelude::*;
+use crate::server_fns::{list_trash, restore_trash_item, purge_trash_item, empty_trash, TrashItemResponse};
+use super::{ai_tool_icon, ai_tool_display_name};
+use super::common::{LoadingText, ErrorMessage, EmptyState};
+
+/// Format an RFC3339 timestamp as a short local-agnostic date ("15/11/2025 14:02")
+fn format_date(rfc3339: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(rfc3339)
+        .map(|dt| dt.format("%d/%m/%Y %H:%M").to_string())
+        .unwrap_or_else(|_| rfc3339.to_string())
+}
+
+// Recycle bin list with restore / purge actions (BEM: c-trash)
+#[component]
+pub fn TrashList() -> Element {
+    let mut items_resource = use_resource(move || async move { list_trash().await });
+    let mut action_error = use_signal(|| None::<String>);
+    let mut busy = use_signal(|| false);
+
+    let content = match &*items_resource.read() {
+        Some(Ok(items)) if items.is_empty() => rsx! {
+            EmptyState {
+                icon: "🗑️".to_string(),
+                title: "Corbeille vide".to_string(),
+                description: "Les sessions et projets supprimés apparaîtront ici.".to_string(),
+                action_text: None,
+                action_handler: None,
+            }
+        },
+        Some(Ok(items)) => {
+            let count = items.len();
+            rsx! {
+                div { class: "c-trash__toolbar",
+                    span { class: "c-trash__count",
+                        if count <= 1 { "{count} élément" } else { "{count} éléments" }
+                    }
+                    button {
+                        class: "c-trash__action c-trash__action--danger",
+                        disabled: busy(),
+                        onclick: move |_| {
+                            spawn(async move {
+                                busy.set(true);
+                                if let Err(e) = empty_trash().await {
+                                    action_error.set(Some(format!("Impossible de vider la corbeille: {}", e)));
+                                }
+                                busy.set(false);
+                                items_resource.restart();
+                            });
+                        },
+                        "Vider la corbeille"
+                    }
+                }
+                ul { class: "c-trash__list",
+                    for item in items.iter() {
+                        TrashItemRow {
+                            key: "{item.id}",
+                            item: item.clone(),
+                            busy: busy(),
+                            on_restore: move |id: String| {
+                                spawn(async move {
+                                    busy.set(true);
+                                    if let Err(e) = restore_trash_item(id).await {
+                                        action_error.set(Some(format!("Restauration impossible: {}", e)));
+                                    }
+                                    busy.set(false);
+                                    items_resource.restart();
+                                });
+                            },
+                            on_purge: move |id: String| {
+                                spawn(async move {
+                                    busy.set(true);
+                                    if let Err(e) = purge_trash_item(id).await {
+                                        action_error.set(Some(format!("Suppression impossible: {}", e)));
+                                    }
+                                    busy.set(false);
+                                    items_resource.restart();
+                                });
+                            },
+                        }
+                    }
+                }
+            }
+        }
+        Some(Err(e)) => rsx! {
+            ErrorMessage { message: format!("Impossible de charger la corbeille: {}", e) }
+        },
+        None => rsx! {
+            LoadingText { message: "Chargement de la corbeille...".to_string() }
+        },
+    };
+
+    rsx! {
+        div { class: "c-trash",
+            if let Some(error) = action_error() {
+                ErrorMessage { message: error }
+            }
+            {content}
+        }
+    }
+}
+
+#[component]
+fn TrashItemRow(
+    item: TrashItemResponse,
+    busy: bool,
+    on_restore: EventHandler<String>,
+    on_purge: EventHandler<String>,
+) -> Element {
+    let mut confirm_purge = use_signal(|| false);
+
+    let kind_label = if item.kind == "project" { "Projet" } else { "Session" };
+    let tool_icon = ai_tool_icon(&item.ai_tool);
+    let tool_name = ai_tool_display_name(&item.ai_tool);
+    let deleted_at = format_date(&item.deleted_at);
+    let expires_at = item.expires_at.as_deref().map(format_date);
+    let original = item.original_paths.first().cloned().unwrap_or_default();
+    let id_for_restore = item.id.clone();
+    let id_for_purge = item.id.clone();
+
+    rsx! {
+        li { class: "c-trash__item",
+            div { class: "c-trash__item-main",
+                div { class: "c-trash__item-title",
+                    span { class: "c-trash__item-kind", "{tool_icon} {kind_label}" }
+                    " {item.label}"
+                }
+                div { class: "c-trash__item-meta",
+                    span { "{tool_name} · supprimé le {deleted_at}" }
+                    if let Some(expires) = expires_at {
+                        span { " · purge auto le {expires}" }
+                    }
+                }
+                div { class: "c-trash__item-path", title: "{original}", "{original}" }
+            }
+            div { class: "c-trash__item-actions",
+                if confirm_purge() {
+                    span { class: "c-trash__confirm-text", "Supprimer définitivement ?" }
+                    button {
+                        class: "c-trash__action c-trash__action--danger",
+                        disabled: busy,
+                        onclick: move |_| {
+                            confirm_purge.set(false);
+                            on_purge.call(id_for_purge.clone());
+                        },
+                        "Oui"
+                    }
+                    button {
+                        class: "c-trash__action",
+                        onclick: move |_| confirm_purge.set(false),
+                        "Non"
+                    }
+                } else {
+                    button {
+                        class: "c-trash__action",
+                        disabled: busy,
+                        onclick: move |_| on_restore.call(id_for_restore.clone()),
+                        "↩️ Restaurer"
+                    }
+                    button {
+                        class: "c-trash__action c-trash__action--danger",
+                        disabled: busy,
+                        onclick: move |_| confirm_purge.set(true),
+                        "🗑️ Purger"
+                    }
+                }
+            }
+        }
+    }
+}
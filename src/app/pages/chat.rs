@@ -98,7 +98,7 @@ pub fn ChatPage(
 
                 // 4. Build request WITH images
                 let mut chat_request = ChatRequest::new(message_content.clone(), request_id.clone())
-                    .with_working_directory(project_name.clone());
+                    .with_project_name(project_name.clone());
 
                 // Add images if any
                 if !image_paths.is_empty() {
@@ -7,10 +7,12 @@ use crate::app::components::SearchBar;
 use crate::app::pages::ChatPage;
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::JsCast;
-use crate::app::components::{ThemeToggle, ThemeSelector, SettingsButton, AiToolLanding, slug_to_ai_tool, ai_tool_display_name, ai_tool_icon, ai_tool_to_slug, SessionsLoading};
+use crate::app::components::{ThemeToggle, ThemeSelector, SettingsButton, AiToolLanding, slug_to_ai_tool, ai_tool_display_name, ai_tool_icon, ai_tool_to_slug, SessionsLoading, TrashList, ShareList, GeminiPathMapper, ProjectArchiveLink, ProjectImportButton};
 use crate::server_fns::{get_sessions_summaries, delete_session, get_projects, get_project, SessionSummaryResponse};
 use crate::domain::models::AiTool;
+use crate::shared::hooks::use_redaction_provider;
 use chrono::{DateTime, Utc, Duration, Datelike};
+use std::collections::{HashMap, HashSet};
 
 use dioxus::prelude::*;
 use dioxus::document;
@@ -84,7 +86,8 @@ fn format_relative_time(timestamp: &DateTime<Utc>, now: &DateTime<Utc>) -> Strin
 }
 
 // Helper function to group sessions by time period
-fn group_sessions_for_display(sessions: &[SessionSummaryResponse], now: &DateTime<Utc>) -> Vec<(String, Vec<SessionSummaryResponse>)> {
+// Forks follow their parent (with their depth in the tree) instead of being dated on their own.
+fn group_sessions_for_display(sessions: &[SessionSummaryResponse], now: &DateTime<Utc>) -> Vec<(String, Vec<(SessionSummaryResponse, usize)>)> {
     let today = now.date_naive();
     let yesterday = today - Duration::days(1);
     let week_ago = today - Duration::days(7);
@@ -100,7 +103,18 @@ fn group_sessions_for_display(sessions: &[SessionSummaryResponse], now: &DateTim
     let mut sorted_sessions: Vec<_> = sessions.to_vec();
     sorted_sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
 
+    // Forks whose parent is listed go under it
+    let listed: HashSet<String> = sorted_sessions.iter().map(|s| s.session_id.clone()).collect();
+    let mut forks: HashMap<String, Vec<SessionSummaryResponse>> = HashMap::new();
+    let mut roots = Vec::new();
     for session in sorted_sessions {
+        match session.parent_session_id.clone().filter(|parent| listed.contains(parent)) {
+            Some(parent) => forks.entry(parent).or_default().push(session),
+            None => roots.push(session),
+        }
+    }
+
+    for session in roots {
         let session_date = chrono::DateTime::parse_from_rfc3339(&session.updated_at)
             .map(|dt| dt.with_timezone(&Utc).date_naive())
             .unwrap_or(today);
@@ -120,24 +134,43 @@ fn group_sessions_for_display(sessions: &[SessionSummaryResponse], now: &DateTim
 
     let mut groups = Vec::new();
     if !today_sessions.is_empty() {
-        groups.push(("Aujourd'hui".to_string(), today_sessions));
+        groups.push(("Aujourd'hui".to_string(), nest_forks(today_sessions, &forks)));
     }
     if !yesterday_sessions.is_empty() {
-        groups.push(("Hier".to_string(), yesterday_sessions));
+        groups.push(("Hier".to_string(), nest_forks(yesterday_sessions, &forks)));
     }
     if !this_week_sessions.is_empty() {
-        groups.push(("Cette semaine".to_string(), this_week_sessions));
+        groups.push(("Cette semaine".to_string(), nest_forks(this_week_sessions, &forks)));
     }
     if !this_month_sessions.is_empty() {
-        groups.push(("Ce mois".to_string(), this_month_sessions));
+        groups.push(("Ce mois".to_string(), nest_forks(this_month_sessions, &forks)));
     }
     if !older_sessions.is_empty() {
-        groups.push(("Plus ancien".to_string(), older_sessions));
+        groups.push(("Plus ancien".to_string(), nest_forks(older_sessions, &forks)));
     }
 
     groups
 }
 
+// Helper function to place each session's forks right after it (depth-first, newest first)
+fn nest_forks(roots: Vec<SessionSummaryResponse>, forks: &HashMap<String, Vec<SessionSummaryResponse>>) -> Vec<(SessionSummaryResponse, usize)> {
+    let mut nested = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack: Vec<(SessionSummaryResponse, usize)> = roots.into_iter().rev().map(|s| (s, 0)).collect();
+
+    while let Some((session, depth)) = stack.pop() {
+        if !seen.insert(session.session_id.clone()) {
+            continue;
+        }
+        if let Some(children) = forks.get(&session.session_id) {
+            stack.extend(children.iter().rev().map(|child| (child.clone(), depth + 1)));
+        }
+        nested.push((session, depth));
+    }
+
+    nested
+}
+
 // SessionItem component for Project page (Claude.ai /recents style)
 #[component]
 fn SessionItem(
@@ -155,6 +188,9 @@ fn SessionItem(
     on_toggle_select: Option<EventHandler<String>>,
     /// Callback when session is deleted (legacy mode only)
     on_deleted: Option<EventHandler<String>>,
+    /// Depth in the fork tree (0 for a session that is not shown under a parent)
+    #[props(default = 0)]
+    depth: usize,
 ) -> Element {
     let mut show_confirm = use_signal(|| false);
     let mut is_deleting = use_signal(|| false);
@@ -187,14 +223,25 @@ fn SessionItem(
     let session_id_for_delete = session.session_id.clone();
     let encoded_name_for_delete = encoded_name.clone();
 
-    let item_class = if is_selected {
-        "c-session-item c-session-item--selected"
-    } else {
-        "c-session-item"
+    let item_class = match (is_selected, session.parent_session_id.is_some()) {
+        (true, true) => "c-session-item c-session-item--selected c-session-item--fork",
+        (true, false) => "c-session-item c-session-item--selected",
+        (false, true) => "c-session-item c-session-item--fork",
+        (false, false) => "c-session-item",
     };
 
+    // Fork origin: "⑂ branche de 1a2b3c4d au message 12"
+    let fork_origin = session.parent_session_id.as_ref().map(|parent| {
+        let short = &parent[..8.min(parent.len())];
+        match session.parent_message {
+            Some(index) => format!("⑂ branche de {} au message {}", short, index + 1),
+            None => format!("⑂ branche de {}", short),
+        }
+    });
+
     rsx! {
         div { class: "{item_class}",
+            style: "--fork-depth: {depth}",
             // Checkbox in selection mode
             if selection_mode {
                 label {
@@ -221,12 +268,15 @@ fn SessionItem(
                 to: Route::Session {
                     tool: tool.clone(),
                     project_name: project_name.clone(),
-                    session_id: session_id.clone()
+                    session_id: session_id.clone(),
+                    message: String::new()
                 },
 
                 // Icon (hidden in selection mode)
                 if !selection_mode {
-                    div { class: "c-session-item__icon", "💬" }
+                    div { class: "c-session-item__icon",
+                        if fork_origin.is_some() { "⑂" } else { "💬" }
+                    }
                 }
 
                 // Content
@@ -234,6 +284,10 @@ fn SessionItem(
                     div { class: "c-session-item__title", "{title}" }
                     div { class: "c-session-item__meta",
                         span { "{session.message_count} messages" }
+                        if let Some(origin) = &fork_origin {
+                            span { class: "c-session-item__meta-separator", "·" }
+                            span { class: "c-session-item__fork", "{origin}" }
+                        }
                     }
                 }
 
@@ -306,16 +360,26 @@ pub enum Route {
     #[route("/")]
     Home {},
 
+    // Recycle bin (deleted sessions and projects)
+    #[route("/trash")]
+    Trash {},
+
+    // Active share links
+    #[route("/shares")]
+    Shares {},
+
     // Tool-specific routes with :tool prefix
     #[route("/:tool")]
     ToolHome { tool: String },
     #[route("/:tool/projects/:project_name")]
     Project { tool: String, project_name: String },
-    #[route("/:tool/projects/:project_name/sessions/:session_id")]
+    // ?message=N opens the session on message N (1-based)
+    #[route("/:tool/projects/:project_name/sessions/:session_id?:message")]
     Session {
         tool: String,
         project_name: String,
-        session_id: String
+        session_id: String,
+        message: String
     },
     #[route("/:tool/projects/:project_name/chat")]
     Chat { tool: String, project_name: String },
@@ -323,6 +387,11 @@ pub enum Route {
     // Legacy routes (kept for backward compatibility)
     #[route("/vision")]
     Vision {},
+    #[end_layout]
+
+    // Login page (outside the app layout, reachable without a session)
+    #[route("/login?:next&:error")]
+    Login { next: String, error: String },
 }
 
 #[component]
@@ -342,6 +411,9 @@ fn Layout() -> Element {
     // Use asset!() macro to ensure CSS is bundled and served correctly
     const BUNDLE_CSS: Asset = asset!("/assets/bundle.css");
 
+    // Secret masking setting, read by the settings panel and MessageItem
+    use_redaction_provider();
+
     rsx! {
         document::Link {
             rel: "stylesheet",
@@ -382,9 +454,20 @@ fn AppNavbar() -> Element {
                 "📜 CLI Session Hub"
             }
 
-            // Right: Theme toggle only
+            // Right: Theme toggle and logout
             div { class: "c-navbar__actions",
                 ThemeToggle {}
+                form {
+                    class: "c-navbar__logout",
+                    method: "post",
+                    action: "/api/auth/logout",
+                    button {
+                        r#type: "submit",
+                        class: "c-navbar__logout-button",
+                        title: "Se déconnecter",
+                        "Déconnexion"
+                    }
+                }
             }
         }
     }
@@ -443,6 +526,18 @@ fn AppSidebar() -> Element {
                     span { class: "c-sidebar__nav-icon", "🏠" }
                     span { class: "c-sidebar__nav-text", "Accueil" }
                 }
+                Link {
+                    to: Route::Trash {},
+                    class: "c-sidebar__nav-item",
+                    span { class: "c-sidebar__nav-icon", "🗑️" }
+                    span { class: "c-sidebar__nav-text", "Corbeille" }
+                }
+                Link {
+                    to: Route::Shares {},
+                    class: "c-sidebar__nav-item",
+                    span { class: "c-sidebar__nav-icon", "🔗" }
+                    span { class: "c-sidebar__nav-text", "Partages" }
+                }
             }
 
             // Search input (functional)
@@ -492,6 +587,7 @@ fn AppSidebar() -> Element {
 
             // Footer with settings button
             div { class: "c-sidebar__footer",
+                ProjectImportButton {}
                 SettingsButton {
                     on_click: move |_| settings_open.set(true),
                 }
@@ -532,6 +628,9 @@ fn SidebarToolSection(tool: AiTool, projects: Vec<crate::server_fns::ProjectResp
                     "data-tool": "{tool_slug}",
                     span { class: "c-sidebar__item-icon", "📁" }
                     span { class: "c-sidebar__item-text", "{project.name}" }
+                    for root in project.roots.iter() {
+                        span { class: "c-root-badge", title: "Racine d'historique : {root}", "{root}" }
+                    }
                     span { class: "c-sidebar__count", "{project.session_count}" }
                 }
             }
@@ -700,6 +799,8 @@ fn Project(tool: String, project_name: String) -> Element {
             div { class: "project-page__header",
                 h1 { "Project: {display_name}" }
 
+                ProjectArchiveLink { tool: current_tool(), project_name: current_project() }
+
                 // Show "Sélectionner" link when not in selection mode
                 if !selection_mode() {
                     button {
@@ -710,6 +811,11 @@ fn Project(tool: String, project_name: String) -> Element {
                 }
             }
 
+            // Unresolved Gemini hash: let the user pick the directory
+            if current_tool() == "gemini" {
+                GeminiPathMapper { key: "{current_project()}", hash: current_project() }
+            }
+
             match &*sessions_resource.read() {
                 Some(Ok(sessions)) => {
                     // Filter out deleted sessions
@@ -825,9 +931,10 @@ fn Project(tool: String, project_name: String) -> Element {
                                 for (group_name, group_sessions) in grouped.iter() {
                                     if !group_sessions.is_empty() {
                                         div { class: "c-sessions__group-header", "{group_name}" }
-                                        for session in group_sessions {
+                                        for (session, depth) in group_sessions {
                                             SessionItem {
                                                 session: session.clone(),
+                                                depth: *depth,
                                                 tool: current_tool(),
                                                 project_name: current_project(),
                                                 encoded_name: project_name_for_delete.clone(),
@@ -857,9 +964,10 @@ fn Project(tool: String, project_name: String) -> Element {
 
 
 #[component]
-fn Session(tool: String, project_name: String, session_id: String) -> Element {
+fn Session(tool: String, project_name: String, session_id: String, message: String) -> Element {
     let tool_clone = tool.clone();
     let project_name_clone = project_name.clone();
+    let jump_to = message.parse::<usize>().ok().and_then(|n| n.checked_sub(1));
 
     rsx! {
         div { class: "c-session-page",
@@ -875,10 +983,91 @@ fn Session(tool: String, project_name: String, session_id: String) -> Element {
             // Main conversation area - full width
             main { class: "c-session-page__content",
                 ConversationView {
-                    key: "{session_id}",
+                    key: "{session_id}-{message}",
                     project_name: project_name.clone(),
-                    session_id: session_id.clone()
+                    session_id: session_id.clone(),
+                    jump_to
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Trash() -> Element {
+    rsx! {
+        div { class: "main-content",
+            div { class: "container",
+                header { class: "page-header",
+                    h1 { class: "page-title", "🗑️ Corbeille" }
+                    p { class: "page-description",
+                        "Restaurez ou supprimez définitivement les sessions et projets supprimés"
+                    }
+                }
+
+                main {
+                    TrashList {}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn Shares() -> Element {
+    rsx! {
+        div { class: "main-content",
+            div { class: "container",
+                header { class: "page-header",
+                    h1 { class: "page-title", "🔗 Liens de partage" }
+                    p { class: "page-description",
+                        "Copies en lecture seule, secrets masqués, accessibles sans connexion à toute personne ayant le lien"
+                    }
+                }
+
+                main {
+                    ShareList {}
+                }
+            }
+        }
+    }
+}
+
+/// Login form, posted natively to /api/auth/login (works before WASM hydration)
+#[component]
+fn Login(next: String, error: String) -> Element {
+    const BUNDLE_CSS: Asset = asset!("/assets/bundle.css");
+
+    let next = if next.is_empty() { "/".to_string() } else { next };
+
+    rsx! {
+        document::Link {
+            rel: "stylesheet",
+            href: BUNDLE_CSS
+        },
+        div { class: "c-login",
+            form {
+                class: "c-login__card",
+                method: "post",
+                action: "/api/auth/login",
+                h1 { class: "c-login__title", "📜 CLI Session Hub" }
+                p { class: "c-login__description",
+                    "Connectez-vous avec votre mot de passe ou votre jeton d'accès"
+                }
+                if !error.is_empty() {
+                    div { class: "c-login__error", "Identifiants invalides" }
+                }
+                input { r#type: "hidden", name: "next", value: "{next}" }
+                input {
+                    r#type: "password",
+                    class: "c-login__input",
+                    name: "secret",
+                    placeholder: "Mot de passe ou jeton",
+                    autocomplete: "current-password",
+                    autofocus: true,
+                    required: true,
                 }
+                button { r#type: "submit", class: "c-login__submit", "Se connecter" }
             }
         }
     }
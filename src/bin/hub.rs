@@ -0,0 +1,29 @@
+//! `hub` command-line front end: list, show, search and export sessions, or run the API server
+//!
+//! Run with: cargo run --bin hub -- projects list
+//! Paths come from ~/.claude-viewer/config.toml, like the servers.
+
+use cli_session_hub_viewer::cli::run_cli;
+use cli_session_hub_viewer::config::init_config;
+
+#[tokio::main]
+async fn main() {
+    // Logs go to stderr, so that --json output stays parseable
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing::Level::WARN.into()),
+        )
+        .init();
+
+    if let Err(e) = init_config() {
+        eprintln!("error: invalid configuration: {}", e);
+        std::process::exit(1);
+    }
+
+    if let Err(e) = run_cli().await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
@@ -7,6 +7,7 @@ use async_trait::async_trait;
 
 use super::detection::CliDetectionResult;
 use crate::cli::traits::{CliExecutor, CliProcess, CliProvider};
+use crate::domain::models::StreamEvent;
 
 // Re-export types from traits for backward compatibility
 pub use crate::cli::traits::{ExecuteOptions, ExecutorError, PermissionMode};
@@ -83,6 +84,9 @@ impl CliExecutor for ClaudeExecutor {
         if let Some(session_id) = &options.session_id {
             args.push("--resume".to_string());
             args.push(session_id.clone());
+            if options.fork_session {
+                args.push("--fork-session".to_string());
+            }
         }
 
         // Add allowed tools if specified
@@ -99,6 +103,34 @@ impl CliExecutor for ClaudeExecutor {
             args.push(options.permission_mode.as_str().to_string());
         }
 
+        // Run settings
+        if let Some(model) = &options.model {
+            args.push("--model".to_string());
+            args.push(model.clone());
+        }
+        if let Some(max_turns) = options.max_turns {
+            args.push("--max-turns".to_string());
+            args.push(max_turns.to_string());
+        }
+        if let Some(prompt) = &options.append_system_prompt {
+            args.push("--append-system-prompt".to_string());
+            args.push(prompt.clone());
+        }
+        if let Some(tools) = &options.disallowed_tools {
+            for tool in tools {
+                args.push("--disallowed-tools".to_string());
+                args.push(tool.clone());
+            }
+        }
+        for dir in &options.additional_dirs {
+            args.push("--add-dir".to_string());
+            args.push(dir.display().to_string());
+        }
+        if let Some(config) = &options.mcp_config {
+            args.push("--mcp-config".to_string());
+            args.push(config.display().to_string());
+        }
+
         // Note: Images are now included in the prompt text above
         // Claude will use Read tool to view them
 
@@ -108,11 +140,21 @@ impl CliExecutor for ClaudeExecutor {
 }
 
 /// Stream response types (matching claude-code-webui format)
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// One per line of the `/api/v1/chat/native` NDJSON body.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum StreamResponse {
     /// Raw JSON from Claude SDK
-    ClaudeJson { data: serde_json::Value },
+    ClaudeJson {
+        /// Claude CLI `stream-json` message (system, assistant, user or result)
+        data: serde_json::Value,
+    },
+    /// Provider-neutral event, from the stream adapter of the CLI
+    Event {
+        /// Inlined so the stream-event JSON Schema stands alone
+        #[schema(inline)]
+        event: StreamEvent,
+    },
     /// Error occurred
     Error { error: String },
     /// Stream completed successfully
@@ -126,6 +168,10 @@ impl StreamResponse {
         Self::ClaudeJson { data }
     }
 
+    pub fn event(event: StreamEvent) -> Self {
+        Self::Event { event }
+    }
+
     pub fn error(msg: impl Into<String>) -> Self {
         Self::Error { error: msg.into() }
     }
@@ -159,6 +205,33 @@ mod tests {
         assert_eq!(opts.session_id, Some("abc123".to_string()));
         assert_eq!(opts.working_directory, Some(PathBuf::from("/tmp")));
         assert_eq!(opts.permission_mode, PermissionMode::Plan);
+        assert!(!opts.fork_session);
+    }
+
+    #[test]
+    fn test_fork_session_args() {
+        let executor = ClaudeExecutor::with_path("/usr/bin/claude");
+        let args = executor.build_args(&ExecuteOptions::new("Hi").with_session_id("abc").with_fork_session());
+        let resume = args.iter().position(|a| a == "--resume").unwrap();
+        assert_eq!(&args[resume + 1..resume + 3], ["abc", "--fork-session"]);
+    }
+
+    #[test]
+    fn test_run_settings_args() {
+        let executor = ClaudeExecutor::with_path("/usr/bin/claude");
+        let options = ExecuteOptions::new("Hi")
+            .with_model("opus")
+            .with_max_turns(5)
+            .with_append_system_prompt("Answer in French")
+            .with_disallowed_tools(vec!["Bash".to_string(), "WebFetch".to_string()])
+            .with_additional_dir("/srv/shared")
+            .with_mcp_config("/etc/mcp.json");
+
+        let args = executor.build_args(&options).join(" ");
+        assert!(args.contains("--model opus --max-turns 5 --append-system-prompt Answer in French"));
+        assert!(args.contains("--disallowed-tools Bash --disallowed-tools WebFetch"));
+        assert!(args.ends_with("--add-dir /srv/shared --mcp-config /etc/mcp.json"));
+        assert!(executor.check_run_settings(&options).is_ok());
     }
 
     #[test]
@@ -166,5 +239,8 @@ mod tests {
         let resp = StreamResponse::claude_json(serde_json::json!({"type": "system"}));
         let json = resp.to_ndjson().unwrap();
         assert!(json.contains("claude_json"));
+
+        let resp = StreamResponse::event(StreamEvent::TextDelta { text: "Hi".to_string() });
+        assert_eq!(resp.to_ndjson().unwrap(), r#"{"type":"event","event":{"type":"text_delta","text":"Hi"}}"#);
     }
 }
@@ -9,8 +9,12 @@ pub mod executor;
 
 pub mod session_manager;
 
+pub mod stream;
+
 pub use detection::*;
 
 pub use executor::*;
 
 pub use session_manager::*;
+
+pub use stream::*;
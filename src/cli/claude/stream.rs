@@ -0,0 +1,179 @@
+//! Claude `--output-format stream-json` adapter
+//!
+//! One JSON message per line: `system` (init), `assistant` (text, thinking and
+//! tool_use blocks), `user` (tool_result blocks) and a final `result`.
+
+use serde_json::Value;
+
+use crate::cli::traits::{parse_json_line, CliProvider, StreamAdapter};
+use crate::domain::models::StreamEvent;
+
+/// Stream adapter for Claude CLI
+#[derive(Debug, Default)]
+pub struct ClaudeStreamAdapter;
+
+impl StreamAdapter for ClaudeStreamAdapter {
+    fn parse_line(&mut self, line: &str) -> Vec<StreamEvent> {
+        let Some(json) = parse_json_line(CliProvider::Claude, line) else {
+            return Vec::new();
+        };
+        let text = |key: &str| json.get(key).and_then(Value::as_str).map(str::to_string);
+
+        match json.get("type").and_then(Value::as_str) {
+            Some("system") if json.get("subtype").and_then(Value::as_str) == Some("init") => {
+                let Some(session_id) = text("session_id") else {
+                    return Vec::new();
+                };
+                vec![StreamEvent::SessionStarted {
+                    session_id,
+                    cwd: text("cwd"),
+                    model: text("model"),
+                    tools: string_list(json.get("tools")),
+                }]
+            }
+            Some("assistant") => content_blocks(&json).iter().filter_map(assistant_block).flatten().collect(),
+            Some("user") => content_blocks(&json).iter().filter_map(tool_result).collect(),
+            Some("result") => {
+                let mut events = Vec::new();
+                if let Some(usage) = json.get("usage") {
+                    let tokens = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+                    // Cached prompt tokens are reported apart; they are input all the same
+                    events.push(StreamEvent::Usage {
+                        input_tokens: tokens("input_tokens")
+                            + tokens("cache_creation_input_tokens")
+                            + tokens("cache_read_input_tokens"),
+                        output_tokens: tokens("output_tokens"),
+                    });
+                }
+                let success = text("subtype").as_deref() == Some("success")
+                    && !json.get("is_error").and_then(Value::as_bool).unwrap_or(false);
+                events.push(StreamEvent::Result {
+                    success,
+                    message: if success { None } else { text("result").or_else(|| text("subtype")) },
+                    duration_ms: json.get("duration_ms").and_then(Value::as_u64),
+                });
+                events
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn content_blocks(json: &Value) -> &[Value] {
+    json.pointer("/message/content").and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
+
+fn string_list(value: Option<&Value>) -> Vec<String> {
+    value
+        .and_then(Value::as_array)
+        .map(|items| items.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Events of an assistant content block (a refused tool call also asks for permission)
+fn assistant_block(block: &Value) -> Option<Vec<StreamEvent>> {
+    let field = |key: &str| block.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let event = match block.get("type").and_then(Value::as_str)? {
+        "text" => StreamEvent::TextDelta { text: field("text")? },
+        "thinking" => StreamEvent::Thinking { text: field("thinking")? },
+        "tool_use" => {
+            let name = field("name")?;
+            if let Some(error) = block.get("error").filter(|e| e.get("type").and_then(Value::as_str) == Some("PermissionError")) {
+                return Some(vec![StreamEvent::PermissionRequired {
+                    tool: name,
+                    patterns: string_list(error.get("patterns")),
+                }]);
+            }
+            StreamEvent::ToolCall {
+                id: field("id"),
+                name,
+                input: block.get("input").cloned().unwrap_or_else(|| serde_json::json!({})),
+            }
+        }
+        _ => return None,
+    };
+    Some(vec![event])
+}
+
+/// Tool result block of a user message (content is a string or text blocks)
+fn tool_result(block: &Value) -> Option<StreamEvent> {
+    if block.get("type").and_then(Value::as_str) != Some("tool_result") {
+        return None;
+    }
+    let output = match block.get("content") {
+        Some(Value::String(text)) => text.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    };
+    Some(StreamEvent::ToolResult {
+        id: block.get("tool_use_id").and_then(Value::as_str).map(str::to_string),
+        output,
+        is_error: block.get("is_error").and_then(Value::as_bool).unwrap_or(false),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(line: Value) -> Vec<StreamEvent> {
+        ClaudeStreamAdapter.parse_line(&line.to_string())
+    }
+
+    #[test]
+    fn test_init_and_assistant_blocks() {
+        let events = parse(json!({
+            "type": "system", "subtype": "init", "session_id": "s1", "cwd": "/tmp/demo",
+            "model": "claude-sonnet-4", "tools": ["Bash", "Read"],
+        }));
+        assert_eq!(events, [StreamEvent::SessionStarted {
+            session_id: "s1".to_string(),
+            cwd: Some("/tmp/demo".to_string()),
+            model: Some("claude-sonnet-4".to_string()),
+            tools: vec!["Bash".to_string(), "Read".to_string()],
+        }]);
+
+        let events = parse(json!({"type": "assistant", "message": {"content": [
+            {"type": "thinking", "thinking": "Look first"},
+            {"type": "text", "text": "Reading it."},
+            {"type": "tool_use", "id": "t1", "name": "Read", "input": {"file_path": "a.rs"}},
+        ]}}));
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], StreamEvent::Thinking { text: "Look first".to_string() });
+        assert_eq!(events[1], StreamEvent::TextDelta { text: "Reading it.".to_string() });
+        assert!(matches!(&events[2], StreamEvent::ToolCall { id: Some(id), name, .. } if id == "t1" && name == "Read"));
+    }
+
+    #[test]
+    fn test_tool_result_and_result() {
+        let events = parse(json!({"type": "user", "message": {"content": [
+            {"type": "tool_result", "tool_use_id": "t1", "content": [{"type": "text", "text": "fn main() {}"}]},
+        ]}}));
+        assert_eq!(events, [StreamEvent::ToolResult {
+            id: Some("t1".to_string()),
+            output: "fn main() {}".to_string(),
+            is_error: false,
+        }]);
+
+        let events = parse(json!({
+            "type": "result", "subtype": "error_max_turns", "is_error": true, "duration_ms": 1200,
+            "usage": {"input_tokens": 10, "cache_read_input_tokens": 90, "output_tokens": 5},
+        }));
+        assert_eq!(events, [
+            StreamEvent::Usage { input_tokens: 100, output_tokens: 5 },
+            StreamEvent::Result {
+                success: false,
+                message: Some("error_max_turns".to_string()),
+                duration_ms: Some(1200),
+            },
+        ]);
+
+        assert!(ClaudeStreamAdapter.parse_line("not json").is_empty());
+    }
+}
@@ -0,0 +1,664 @@
+//! `hub` subcommands
+//!
+//! Everything except `serve` reads the provider history files directly: no
+//! server or database needed. Text output is meant for a terminal (bold and dim
+//! only when stdout is one); `--json` prints the same types as the REST API.
+
+use std::io::{IsTerminal, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+
+use chrono::{DateTime, Local, Utc};
+use rayon::prelude::*;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+use super::{ExportKind, Provider};
+use crate::config::app_config::{redactor, IMPORTED_ROOT_LABEL};
+use crate::config::config;
+use crate::domain::models::{AiTool, Conversation, Message};
+use crate::handlers::projects::ProjectResponse;
+use crate::history::archive::{export_project, import_archive, ArchiveFormat};
+use crate::history::export::{export_conversation, ExportFormat};
+use crate::history::{
+    find_conversation_file, list_project_summaries_for_tool, list_projects as history_projects,
+    load_conversation_file, viewer_messages, ConversationHistory, ProjectInfo,
+};
+use crate::shared::hooks::Theme;
+
+/// Characters of context kept on each side of a search match
+const SNIPPET_CONTEXT: usize = 60;
+
+/// Tool inputs and outputs longer than this are cut in `show`
+const TOOL_TEXT_LIMIT: usize = 2000;
+
+/// How results are printed
+pub struct Output {
+    json: bool,
+    color: bool,
+}
+
+impl Output {
+    pub fn new(json: bool) -> Self {
+        Self {
+            json,
+            color: !json && std::io::stdout().is_terminal(),
+        }
+    }
+
+    /// Text without escape codes (files)
+    pub fn plain() -> Self {
+        Self { json: false, color: false }
+    }
+
+    fn print_json<T: Serialize>(&self, value: &T) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize: {}", e))?;
+        println!("{}", json);
+        Ok(())
+    }
+
+    fn bold(&self, text: &str) -> String {
+        self.paint("1", text)
+    }
+
+    fn dim(&self, text: &str) -> String {
+        self.paint("2", text)
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        if self.color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+/// A message matching `hub search`
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub project: String,
+    pub project_name: String,
+    pub provider: &'static str,
+    pub session_id: String,
+    /// Message number in the session (0 = oldest)
+    pub index: usize,
+    pub role: &'static str,
+    pub timestamp: DateTime<Utc>,
+    pub snippet: String,
+}
+
+/// hub projects list
+pub fn list_projects(output: &Output, provider: Option<Provider>, search: Option<&str>) -> Result<(), String> {
+    let mut projects = projects_for(provider)?;
+    if let Some(search) = search.filter(|s| !s.is_empty()) {
+        let search = search.to_lowercase();
+        projects.retain(|p| p.name.to_lowercase().contains(&search) || p.path.to_lowercase().contains(&search));
+    }
+
+    if output.json {
+        let projects: Vec<ProjectResponse> = projects.into_iter().map(ProjectResponse::from).collect();
+        return output.print_json(&projects);
+    }
+
+    for project in &projects {
+        println!(
+            "{:<8} {:>5}  {}  {}  {}",
+            Provider::from_ai_tool(&project.ai_tool).slug(),
+            project.session_count,
+            format_time(&project.last_updated),
+            output.bold(&project.name),
+            output.dim(&project.encoded_name),
+        );
+    }
+    Ok(())
+}
+
+/// hub sessions list <project>
+pub fn list_sessions(output: &Output, provider: Option<Provider>, project: &str) -> Result<(), String> {
+    let project = resolve_project(provider, project)?;
+    let mut sessions = list_project_summaries_for_tool(&project.encoded_name, provider.map(Provider::slug))?;
+    sessions.sort_by(|a, b| b.last_time.cmp(&a.last_time));
+
+    if output.json {
+        return output.print_json(&sessions);
+    }
+
+    for session in &sessions {
+        let last_time = DateTime::parse_from_rfc3339(&session.last_time)
+            .map(|t| format_time(&t.with_timezone(&Utc)))
+            .unwrap_or_else(|_| session.last_time.clone());
+        println!(
+            "{}  {}  {:>5} msgs  {}",
+            output.bold(&session.session_id),
+            last_time,
+            session.message_count,
+            output.dim(&truncate(&one_line(&session.last_message_preview), 60)),
+        );
+    }
+    Ok(())
+}
+
+/// hub show <session>
+pub fn show(output: &Output, provider: Option<Provider>, session: &str, project: Option<&str>) -> Result<(), String> {
+    let (project, ai_tool, conversation) = load_session(provider, session, project)?;
+    if output.json {
+        return output.print_json(&conversation);
+    }
+    print!("{}", render_conversation(output, &project, &ai_tool, &conversation));
+    Ok(())
+}
+
+/// hub search <query>
+pub fn search(
+    output: &Output,
+    provider: Option<Provider>,
+    query: &str,
+    project: Option<&str>,
+    limit: usize,
+) -> Result<(), String> {
+    let pattern = query_regex(query)?;
+    let projects = match project {
+        Some(project) => vec![resolve_project(provider, project)?],
+        None => projects_for(provider)?,
+    };
+
+    // One entry per (project, session); projects are listed once per provider
+    let mut sessions = Vec::new();
+    for project in &projects {
+        let slug = Provider::from_ai_tool(&project.ai_tool).slug();
+        for summary in list_project_summaries_for_tool(&project.encoded_name, Some(slug))? {
+            sessions.push((project, summary.session_id));
+        }
+    }
+
+    let mut hits: Vec<SearchHit> = sessions
+        .par_iter()
+        .flat_map_iter(|(project, session_id)| {
+            let messages = find_conversation_file(&project.encoded_name, session_id)
+                .and_then(|(ai_tool, path)| load_conversation_file(&ai_tool, &path, session_id).ok())
+                .map(|history| viewer_messages(&history.messages, history.created_at))
+                .unwrap_or_default();
+            search_messages(&pattern, &messages)
+                .into_iter()
+                .map(|(index, snippet)| SearchHit {
+                    project: project.encoded_name.clone(),
+                    project_name: project.name.clone(),
+                    provider: Provider::from_ai_tool(&project.ai_tool).slug(),
+                    session_id: session_id.clone(),
+                    index,
+                    role: role(&messages[index]),
+                    timestamp: *timestamp(&messages[index]),
+                    snippet,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    hits.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    hits.truncate(limit);
+
+    if output.json {
+        return output.print_json(&hits);
+    }
+
+    for hit in &hits {
+        println!(
+            "{} {} #{} {}  {}",
+            output.bold(&hit.project_name),
+            output.dim(&hit.session_id),
+            hit.index,
+            hit.role,
+            format_time(&hit.timestamp),
+        );
+        let snippet = pattern.replace_all(&hit.snippet, |m: &regex::Captures| output.bold(&m[0]));
+        println!("    {}", snippet);
+    }
+    println!("{}", output.dim(&format!("{} matches", hits.len())));
+    Ok(())
+}
+
+/// hub export <session>
+/// `--json` is a shorthand for `--format json`; `theme` only applies to HTML.
+pub fn export(
+    output: &Output,
+    provider: Option<Provider>,
+    session: &str,
+    project: Option<&str>,
+    format: ExportKind,
+    theme: &str,
+    file: Option<&Path>,
+) -> Result<(), String> {
+    let format = match (format, output.json) {
+        (ExportKind::Text, false) => None,
+        (ExportKind::Text | ExportKind::Json, _) => Some(ExportFormat::Json),
+        (ExportKind::Markdown, _) => Some(ExportFormat::Markdown),
+        (ExportKind::Html, _) => Some(ExportFormat::Html),
+    };
+
+    let (project, ai_tool, history) = load_history(provider, session, project)?;
+    let message_count = history.messages.len();
+    let content = match format {
+        Some(format) => {
+            let theme: Theme = theme.parse().unwrap_or(Theme::Dark);
+            export_conversation(&history, &ai_tool, format, &theme)?
+        }
+        None => {
+            let text = render_conversation(&Output::plain(), &project, &ai_tool, &to_conversation(history));
+            redactor().redact(&text).into_owned()
+        }
+    };
+
+    match file {
+        Some(file) => {
+            std::fs::write(file, content).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+            eprintln!("Exported {} messages to {}", message_count, file.display());
+        }
+        None => {
+            std::io::stdout()
+                .write_all(content.as_bytes())
+                .map_err(|e| format!("Failed to write output: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+/// hub archive <project> -o <file>
+pub fn archive(
+    output: &Output,
+    provider: Option<Provider>,
+    project: &str,
+    format: Option<ArchiveFormat>,
+    file: &Path,
+) -> Result<(), String> {
+    let project = resolve_project(provider, project)?;
+    let format = format.unwrap_or_else(|| {
+        if file.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("zip")) {
+            ArchiveFormat::Zip
+        } else {
+            ArchiveFormat::TarZst
+        }
+    });
+
+    let bytes = export_project(config(), &project.ai_tool, &project.encoded_name, format)?;
+    std::fs::write(file, &bytes).map_err(|e| format!("Failed to write {}: {}", file.display(), e))?;
+
+    if output.json {
+        return output.print_json(&serde_json::json!({
+            "project": project.encoded_name,
+            "provider": Provider::from_ai_tool(&project.ai_tool).slug(),
+            "format": format.as_str(),
+            "path": file,
+            "size": bytes.len(),
+        }));
+    }
+    println!(
+        "Archived {} ({} sessions) to {} ({} bytes)",
+        output.bold(&project.name),
+        project.session_count,
+        file.display(),
+        bytes.len()
+    );
+    Ok(())
+}
+
+/// hub import <file>
+pub fn import(output: &Output, file: &Path) -> Result<(), String> {
+    let bytes = std::fs::read(file).map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+    let report = import_archive(config(), &bytes)?;
+
+    if output.json {
+        return output.print_json(&report);
+    }
+    println!(
+        "Imported {} ({}, {} sessions) into the {} root",
+        output.bold(&report.project_name),
+        report.provider,
+        report.sessions,
+        IMPORTED_ROOT_LABEL
+    );
+    println!(
+        "{}",
+        output.dim(&format!(
+            "{} files written, {} replaced, {} unchanged; {} images written, {} already present",
+            report.files_written,
+            report.files_replaced,
+            report.files_unchanged,
+            report.images_written,
+            report.images_skipped
+        ))
+    );
+    Ok(())
+}
+
+/// hub serve
+pub async fn serve(host: Option<IpAddr>, port: Option<u16>) -> Result<(), String> {
+    let server = &config().server;
+    let addr = SocketAddr::new(host.unwrap_or(server.host), port.unwrap_or(server.port));
+    crate::handlers::serve_api(addr).await
+}
+
+/// Projects of one provider (or all), most recently updated first
+pub(super) fn projects_for(provider: Option<Provider>) -> Result<Vec<ProjectInfo>, String> {
+    let mut projects = history_projects()?;
+    if let Some(provider) = provider {
+        let ai_tool = provider.ai_tool();
+        projects.retain(|p| p.ai_tool == ai_tool);
+    }
+    projects.sort_by(|a, b| b.last_updated.cmp(&a.last_updated));
+    Ok(projects)
+}
+
+/// Find a project by encoded name, then path, then name
+fn resolve_project(provider: Option<Provider>, query: &str) -> Result<ProjectInfo, String> {
+    let projects = projects_for(provider)?;
+
+    if let Some(project) = projects
+        .iter()
+        .find(|p| p.encoded_name == query)
+        .or_else(|| projects.iter().find(|p| p.path == query))
+    {
+        return Ok(project.clone());
+    }
+
+    let named: Vec<&ProjectInfo> = projects.iter().filter(|p| p.name == query).collect();
+    match named.as_slice() {
+        [] => Err(format!("Project not found: {}", query)),
+        [project] => Ok((*project).clone()),
+        several => Err(format!(
+            "Several projects are named {}, use the encoded name: {}",
+            query,
+            several.iter().map(|p| p.encoded_name.as_str()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Locate and parse a session, in one project or in all of them
+fn load_history(
+    provider: Option<Provider>,
+    session_id: &str,
+    project: Option<&str>,
+) -> Result<(ProjectInfo, AiTool, ConversationHistory), String> {
+    let projects = match project {
+        Some(project) => vec![resolve_project(provider, project)?],
+        None => projects_for(provider)?,
+    };
+
+    let (project, ai_tool, path) = projects
+        .into_iter()
+        .find_map(|project| {
+            let (ai_tool, path) = find_conversation_file(&project.encoded_name, session_id)?;
+            if provider.is_some_and(|provider| provider.ai_tool() != ai_tool) {
+                return None;
+            }
+            Some((project, ai_tool, path))
+        })
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+
+    let history = load_conversation_file(&ai_tool, &path, session_id)?;
+    Ok((project, ai_tool, history))
+}
+
+/// `load_history` with the viewer's messages
+fn load_session(
+    provider: Option<Provider>,
+    session_id: &str,
+    project: Option<&str>,
+) -> Result<(ProjectInfo, AiTool, Conversation), String> {
+    let (project, ai_tool, history) = load_history(provider, session_id, project)?;
+    Ok((project, ai_tool, to_conversation(history)))
+}
+
+fn to_conversation(history: ConversationHistory) -> Conversation {
+    Conversation {
+        session_id: history.session_id,
+        messages: viewer_messages(&history.messages, history.created_at),
+    }
+}
+
+/// Readable transcript of a session
+fn render_conversation(output: &Output, project: &ProjectInfo, ai_tool: &AiTool, conversation: &Conversation) -> String {
+    let mut text = format!(
+        "{}\n{}\n\n",
+        output.bold(&format!("{} · {}", project.name, conversation.session_id)),
+        output.dim(&format!(
+            "{} · {} · {} messages",
+            Provider::from_ai_tool(ai_tool).slug(),
+            project.path,
+            conversation.messages.len()
+        )),
+    );
+
+    for message in &conversation.messages {
+        let heading = match message {
+            Message::Assistant { model: Some(model), .. } => format!("Assistant ({})", model),
+            Message::Tool { name, .. } => format!("Tool {}", name),
+            other => capitalize(role(other)),
+        };
+        text.push_str(&format!("{} {}\n", output.bold(&heading), output.dim(&format_time(timestamp(message)))));
+
+        match message {
+            Message::Tool { input, output: result, .. } => {
+                text.push_str(&format!("{}\n", output.dim(&truncate(&input.to_string(), TOOL_TEXT_LIMIT))));
+                if let Some(result) = result {
+                    text.push_str(&truncate(result, TOOL_TEXT_LIMIT));
+                    text.push('\n');
+                }
+            }
+            Message::Todo { items, .. } => {
+                for item in items {
+                    let mark = match item.status.as_str() {
+                        "completed" => "[x]",
+                        "in_progress" => "[~]",
+                        _ => "[ ]",
+                    };
+                    text.push_str(&format!("{} {}\n", mark, item.content));
+                }
+            }
+            other => {
+                text.push_str(message_text(other).trim_end());
+                text.push('\n');
+            }
+        }
+
+        if let Message::User { images, .. } | Message::Assistant { images, .. } = message {
+            for image in images {
+                text.push_str(&format!("{}\n", output.dim(&format!("[image: {}]", image.filename))));
+            }
+        }
+        text.push('\n');
+    }
+    text
+}
+
+/// Case-insensitive literal match of the search query
+pub(super) fn query_regex(query: &str) -> Result<Regex, String> {
+    if query.trim().is_empty() {
+        return Err("Empty search query".to_string());
+    }
+    RegexBuilder::new(&regex::escape(query))
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| format!("Invalid search query: {}", e))
+}
+
+/// (message index, snippet) of every message matching `pattern`
+fn search_messages(pattern: &Regex, messages: &[Message]) -> Vec<(usize, String)> {
+    messages
+        .iter()
+        .enumerate()
+        .filter_map(|(index, message)| {
+            let text = message_text(message);
+            let found = pattern.find(&text)?;
+            Some((index, snippet(&text, found.start(), found.end())))
+        })
+        .collect()
+}
+
+/// Match with some context on each side, on one line
+fn snippet(text: &str, start: usize, end: usize) -> String {
+    let before: String = text[..start].chars().rev().take(SNIPPET_CONTEXT).collect::<Vec<_>>().into_iter().rev().collect();
+    let after: String = text[end..].chars().take(SNIPPET_CONTEXT).collect();
+    let prefix = if before.len() < start { "…" } else { "" };
+    let suffix = if after.len() < text.len() - end { "…" } else { "" };
+    one_line(&format!("{}{}{}{}{}", prefix, before, &text[start..end], after, suffix))
+}
+
+/// Searchable text of a message
+pub(super) fn message_text(message: &Message) -> String {
+    match message {
+        Message::User { content, .. }
+        | Message::Assistant { content, .. }
+        | Message::System { content, .. }
+        | Message::Thinking { content, .. }
+        | Message::Plan { content, .. } => content.clone(),
+        Message::Tool { name, input, output, .. } => {
+            format!("{} {} {}", name, input, output.as_deref().unwrap_or_default())
+        }
+        Message::Todo { items, .. } => items.iter().map(|i| i.content.as_str()).collect::<Vec<_>>().join("\n"),
+    }
+}
+
+pub(super) fn role(message: &Message) -> &'static str {
+    match message {
+        Message::User { .. } => "user",
+        Message::Assistant { .. } => "assistant",
+        Message::Tool { .. } => "tool",
+        Message::System { .. } => "system",
+        Message::Thinking { .. } => "thinking",
+        Message::Plan { .. } => "plan",
+        Message::Todo { .. } => "todo",
+    }
+}
+
+pub(super) fn timestamp(message: &Message) -> &DateTime<Utc> {
+    match message {
+        Message::User { timestamp, .. }
+        | Message::Assistant { timestamp, .. }
+        | Message::Tool { timestamp, .. }
+        | Message::System { timestamp, .. }
+        | Message::Thinking { timestamp, .. }
+        | Message::Plan { timestamp, .. }
+        | Message::Todo { timestamp, .. } => timestamp,
+    }
+}
+
+pub(super) fn format_time(time: &DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
+
+pub(super) fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+pub(super) fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cut after `max` characters
+pub(super) fn truncate(text: &str, max: usize) -> String {
+    match text.char_indices().nth(max) {
+        Some((end, _)) => format!("{}… ({} more characters)", &text[..end], text[end..].chars().count()),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::TodoItem;
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(&format!("2025-01-01T10:{:02}:00Z", minute)).unwrap().with_timezone(&Utc)
+    }
+
+    fn messages() -> Vec<Message> {
+        vec![
+            Message::User {
+                content: "Why does the Parser panic on empty input?".to_string(),
+                timestamp: at(0),
+                images: Vec::new(),
+                metadata: None,
+            },
+            Message::Tool {
+                name: "Read".to_string(),
+                input: serde_json::json!({"file_path": "src/parser.rs"}),
+                output: Some("x".repeat(TOOL_TEXT_LIMIT + 10)),
+                timestamp: at(1),
+                tool_call_id: None,
+                metadata: None,
+            },
+            Message::Todo {
+                items: vec![TodoItem {
+                    content: "Handle empty input in the parser".to_string(),
+                    status: "completed".to_string(),
+                    active_form: String::new(),
+                }],
+                timestamp: at(2),
+                metadata: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_conversation() {
+        let project = ProjectInfo {
+            name: "demo".to_string(),
+            path: "/tmp/demo".to_string(),
+            encoded_name: "-tmp-demo".to_string(),
+            session_count: 1,
+            ai_tool: AiTool::ClaudeCode,
+            last_updated: at(2),
+            roots: Vec::new(),
+        };
+        let conversation = Conversation {
+            session_id: "s1".to_string(),
+            messages: messages(),
+        };
+
+        let text = render_conversation(&Output::plain(), &project, &AiTool::ClaudeCode, &conversation);
+        assert!(text.starts_with("demo · s1\nclaude · /tmp/demo · 3 messages\n"));
+        assert!(text.contains("\nUser "));
+        assert!(text.contains("Why does the Parser panic on empty input?\n"));
+        assert!(text.contains("\nTool Read "));
+        assert!(text.contains("… (10 more characters)"));
+        assert!(text.contains("[x] Handle empty input in the parser\n"));
+        assert!(!text.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_search_messages() {
+        let pattern = query_regex("PARSER").unwrap();
+        let hits = search_messages(&pattern, &messages());
+        assert_eq!(hits.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(hits[0].1, "Why does the Parser panic on empty input?");
+
+        let long = format!("{} needle {}", "a".repeat(100), "b".repeat(100));
+        let found = pattern_find(&long, "needle");
+        assert!(found.starts_with('…') && found.ends_with('…'));
+        assert_eq!(found.chars().count(), 2 * SNIPPET_CONTEXT + "needle".len() + 2);
+
+        assert!(query_regex("  ").is_err());
+        assert!(search_messages(&query_regex("a.b").unwrap(), &messages()).is_empty());
+    }
+
+    #[test]
+    fn test_session_keeps_tool_activity() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/claude_tool_session.jsonl");
+        let conversation = to_conversation(crate::history::parse_jsonl_file(&fixture).unwrap());
+
+        let hits = search_messages(&query_regex("cargo.toml").unwrap(), &conversation.messages);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(role(&conversation.messages[hits[0].0]), "tool");
+        // The last message has no timestamp: it keeps the one before it
+        assert_eq!(timestamp(&conversation.messages[3]), timestamp(&conversation.messages[2]));
+    }
+
+    fn pattern_find(text: &str, query: &str) -> String {
+        let found = query_regex(query).unwrap().find(text).unwrap();
+        snippet(text, found.start(), found.end())
+    }
+}
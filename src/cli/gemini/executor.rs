@@ -70,10 +70,26 @@ impl CliExecutor for GeminiExecutor {
     fn build_args(&self, options: &ExecuteOptions) -> Vec<String> {
         // Gemini CLI has limited options in non-interactive mode
         // Session resume and other features may not be available
-        vec![
+        // JSON events, normalized by GeminiStreamAdapter
+        let mut args = vec![
             "-p".to_string(),
             options.message.clone(),
-        ]
+            "--output-format".to_string(),
+            "stream-json".to_string(),
+        ];
+
+        // Run settings: model and extra directories only (others rejected by check_run_settings)
+        if let Some(model) = &options.model {
+            args.push("--model".to_string());
+            args.push(model.clone());
+        }
+        if !options.additional_dirs.is_empty() {
+            let dirs: Vec<String> = options.additional_dirs.iter().map(|d| d.display().to_string()).collect();
+            args.push("--include-directories".to_string());
+            args.push(dirs.join(","));
+        }
+
+        args
     }
     // execute() uses default implementation from CliExecutor trait
 }
@@ -91,6 +107,23 @@ mod tests {
 
         assert_eq!(args[0], "-p");
         assert_eq!(args[1], "Hello");
+        assert_eq!(args[2..4], ["--output-format", "stream-json"]);
+    }
+
+    #[test]
+    fn test_run_settings() {
+        let executor = GeminiExecutor::with_path("/usr/bin/gemini");
+        let options = ExecuteOptions::new("Hello")
+            .with_model("gemini-2.5-pro")
+            .with_additional_dir("/a")
+            .with_additional_dir("/b");
+
+        let args = executor.build_args(&options);
+        assert_eq!(args[4..], ["--model", "gemini-2.5-pro", "--include-directories", "/a,/b"]);
+        assert!(executor.check_run_settings(&options).is_ok());
+
+        let options = options.with_mcp_config("/etc/mcp.json");
+        assert!(matches!(executor.check_run_settings(&options), Err(ExecutorError::NotSupported(_))));
     }
 
     #[test]
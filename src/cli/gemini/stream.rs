@@ -0,0 +1,121 @@
+//! Gemini `--output-format stream-json` adapter
+//!
+//! One JSON event per line: `init` (session and model), `message` (user echo,
+//! or assistant text as deltas), `tool_use`, `tool_result`, `error` (warnings
+//! the run survives) and a final `result` with the run statistics.
+
+use serde_json::Value;
+
+use crate::cli::traits::{parse_json_line, CliProvider, StreamAdapter};
+use crate::domain::models::StreamEvent;
+
+/// Stream adapter for Gemini CLI
+#[derive(Debug, Default)]
+pub struct GeminiStreamAdapter;
+
+impl StreamAdapter for GeminiStreamAdapter {
+    fn parse_line(&mut self, line: &str) -> Vec<StreamEvent> {
+        let Some(json) = parse_json_line(CliProvider::Gemini, line) else {
+            return Vec::new();
+        };
+        let text = |key: &str| json.get(key).and_then(Value::as_str).map(str::to_string);
+
+        let event = match json.get("type").and_then(Value::as_str) {
+            Some("init") => StreamEvent::SessionStarted {
+                session_id: match text("session_id") {
+                    Some(session_id) => session_id,
+                    None => return Vec::new(),
+                },
+                cwd: None,
+                model: text("model"),
+                tools: Vec::new(),
+            },
+            Some("message") if text("role").as_deref() == Some("assistant") => match text("content") {
+                Some(text) => StreamEvent::TextDelta { text },
+                None => return Vec::new(),
+            },
+            Some("tool_use") => StreamEvent::ToolCall {
+                id: text("tool_id"),
+                name: text("tool_name").unwrap_or_default(),
+                input: json.get("parameters").cloned().unwrap_or_else(|| serde_json::json!({})),
+            },
+            Some("tool_result") => {
+                let is_error = text("status").as_deref() == Some("error");
+                let output = if is_error {
+                    json.pointer("/error/message").and_then(Value::as_str).map(str::to_string).or_else(|| text("output"))
+                } else {
+                    text("output")
+                };
+                StreamEvent::ToolResult { id: text("tool_id"), output: output.unwrap_or_default(), is_error }
+            }
+            Some("error") => {
+                tracing::warn!("Gemini CLI {}: {}", text("severity").unwrap_or_default(), text("message").unwrap_or_default());
+                return Vec::new();
+            }
+            Some("result") => {
+                let stat = |key: &str| json.pointer(&format!("/stats/{}", key)).and_then(Value::as_u64);
+                let success = text("status").as_deref() == Some("success");
+                return vec![
+                    StreamEvent::Usage {
+                        input_tokens: stat("input_tokens").unwrap_or(0),
+                        output_tokens: stat("output_tokens").unwrap_or(0),
+                    },
+                    StreamEvent::Result {
+                        success,
+                        message: if success {
+                            None
+                        } else {
+                            json.pointer("/error/message").and_then(Value::as_str).map(str::to_string)
+                        },
+                        duration_ms: stat("duration_ms"),
+                    },
+                ];
+            }
+            _ => return Vec::new(),
+        };
+        vec![event]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn parse(line: Value) -> Vec<StreamEvent> {
+        GeminiStreamAdapter.parse_line(&line.to_string())
+    }
+
+    #[test]
+    fn test_init_messages_and_tools() {
+        let events = parse(json!({"type": "init", "timestamp": "2025-10-10T12:00:00Z", "session_id": "g1", "model": "gemini-2.5-pro"}));
+        assert!(matches!(&events[..], [StreamEvent::SessionStarted { session_id, model: Some(model), .. }] if session_id == "g1" && model == "gemini-2.5-pro"));
+
+        assert!(parse(json!({"type": "message", "role": "user", "content": "List files"})).is_empty());
+        assert_eq!(
+            parse(json!({"type": "message", "role": "assistant", "content": "Here", "delta": true})),
+            [StreamEvent::TextDelta { text: "Here".to_string() }]
+        );
+
+        let events = parse(json!({"type": "tool_use", "tool_name": "list_directory", "tool_id": "t1", "parameters": {"path": "."}}));
+        assert!(matches!(&events[..], [StreamEvent::ToolCall { id: Some(id), name, .. }] if id == "t1" && name == "list_directory"));
+
+        assert_eq!(
+            parse(json!({"type": "tool_result", "tool_id": "t1", "status": "error", "error": {"type": "io", "message": "No such directory"}})),
+            [StreamEvent::ToolResult { id: Some("t1".to_string()), output: "No such directory".to_string(), is_error: true }]
+        );
+    }
+
+    #[test]
+    fn test_result() {
+        let events = parse(json!({
+            "type": "result", "status": "success",
+            "stats": {"total_tokens": 150, "input_tokens": 120, "output_tokens": 30, "duration_ms": 2500, "tool_calls": 1},
+        }));
+        assert_eq!(events, [
+            StreamEvent::Usage { input_tokens: 120, output_tokens: 30 },
+            StreamEvent::Result { success: true, message: None, duration_ms: Some(2500) },
+        ]);
+        assert!(parse(json!({"type": "error", "severity": "warning", "message": "Loop detected"})).is_empty());
+    }
+}
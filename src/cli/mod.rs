@@ -1,4 +1,8 @@
-pub mod generators;
+/// `hub` command-line front end over the history module
+pub mod commands;
+
+/// `hub tui` terminal session browser
+pub mod tui;
 
 /// Common CLI traits and types for multi-provider support
 pub mod traits;
@@ -12,194 +16,201 @@ pub mod opencode;
 /// Gemini CLI interaction module
 pub mod gemini;
 
-use clap::{Parser, Subcommand};
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::domain::models::AiTool;
+use crate::history::archive::ArchiveFormat;
 
 #[derive(Parser)]
-#[command(name = "dx")]
-#[command(about = "Dioxus CLI - Rails-like generators for fullstack Rust apps")]
+#[command(name = "hub")]
+#[command(about = "Browse, search and export Claude Code, OpenCode and Gemini CLI sessions")]
 pub struct Cli {
+    /// Print JSON instead of text (for scripts)
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    /// Only look at one provider's histories
+    #[arg(long, global = true, value_enum)]
+    pub provider: Option<Provider>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+/// Provider filter, named like `providers.roots[].provider` in config.toml
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Provider {
+    Claude,
+    Opencode,
+    Gemini,
+}
+
+impl Provider {
+    /// Slug used by `list_project_summaries_for_tool`
+    pub fn slug(self) -> &'static str {
+        match self {
+            Provider::Claude => "claude",
+            Provider::Opencode => "opencode",
+            Provider::Gemini => "gemini",
+        }
+    }
+
+    pub fn ai_tool(self) -> AiTool {
+        match self {
+            Provider::Claude => AiTool::ClaudeCode,
+            Provider::Opencode => AiTool::OpenCode,
+            Provider::Gemini => AiTool::Gemini,
+        }
+    }
+
+    pub fn from_ai_tool(ai_tool: &AiTool) -> Self {
+        match ai_tool {
+            AiTool::ClaudeCode => Provider::Claude,
+            AiTool::OpenCode => Provider::Opencode,
+            AiTool::Gemini => Provider::Gemini,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Generate new code (alias: g)
-    Generate {
+    /// Projects found in the history directories
+    Projects {
         #[command(subcommand)]
-        generator: GeneratorCommands,
+        action: ProjectCommands,
     },
-    /// Alias for generate
-    G {
+    /// Sessions of a project
+    Sessions {
         #[command(subcommand)]
-        generator: GeneratorCommands,
+        action: SessionCommands,
     },
-    /// Database operations
-    Db {
-        #[command(subcommand)]
-        action: DbCommands,
+    /// Print a session
+    Show {
+        /// Session id
+        session: String,
+        /// Project holding the session (encoded name, name or path); all projects if omitted
+        #[arg(long, short)]
+        project: Option<String>,
     },
-    /// Setup project interactively
-    Setup,
-}
-
-#[derive(Subcommand)]
-pub enum GeneratorCommands {
-    /// Generate a full CRUD scaffold (model + migration + API + UI)
-    Scaffold {
-        /// Name of the resource (e.g., User, Post)
-        name: String,
-        /// Fields in format: name:type[:options]
-        /// Examples: title:string, email:string:unique, age:integer
-        fields: Vec<String>,
-        /// Skip generating certain parts
-        #[arg(long)]
-        skip: Vec<String>,
-        /// Force overwrite existing files
-        #[arg(long)]
-        force: bool,
+    /// Search message contents (case-insensitive)
+    Search {
+        query: String,
+        /// Only search this project (encoded name, name or path)
+        #[arg(long, short)]
+        project: Option<String>,
+        /// Maximum number of matches
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
     },
-    /// Generate a SeaORM model
-    Model {
-        /// Name of the model (e.g., User, Post)
-        name: String,
-        /// Fields in format: name:type[:options]
-        fields: Vec<String>,
-        /// Add timestamps (created_at, updated_at)
-        #[arg(long)]
-        timestamps: bool,
-        /// Use UUID instead of auto-incrementing integer
-        #[arg(long)]
-        uuid: bool,
-        /// Add soft delete (deleted_at)
-        #[arg(long)]
-        soft_delete: bool,
+    /// Write a session as a text transcript, Markdown, JSON or HTML
+    Export {
+        /// Session id
+        session: String,
+        /// Project holding the session (encoded name, name or path); all projects if omitted
+        #[arg(long, short)]
+        project: Option<String>,
+        /// Output format (--json is the same as --format json)
+        #[arg(long, short, value_enum, default_value_t = ExportKind::Text)]
+        format: ExportKind,
+        /// Web UI theme of HTML exports (dark, light, golden, pistachio, dark-golden, dark-pistachio)
+        #[arg(long, default_value = "dark")]
+        theme: String,
+        /// Output file (stdout if omitted)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
     },
-    /// Generate API controller/routes
-    Controller {
-        /// Name of the controller (e.g., api/v1/users)
-        name: String,
-        /// Actions to generate (index, show, create, update, destroy)
-        actions: Vec<String>,
-        /// Generate as resource (includes all CRUD actions)
-        #[arg(long)]
-        resource: bool,
-    },
-    /// Generate authentication system
-    Auth {
-        /// Model name for authentication (default: User)
-        model: Option<String>,
-        /// Authentication method (jwt, session)
-        #[arg(long, default_value = "jwt")]
-        method: String,
+    /// Bundle a whole project (session files, images, manifest) into an archive
+    Archive {
+        /// Encoded name, name or path of the project
+        project: String,
+        /// tar.zst or zip (default: from the output extension, else tar.zst)
+        #[arg(long, short)]
+        format: Option<ArchiveFormat>,
+        /// Archive file to write
+        #[arg(long, short)]
+        output: PathBuf,
     },
-    /// Generate a UI component
-    Component {
-        /// Name of the component
-        name: String,
-        /// Props in format: name:type
-        props: Vec<String>,
+    /// Import a project archive into the read-only `imported` history root
+    Import {
+        /// Archive made by `hub archive` or the web UI (.tar.zst or zip)
+        file: PathBuf,
     },
-    /// Generate a page
-    Page {
-        /// Name of the page/route
-        name: String,
-        /// Require authentication
+    /// Browse projects, sessions and conversations in the terminal
+    Tui,
+    /// Run the API server (same as the `server` binary)
+    Serve {
+        /// Listen address (default: `server.host` from config.toml)
         #[arg(long)]
-        auth_required: bool,
-        /// Admin only access
+        host: Option<IpAddr>,
+        /// Listen port (default: `server.port` from config.toml)
         #[arg(long)]
-        admin_only: bool,
+        port: Option<u16>,
     },
 }
 
-#[derive(Subcommand)]
-pub enum DbCommands {
-    /// Setup database and run migrations
-    Setup,
-    /// Create database
-    Create,
-    /// Drop database
-    Drop,
-    /// Seed database with sample data
-    Seed,
-    /// Reset database (drop + create + migrate + seed)
-    Reset,
+/// `hub export` formats: the terminal transcript, or one of the web exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportKind {
+    Text,
+    Markdown,
+    Json,
+    Html,
 }
 
-pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
-
-    match cli.command {
-        Commands::Generate { generator } | Commands::G { generator } => {
-            handle_generator(generator)
-        }
-        Commands::Db { action } => {
-            handle_db(action)
-        }
-        Commands::Setup => {
-            handle_setup()
-        }
-    }
+#[derive(Subcommand)]
+pub enum ProjectCommands {
+    /// List projects, most recently updated first
+    List {
+        /// Case-insensitive filter on project name and path
+        #[arg(long, short)]
+        search: Option<String>,
+    },
 }
 
-fn handle_generator(generator: GeneratorCommands) -> Result<(), Box<dyn std::error::Error>> {
-    match generator {
-        GeneratorCommands::Model { name, fields, timestamps, uuid, soft_delete } => {
-            generators::model::generate_model(&name, &fields, timestamps, uuid, soft_delete)
-        }
-        GeneratorCommands::Scaffold { name, fields, skip, force } => {
-            generators::scaffold::generate_scaffold(&name, &fields, &skip, force)
-        }
-        GeneratorCommands::Controller { name, actions, resource } => {
-            generators::controller::generate_controller(&name, &actions, resource)
-        }
-        GeneratorCommands::Auth { model, method } => {
-            let model_name = model.unwrap_or_else(|| "User".to_string());
-            generators::auth::generate_auth(&model_name, &method)
-        }
-        GeneratorCommands::Component { name, props } => {
-            generators::component::generate_component(&name, &props)
-        }
-        GeneratorCommands::Page { name, auth_required, admin_only } => {
-            generators::page::generate_page(&name, auth_required, admin_only)
-        }
-    }
+#[derive(Subcommand)]
+pub enum SessionCommands {
+    /// List the sessions of a project, most recent first
+    List {
+        /// Encoded name, name or path of the project
+        project: String,
+    },
 }
 
-fn handle_db(action: DbCommands) -> Result<(), Box<dyn std::error::Error>> {
-    match action {
-        DbCommands::Setup => {
-            println!("Setting up database...");
-            // TODO: Implement database setup
-            Ok(())
+/// Parse the command line and run it (`bin/hub.rs`)
+pub async fn run_cli() -> Result<(), String> {
+    let cli = Cli::parse();
+    let output = commands::Output::new(cli.json);
+
+    match cli.command {
+        Commands::Projects { action: ProjectCommands::List { search } } => {
+            commands::list_projects(&output, cli.provider, search.as_deref())
         }
-        DbCommands::Create => {
-            println!("Creating database...");
-            // TODO: Implement database creation
-            Ok(())
+        Commands::Sessions { action: SessionCommands::List { project } } => {
+            commands::list_sessions(&output, cli.provider, &project)
         }
-        DbCommands::Drop => {
-            println!("Dropping database...");
-            // TODO: Implement database drop
-            Ok(())
+        Commands::Show { session, project } => {
+            commands::show(&output, cli.provider, &session, project.as_deref())
         }
-        DbCommands::Seed => {
-            println!("Seeding database...");
-            // TODO: Implement seeding
-            Ok(())
+        Commands::Search { query, project, limit } => {
+            commands::search(&output, cli.provider, &query, project.as_deref(), limit)
         }
-        DbCommands::Reset => {
-            println!("Resetting database...");
-            // TODO: Implement reset
-            Ok(())
+        Commands::Export { session, project, format, theme, output: file } => commands::export(
+            &output,
+            cli.provider,
+            &session,
+            project.as_deref(),
+            format,
+            &theme,
+            file.as_deref(),
+        ),
+        Commands::Archive { project, format, output: file } => {
+            commands::archive(&output, cli.provider, &project, format, &file)
         }
+        Commands::Import { file } => commands::import(&output, &file),
+        Commands::Tui => tui::run(cli.provider),
+        Commands::Serve { host, port } => commands::serve(host, port).await,
     }
 }
-
-fn handle_setup() -> Result<(), Box<dyn std::error::Error>> {
-    println!("🚀 Welcome to Dioxus CLI Setup!");
-    println!("This will help you configure your project interactively.");
-    // TODO: Implement interactive setup
-    Ok(())
-}
\ No newline at end of file
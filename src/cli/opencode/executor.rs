@@ -73,10 +73,13 @@ impl CliExecutor for OpenCodeExecutor {
             )
         };
 
+        // JSON events, normalized by OpenCodeStreamAdapter
         let mut args = vec![
             "run".to_string(),
             "-p".to_string(),
             message,
+            "--format".to_string(),
+            "json".to_string(),
         ];
 
         // Add session resume if provided
@@ -85,6 +88,12 @@ impl CliExecutor for OpenCodeExecutor {
             args.push(session_id.clone());
         }
 
+        // Model as "provider/model"; other run settings are rejected by check_run_settings
+        if let Some(model) = &options.model {
+            args.push("--model".to_string());
+            args.push(model.clone());
+        }
+
         // Note: Images are now included in the prompt text above
         // OpenCode will use Read tool to view them
 
@@ -107,6 +116,7 @@ mod tests {
         assert_eq!(args[0], "run");
         assert_eq!(args[1], "-p");
         assert_eq!(args[2], "Hello");
+        assert_eq!(args[3..5], ["--format", "json"]);
     }
 
     #[test]
@@ -121,6 +131,19 @@ mod tests {
         assert!(args.contains(&"session123".to_string()));
     }
 
+    #[test]
+    fn test_run_settings() {
+        let executor = OpenCodeExecutor::with_path("/usr/bin/opencode");
+        let options = ExecuteOptions::new("Hello").with_model("anthropic/claude-sonnet-4");
+
+        let args = executor.build_args(&options);
+        assert_eq!(args[5..], ["--model", "anthropic/claude-sonnet-4"]);
+        assert!(executor.check_run_settings(&options).is_ok());
+
+        let options = options.with_max_turns(3);
+        assert!(matches!(executor.check_run_settings(&options), Err(ExecutorError::NotSupported(_))));
+    }
+
     #[test]
     fn test_build_args_with_images() {
         let executor = OpenCodeExecutor::with_path("/usr/bin/opencode");
@@ -2,6 +2,8 @@
 
 pub mod detection;
 pub mod executor;
+pub mod stream;
 
 pub use detection::*;
 pub use executor::*;
+pub use stream::*;
@@ -0,0 +1,152 @@
+//! OpenCode `run --format json` adapter
+//!
+//! Each line is an event about one part of the answer, tagged with the session:
+//! `text`, `reasoning`, `tool_use` (sent once the tool has finished, with its
+//! output), `step_start`/`step_finish` around each model call, and `error`.
+//! OpenCode has no start or end message: the session starts with the first
+//! event and the run ends with the step finishing for reason `stop`.
+
+use serde_json::Value;
+
+use crate::cli::traits::{parse_json_line, CliProvider, StreamAdapter};
+use crate::domain::models::StreamEvent;
+
+/// Stream adapter for OpenCode CLI
+#[derive(Debug, Default)]
+pub struct OpenCodeStreamAdapter {
+    session_id: Option<String>,
+    /// Tokens of the steps so far (each step reports its own)
+    input_tokens: u64,
+    output_tokens: u64,
+}
+
+impl OpenCodeStreamAdapter {
+    fn finish(&self, success: bool, message: Option<String>) -> Vec<StreamEvent> {
+        vec![
+            StreamEvent::Usage { input_tokens: self.input_tokens, output_tokens: self.output_tokens },
+            StreamEvent::Result { success, message, duration_ms: None },
+        ]
+    }
+}
+
+impl StreamAdapter for OpenCodeStreamAdapter {
+    fn parse_line(&mut self, line: &str) -> Vec<StreamEvent> {
+        let Some(json) = parse_json_line(CliProvider::OpenCode, line) else {
+            return Vec::new();
+        };
+        let mut events = Vec::new();
+
+        let session_id = json.get("sessionID").and_then(Value::as_str);
+        if let Some(session_id) = session_id.filter(|_| self.session_id.is_none()) {
+            self.session_id = Some(session_id.to_string());
+            events.push(StreamEvent::SessionStarted {
+                session_id: session_id.to_string(),
+                cwd: None,
+                model: None,
+                tools: Vec::new(),
+            });
+        }
+
+        let part = json.get("part").unwrap_or(&Value::Null);
+        let part_text = || part.get("text").and_then(Value::as_str).map(str::to_string);
+        match json.get("type").and_then(Value::as_str) {
+            Some("text") => events.extend(part_text().map(|text| StreamEvent::TextDelta { text })),
+            Some("reasoning") => events.extend(part_text().map(|text| StreamEvent::Thinking { text })),
+            Some("tool_use") => events.extend(tool_use(part)),
+            Some("step_finish") => {
+                let tokens = |key: &str| part.pointer(&format!("/tokens/{}", key)).and_then(Value::as_u64).unwrap_or(0);
+                self.input_tokens += tokens("input") + tokens("cache/read") + tokens("cache/write");
+                self.output_tokens += tokens("output") + tokens("reasoning");
+                if part.get("reason").and_then(Value::as_str) == Some("stop") {
+                    events.extend(self.finish(true, None));
+                }
+            }
+            Some("error") => {
+                let error = json.get("error").unwrap_or(&Value::Null);
+                let message = error
+                    .pointer("/data/message")
+                    .or_else(|| error.get("name"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("OpenCode error");
+                events.extend(self.finish(false, Some(message.to_string())));
+            }
+            _ => {}
+        }
+        events
+    }
+}
+
+/// Call and result of a finished tool part
+fn tool_use(part: &Value) -> Vec<StreamEvent> {
+    let id = part.get("callID").and_then(Value::as_str).map(str::to_string);
+    let Some(name) = part.get("tool").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+    let state = part.get("state").unwrap_or(&Value::Null);
+    let mut events = vec![StreamEvent::ToolCall {
+        id: id.clone(),
+        name: name.to_string(),
+        input: state.get("input").cloned().unwrap_or_else(|| serde_json::json!({})),
+    }];
+
+    let field = |key: &str| state.get(key).and_then(Value::as_str).unwrap_or_default().to_string();
+    match state.get("status").and_then(Value::as_str) {
+        Some("completed") => events.push(StreamEvent::ToolResult { id, output: field("output"), is_error: false }),
+        Some("error") => events.push(StreamEvent::ToolResult { id, output: field("error"), is_error: true }),
+        _ => {}
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_session_text_and_tools() {
+        let mut adapter = OpenCodeStreamAdapter::default();
+
+        let events = adapter.parse_line(&json!({
+            "type": "step_start", "sessionID": "ses_1", "part": {"type": "step-start"},
+        }).to_string());
+        assert!(matches!(&events[..], [StreamEvent::SessionStarted { session_id, .. }] if session_id == "ses_1"));
+
+        let events = adapter.parse_line(&json!({
+            "type": "tool_use", "sessionID": "ses_1",
+            "part": {"type": "tool", "callID": "c1", "tool": "bash", "state": {
+                "status": "completed", "input": {"command": "ls"}, "output": "Cargo.toml\n",
+            }},
+        }).to_string());
+        assert_eq!(events, [
+            StreamEvent::ToolCall { id: Some("c1".to_string()), name: "bash".to_string(), input: json!({"command": "ls"}) },
+            StreamEvent::ToolResult { id: Some("c1".to_string()), output: "Cargo.toml\n".to_string(), is_error: false },
+        ]);
+
+        let events = adapter.parse_line(&json!({
+            "type": "text", "sessionID": "ses_1", "part": {"type": "text", "text": "One file."},
+        }).to_string());
+        assert_eq!(events, [StreamEvent::TextDelta { text: "One file.".to_string() }]);
+    }
+
+    #[test]
+    fn test_usage_adds_up_steps() {
+        let mut adapter = OpenCodeStreamAdapter::default();
+        let step = |reason: &str, input: u64| json!({
+            "type": "step_finish", "sessionID": "ses_1",
+            "part": {"type": "step-finish", "reason": reason, "tokens": {"input": input, "output": 5, "cache": {"read": 0, "write": 0}}},
+        }).to_string();
+
+        assert_eq!(adapter.parse_line(&step("tool-calls", 100)).len(), 1);
+        let events = adapter.parse_line(&step("stop", 200));
+        assert_eq!(events, [
+            StreamEvent::Usage { input_tokens: 300, output_tokens: 10 },
+            StreamEvent::Result { success: true, message: None, duration_ms: None },
+        ]);
+
+        let events = adapter.parse_line(&json!({
+            "type": "error", "sessionID": "ses_1", "error": {"name": "APIError", "data": {"message": "Rate limited"}},
+        }).to_string());
+        assert!(matches!(&events[1], StreamEvent::Result { success: false, message: Some(m), .. } if m == "Rate limited"));
+    }
+}
@@ -11,6 +11,11 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::mpsc;
 
+use crate::domain::models::{RunSetting, StreamEvent};
+use super::claude::stream::ClaudeStreamAdapter;
+use super::gemini::stream::GeminiStreamAdapter;
+use super::opencode::stream::OpenCodeStreamAdapter;
+
 /// Supported CLI providers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -40,6 +45,20 @@ impl CliProvider {
         }
     }
 
+    /// Check if this provider's CLI has a flag for a run setting
+    pub fn supports_setting(&self, setting: RunSetting) -> bool {
+        setting.supported_by(self.executable_name())
+    }
+
+    /// Adapter turning this provider's JSON output into stream events
+    pub fn stream_adapter(&self) -> Box<dyn StreamAdapter> {
+        match self {
+            CliProvider::Claude => Box::new(ClaudeStreamAdapter),
+            CliProvider::OpenCode => Box::new(OpenCodeStreamAdapter::default()),
+            CliProvider::Gemini => Box::new(GeminiStreamAdapter),
+        }
+    }
+
     /// Check if this provider supports images in non-interactive mode
     pub fn supports_images(&self) -> bool {
         match self {
@@ -120,6 +139,8 @@ pub struct ExecuteOptions {
     pub message: String,
     /// Session ID to resume (for conversation continuity)
     pub session_id: Option<String>,
+    /// Resume `session_id` into a new session instead of appending to it
+    pub fork_session: bool,
     /// Working directory for file operations
     pub working_directory: Option<PathBuf>,
     /// Allowed tools (if restricted)
@@ -128,6 +149,18 @@ pub struct ExecuteOptions {
     pub permission_mode: PermissionMode,
     /// Image paths to include for vision capabilities
     pub image_paths: Vec<PathBuf>,
+    /// Model name or alias
+    pub model: Option<String>,
+    /// Maximum number of agent turns
+    pub max_turns: Option<u32>,
+    /// Text appended to the system prompt
+    pub append_system_prompt: Option<String>,
+    /// Tools the CLI may not use
+    pub disallowed_tools: Option<Vec<String>>,
+    /// Directories accessible besides the working directory
+    pub additional_dirs: Vec<PathBuf>,
+    /// MCP servers configuration file
+    pub mcp_config: Option<PathBuf>,
 }
 
 impl ExecuteOptions {
@@ -143,6 +176,12 @@ impl ExecuteOptions {
         self
     }
 
+    /// Continue `session_id` in a new session, leaving the original untouched
+    pub fn with_fork_session(mut self) -> Self {
+        self.fork_session = true;
+        self
+    }
+
     pub fn with_working_directory(mut self, dir: impl Into<PathBuf>) -> Self {
         self.working_directory = Some(dir.into());
         self
@@ -169,6 +208,73 @@ impl ExecuteOptions {
         self.image_paths.push(path.into());
         self
     }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+
+    pub fn with_max_turns(mut self, max_turns: u32) -> Self {
+        self.max_turns = Some(max_turns);
+        self
+    }
+
+    pub fn with_append_system_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.append_system_prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn with_disallowed_tools(mut self, tools: Vec<String>) -> Self {
+        self.disallowed_tools = Some(tools);
+        self
+    }
+
+    /// Add a directory the CLI may access besides the working directory
+    pub fn with_additional_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.additional_dirs.push(dir.into());
+        self
+    }
+
+    pub fn with_mcp_config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.mcp_config = Some(path.into());
+        self
+    }
+
+    /// Run settings set in these options
+    pub fn run_settings(&self) -> Vec<RunSetting> {
+        [
+            (RunSetting::Model, self.model.is_some()),
+            (RunSetting::MaxTurns, self.max_turns.is_some()),
+            (RunSetting::AppendSystemPrompt, self.append_system_prompt.is_some()),
+            (RunSetting::DisallowedTools, self.disallowed_tools.is_some()),
+            (RunSetting::AdditionalDirs, !self.additional_dirs.is_empty()),
+            (RunSetting::McpConfig, self.mcp_config.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(setting, set)| set.then_some(setting))
+        .collect()
+    }
+}
+
+/// Converts the stdout lines of a CLI, as they arrive, into provider-neutral events
+pub trait StreamAdapter: Send {
+    /// Events of one line (none for lines with nothing to show)
+    fn parse_line(&mut self, line: &str) -> Vec<StreamEvent>;
+}
+
+/// JSON object of an output line; other lines (banners, warnings) are logged and skipped
+pub(crate) fn parse_json_line(provider: CliProvider, line: &str) -> Option<serde_json::Value> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    match serde_json::from_str::<serde_json::Value>(line) {
+        Ok(json) if json.is_object() => Some(json),
+        _ => {
+            tracing::warn!("Skipping non-JSON {} output: {}", provider, line);
+            None
+        }
+    }
 }
 
 /// A running CLI process
@@ -227,6 +333,19 @@ pub trait CliExecutor: Send + Sync {
     /// Build command arguments for execution (CLI-specific)
     fn build_args(&self, options: &ExecuteOptions) -> Vec<String>;
 
+    /// Reject run settings the CLI has no flag for (see `RunSetting::supported_by`)
+    fn check_run_settings(&self, options: &ExecuteOptions) -> Result<(), ExecutorError> {
+        let provider = self.provider();
+        match options.run_settings().into_iter().find(|setting| !provider.supports_setting(*setting)) {
+            Some(setting) => Err(ExecutorError::NotSupported(format!(
+                "{} CLI ne prend pas en charge l'option « {} »",
+                provider,
+                setting.label()
+            ))),
+            None => Ok(()),
+        }
+    }
+
     /// Validate options before execution (can be overridden for CLI-specific validation)
     fn validate_options(&self, options: &ExecuteOptions) -> Result<(), ExecutorError> {
         // Default: no validation
@@ -238,6 +357,7 @@ pub trait CliExecutor: Send + Sync {
     /// Default implementation uses cli_path() and build_args()
     async fn execute(&self, options: ExecuteOptions) -> Result<CliProcess, ExecutorError> {
         // Validate options first
+        self.check_run_settings(&options)?;
         self.validate_options(&options)?;
         let args = self.build_args(&options);
         let provider = self.provider();
@@ -253,6 +373,12 @@ pub trait CliExecutor: Send + Sync {
 
         // Set working directory if specified
         if let Some(cwd) = &options.working_directory {
+            if !cwd.is_dir() {
+                return Err(ExecutorError::SpawnFailed(format!(
+                    "Working directory does not exist: {}",
+                    cwd.display()
+                )));
+            }
             cmd.current_dir(cwd);
         }
 
@@ -0,0 +1,554 @@
+//! State and key handling of `hub tui` (drawing is in `ui.rs`)
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::cli::commands::{message_text, projects_for};
+use crate::cli::Provider;
+use crate::domain::models::{group_by_period, Message, SessionPeriod};
+use crate::history::{list_project_summaries_for_tool, load_conversation, viewer_messages, ConversationSummary, ProjectInfo};
+
+/// Messages moved by PageUp / PageDown
+const PAGE_MESSAGES: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Projects,
+    Sessions,
+    Conversation,
+}
+
+/// Sessions of the opened project, grouped like the web sidebar
+pub struct SessionList {
+    pub project: ProjectInfo,
+    /// Non-empty periods, most recent first
+    pub groups: Vec<(SessionPeriod, Vec<ConversationSummary>)>,
+    pub selected: usize,
+}
+
+impl SessionList {
+    pub fn new(project: ProjectInfo, summaries: Vec<ConversationSummary>, now: &DateTime<Utc>) -> Self {
+        let groups = group_by_period(summaries, |s| parse_time(&s.last_time), now)
+            .into_iter()
+            .filter(|(_, sessions)| !sessions.is_empty())
+            .map(|(period, mut sessions)| {
+                sessions.sort_by(|a, b| b.last_time.cmp(&a.last_time));
+                (period, sessions)
+            })
+            .collect();
+        Self {
+            project,
+            groups,
+            selected: 0,
+        }
+    }
+
+    /// Sessions in display order
+    pub fn sessions(&self) -> impl Iterator<Item = &ConversationSummary> {
+        self.groups.iter().flat_map(|(_, sessions)| sessions.iter())
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.iter().map(|(_, sessions)| sessions.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn current(&self) -> Option<&ConversationSummary> {
+        self.sessions().nth(self.selected)
+    }
+}
+
+/// Opened session
+pub struct ConversationView {
+    pub project_name: String,
+    pub session_id: String,
+    pub messages: Vec<Message>,
+    /// Message under the cursor
+    pub selected: usize,
+    /// Tool calls shown with their input and output
+    pub expanded: HashSet<usize>,
+    /// First visible line
+    pub scroll: usize,
+    /// Scroll to the selected message on the next draw
+    pub follow: bool,
+}
+
+impl ConversationView {
+    pub fn new(project_name: String, session_id: String, messages: Vec<Message>) -> Self {
+        Self {
+            project_name,
+            session_id,
+            messages,
+            selected: 0,
+            expanded: HashSet::new(),
+            scroll: 0,
+            follow: true,
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.messages.len().saturating_sub(1));
+        self.follow = true;
+    }
+
+    /// Expand or collapse the selected tool call
+    fn toggle_selected(&mut self) {
+        if matches!(self.messages.get(self.selected), Some(Message::Tool { .. })) && !self.expanded.remove(&self.selected) {
+            self.expanded.insert(self.selected);
+        }
+    }
+
+    /// Expand every tool call, or collapse them all if they already are
+    fn toggle_all(&mut self) {
+        let tools: HashSet<usize> = self
+            .messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| matches!(m, Message::Tool { .. }))
+            .map(|(i, _)| i)
+            .collect();
+        if self.expanded.is_superset(&tools) {
+            self.expanded.clear();
+        } else {
+            self.expanded = tools;
+        }
+    }
+}
+
+/// Incremental search in the opened session
+#[derive(Default)]
+pub struct Search {
+    pub query: String,
+    /// Typing the query (keys go to the prompt)
+    pub editing: bool,
+    /// Indexes of the matching messages
+    pub matches: Vec<usize>,
+    /// Selected message when the search started, restored by Esc
+    origin: usize,
+}
+
+pub struct App {
+    pub provider: Option<Provider>,
+    pub pane: Pane,
+    pub projects: Vec<ProjectInfo>,
+    pub project_index: usize,
+    pub sessions: Option<SessionList>,
+    pub conversation: Option<ConversationView>,
+    pub search: Search,
+    /// Error or information shown in the status bar
+    pub status: Option<String>,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(provider: Option<Provider>, projects: Vec<ProjectInfo>) -> Self {
+        Self {
+            provider,
+            pane: Pane::Projects,
+            projects,
+            project_index: 0,
+            sessions: None,
+            conversation: None,
+            search: Search::default(),
+            status: None,
+            quit: false,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if self.search.editing {
+            self.handle_search_key(key);
+            return;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+        self.status = None;
+
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => self.next_pane(),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') | KeyCode::Esc => self.previous_pane(),
+            KeyCode::Char('r') => self.reload_projects(),
+            _ => match self.pane {
+                Pane::Projects => self.handle_projects_key(key.code),
+                Pane::Sessions => self.handle_sessions_key(key.code),
+                Pane::Conversation => self.handle_conversation_key(key.code),
+            },
+        }
+    }
+
+    fn handle_projects_key(&mut self, code: KeyCode) {
+        let count = self.projects.len();
+        match code {
+            KeyCode::Down | KeyCode::Char('j') => self.project_index = step(self.project_index, 1, count),
+            KeyCode::Up | KeyCode::Char('k') => self.project_index = step(self.project_index, -1, count),
+            KeyCode::Home | KeyCode::Char('g') => self.project_index = 0,
+            KeyCode::End | KeyCode::Char('G') => self.project_index = count.saturating_sub(1),
+            KeyCode::Enter => self.open_project(),
+            _ => {}
+        }
+    }
+
+    fn handle_sessions_key(&mut self, code: KeyCode) {
+        let Some(sessions) = self.sessions.as_mut() else { return };
+        let count = sessions.len();
+        match code {
+            KeyCode::Down | KeyCode::Char('j') => sessions.selected = step(sessions.selected, 1, count),
+            KeyCode::Up | KeyCode::Char('k') => sessions.selected = step(sessions.selected, -1, count),
+            KeyCode::Home | KeyCode::Char('g') => sessions.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => sessions.selected = count.saturating_sub(1),
+            KeyCode::Enter => self.open_session(),
+            _ => {}
+        }
+    }
+
+    fn handle_conversation_key(&mut self, code: KeyCode) {
+        let Some(view) = self.conversation.as_mut() else { return };
+        match code {
+            KeyCode::Char('j') => view.select(view.selected + 1),
+            KeyCode::Char('k') => view.select(view.selected.saturating_sub(1)),
+            KeyCode::Char('g') | KeyCode::Home => view.select(0),
+            KeyCode::Char('G') | KeyCode::End => view.select(view.messages.len()),
+            KeyCode::Down => {
+                view.scroll += 1;
+                view.follow = false;
+            }
+            KeyCode::Up => {
+                view.scroll = view.scroll.saturating_sub(1);
+                view.follow = false;
+            }
+            KeyCode::PageDown => view.select(view.selected + PAGE_MESSAGES),
+            KeyCode::PageUp => view.select(view.selected.saturating_sub(PAGE_MESSAGES)),
+            KeyCode::Enter | KeyCode::Char(' ') => view.toggle_selected(),
+            KeyCode::Char('o') => view.toggle_all(),
+            KeyCode::Char('/') => {
+                self.search = Search {
+                    editing: true,
+                    origin: view.selected,
+                    ..Search::default()
+                };
+            }
+            KeyCode::Char('n') => self.jump_to_match(true),
+            KeyCode::Char('N') => self.jump_to_match(false),
+            _ => {}
+        }
+    }
+
+    /// Keys typed at the search prompt: the selection follows the first match
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => {
+                self.search.editing = false;
+                if self.search.matches.is_empty() && !self.search.query.is_empty() {
+                    self.status = Some(format!("Aucun résultat pour « {} »", self.search.query));
+                }
+                return;
+            }
+            KeyCode::Esc => {
+                let origin = self.search.origin;
+                self.search = Search::default();
+                if let Some(view) = self.conversation.as_mut() {
+                    view.select(origin);
+                }
+                return;
+            }
+            KeyCode::Backspace => {
+                self.search.query.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => self.search.query.push(c),
+            _ => return,
+        }
+
+        self.update_matches();
+        let origin = self.search.origin;
+        let first = self
+            .search
+            .matches
+            .iter()
+            .find(|&&index| index >= origin)
+            .or(self.search.matches.first())
+            .copied();
+        if let Some(view) = self.conversation.as_mut() {
+            view.select(first.unwrap_or(origin));
+        }
+    }
+
+    fn update_matches(&mut self) {
+        let query = self.search.query.to_lowercase();
+        self.search.matches = match &self.conversation {
+            Some(view) if !query.is_empty() => view
+                .messages
+                .iter()
+                .enumerate()
+                .filter(|(_, m)| message_text(m).to_lowercase().contains(&query))
+                .map(|(i, _)| i)
+                .collect(),
+            _ => Vec::new(),
+        };
+    }
+
+    /// Select the next (or previous) match, wrapping around
+    fn jump_to_match(&mut self, forward: bool) {
+        let Some(view) = self.conversation.as_mut() else { return };
+        let matches = &self.search.matches;
+        let target = if forward {
+            matches.iter().find(|&&i| i > view.selected).or(matches.first())
+        } else {
+            matches.iter().rev().find(|&&i| i < view.selected).or(matches.last())
+        };
+        match target {
+            Some(&index) => view.select(index),
+            None if !self.search.query.is_empty() => {
+                self.status = Some(format!("Aucun résultat pour « {} »", self.search.query));
+            }
+            None => {}
+        }
+    }
+
+    fn next_pane(&mut self) {
+        self.pane = match self.pane {
+            Pane::Projects if self.sessions.is_some() => Pane::Sessions,
+            Pane::Sessions if self.conversation.is_some() => Pane::Conversation,
+            pane => pane,
+        };
+    }
+
+    fn previous_pane(&mut self) {
+        self.pane = match self.pane {
+            Pane::Conversation => Pane::Sessions,
+            _ => Pane::Projects,
+        };
+    }
+
+    fn reload_projects(&mut self) {
+        match projects_for(self.provider) {
+            Ok(projects) => {
+                self.project_index = self.project_index.min(projects.len().saturating_sub(1));
+                self.projects = projects;
+                self.status = Some(format!("{} projets", self.projects.len()));
+            }
+            Err(e) => self.status = Some(e),
+        }
+    }
+
+    fn open_project(&mut self) {
+        let Some(project) = self.projects.get(self.project_index).cloned() else { return };
+        // Projects are listed once per provider
+        let slug = Provider::from_ai_tool(&project.ai_tool).slug();
+        match list_project_summaries_for_tool(&project.encoded_name, Some(slug)) {
+            Ok(summaries) => {
+                self.sessions = Some(SessionList::new(project, summaries, &Utc::now()));
+                self.pane = Pane::Sessions;
+            }
+            Err(e) => self.status = Some(e),
+        }
+    }
+
+    fn open_session(&mut self) {
+        let Some(sessions) = &self.sessions else { return };
+        let Some(summary) = sessions.current() else { return };
+        match load_conversation(&sessions.project.encoded_name, &summary.session_id) {
+            Ok(history) => {
+                let messages = viewer_messages(&history.messages, history.created_at);
+                self.conversation = Some(ConversationView::new(
+                    sessions.project.name.clone(),
+                    summary.session_id.clone(),
+                    messages,
+                ));
+                self.search = Search::default();
+                self.pane = Pane::Conversation;
+            }
+            Err(e) => self.status = Some(e),
+        }
+    }
+}
+
+/// Move a list selection by `delta`, staying in bounds
+fn step(index: usize, delta: isize, count: usize) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    index.saturating_add_signed(delta).min(count - 1)
+}
+
+fn parse_time(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or(DateTime::<Utc>::MIN_UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::commands::timestamp;
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(app: &mut App, text: &str) {
+        for c in text.chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+    }
+
+    fn summary(session_id: &str, last_time: &str) -> ConversationSummary {
+        ConversationSummary {
+            session_id: session_id.to_string(),
+            start_time: last_time.to_string(),
+            last_time: last_time.to_string(),
+            message_count: 1,
+            last_message_preview: String::new(),
+        }
+    }
+
+    fn text_message(content: &str) -> Message {
+        Message::User {
+            content: content.to_string(),
+            timestamp: Utc::now(),
+            images: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    fn tool_message() -> Message {
+        Message::Tool {
+            name: "Bash".to_string(),
+            input: serde_json::json!({"command": "ls"}),
+            output: Some("Cargo.toml".to_string()),
+            timestamp: Utc::now(),
+            tool_call_id: None,
+            metadata: None,
+        }
+    }
+
+    fn app_with_conversation(messages: Vec<Message>) -> App {
+        let mut app = App::new(None, Vec::new());
+        app.conversation = Some(ConversationView::new("demo".to_string(), "s1".to_string(), messages));
+        app.pane = Pane::Conversation;
+        app
+    }
+
+    #[test]
+    fn test_session_list_groups_by_period() {
+        let now = DateTime::parse_from_rfc3339("2025-03-10T12:00:00Z").unwrap().with_timezone(&Utc);
+        let project = ProjectInfo {
+            name: "demo".to_string(),
+            path: "/tmp/demo".to_string(),
+            encoded_name: "-tmp-demo".to_string(),
+            session_count: 4,
+            ai_tool: crate::domain::models::AiTool::ClaudeCode,
+            last_updated: now,
+            roots: Vec::new(),
+        };
+        let list = SessionList::new(
+            project,
+            vec![
+                summary("old", "2024-01-01T00:00:00Z"),
+                summary("morning", "2025-03-10T08:00:00Z"),
+                summary("noon", "2025-03-10T11:00:00Z"),
+                summary("yesterday", "2025-03-09T20:00:00Z"),
+            ],
+            &now,
+        );
+
+        let periods: Vec<SessionPeriod> = list.groups.iter().map(|(p, _)| *p).collect();
+        assert_eq!(periods, vec![SessionPeriod::Today, SessionPeriod::Yesterday, SessionPeriod::Older]);
+        let order: Vec<&str> = list.sessions().map(|s| s.session_id.as_str()).collect();
+        assert_eq!(order, vec!["noon", "morning", "yesterday", "old"]);
+        assert_eq!(list.current().unwrap().session_id, "noon");
+    }
+
+    #[test]
+    fn test_incremental_search() {
+        let mut app = app_with_conversation(vec![
+            text_message("first"),
+            text_message("Deploy the parser"),
+            tool_message(),
+            text_message("parser fixed"),
+        ]);
+
+        app.handle_key(key(KeyCode::Char('/')));
+        type_text(&mut app, "pars");
+        assert!(app.search.editing);
+        assert_eq!(app.search.matches, vec![1, 3]);
+        assert_eq!(app.conversation.as_ref().unwrap().selected, 1);
+
+        // Keys go to the prompt while editing: 'q' does not quit
+        type_text(&mut app, "q");
+        assert!(!app.quit);
+        assert!(app.search.matches.is_empty());
+        app.handle_key(key(KeyCode::Backspace));
+        app.handle_key(key(KeyCode::Enter));
+        assert!(!app.search.editing);
+
+        app.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(app.conversation.as_ref().unwrap().selected, 3);
+        app.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(app.conversation.as_ref().unwrap().selected, 1);
+        app.handle_key(key(KeyCode::Char('N')));
+        assert_eq!(app.conversation.as_ref().unwrap().selected, 3);
+
+        // Esc at the prompt restores the selection
+        app.handle_key(key(KeyCode::Char('/')));
+        type_text(&mut app, "first");
+        assert_eq!(app.conversation.as_ref().unwrap().selected, 0);
+        app.handle_key(key(KeyCode::Esc));
+        assert_eq!(app.conversation.as_ref().unwrap().selected, 3);
+        assert!(app.search.query.is_empty());
+    }
+
+    #[test]
+    fn test_session_file_shows_tool_calls() {
+        let fixture = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/claude_tool_session.jsonl");
+        let history = crate::history::parse_jsonl_file(&fixture).unwrap();
+        let messages = viewer_messages(&history.messages, history.created_at);
+
+        assert_eq!(messages.len(), 4);
+        assert!(matches!(&messages[1], Message::Assistant { content, .. } if content == "Let me look."));
+        match &messages[2] {
+            Message::Tool { name, input, output, .. } => {
+                assert_eq!(name, "Bash");
+                assert_eq!(input["command"], "ls");
+                assert_eq!(output.as_deref(), Some("Cargo.toml\nsrc"));
+            }
+            other => panic!("expected a tool call, got {:?}", other),
+        }
+        // No timestamp: the previous message's, not the current time
+        assert_eq!(timestamp(&messages[3]), timestamp(&messages[2]));
+
+        let mut app = app_with_conversation(messages);
+        app.handle_key(key(KeyCode::Char('o')));
+        assert_eq!(app.conversation.as_ref().unwrap().expanded, HashSet::from([2]));
+    }
+
+    #[test]
+    fn test_tool_calls_collapse() {
+        let mut app = app_with_conversation(vec![text_message("run it"), tool_message(), tool_message()]);
+
+        // Only tool calls expand
+        app.handle_key(key(KeyCode::Enter));
+        assert!(app.conversation.as_ref().unwrap().expanded.is_empty());
+
+        app.handle_key(key(KeyCode::Char('j')));
+        app.handle_key(key(KeyCode::Enter));
+        assert_eq!(app.conversation.as_ref().unwrap().expanded, HashSet::from([1]));
+
+        app.handle_key(key(KeyCode::Char('o')));
+        assert_eq!(app.conversation.as_ref().unwrap().expanded, HashSet::from([1, 2]));
+        app.handle_key(key(KeyCode::Char('o')));
+        assert!(app.conversation.as_ref().unwrap().expanded.is_empty());
+
+        app.handle_key(key(KeyCode::Char('G')));
+        assert_eq!(app.conversation.as_ref().unwrap().selected, 2);
+        app.handle_key(key(KeyCode::Char('q')));
+        assert!(app.quit);
+    }
+}
@@ -0,0 +1,38 @@
+//! `hub tui`: terminal session browser, for when the web UI is out of reach (tmux over SSH...)
+//!
+//! Three panes: projects, sessions grouped by period like the web sidebar, and
+//! the selected conversation with collapsible tool calls and incremental search.
+//! Reads the history files directly, like the other `hub` commands.
+
+pub mod app;
+pub mod ui;
+
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+
+use super::commands::projects_for;
+use super::Provider;
+use app::App;
+
+/// Run until `q`; the terminal is restored on exit and on panic
+pub fn run(provider: Option<Provider>) -> Result<(), String> {
+    let projects = projects_for(provider)?;
+    let mut app = App::new(provider, projects);
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result.map_err(|e| format!("Terminal error: {}", e))
+}
+
+fn event_loop(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> std::io::Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| ui::draw(frame, app))?;
+        if let Event::Key(key) = event::read()? {
+            // Windows also reports releases
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,307 @@
+//! Drawing of `hub tui`: projects | sessions | conversation, and a status bar
+
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+use regex::Regex;
+
+use super::app::{App, ConversationView, Pane, SessionList};
+use crate::cli::commands::{capitalize, format_time, one_line, query_regex, role, timestamp};
+use crate::cli::Provider;
+use crate::domain::models::Message;
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [main, status] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [projects, sessions, conversation] = Layout::horizontal([
+        Constraint::Percentage(20),
+        Constraint::Percentage(25),
+        Constraint::Percentage(55),
+    ])
+    .areas(main);
+
+    draw_projects(frame, app, projects);
+    draw_sessions(frame, app, sessions);
+    draw_conversation(frame, app, conversation);
+    draw_status(frame, app, status);
+}
+
+fn pane_block(title: String, focused: bool) -> Block<'static> {
+    let block = Block::bordered().title(title);
+    if focused {
+        block.border_style(Style::new().fg(Color::Cyan))
+    } else {
+        block
+    }
+}
+
+fn draw_projects(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .projects
+        .iter()
+        .map(|project| {
+            ListItem::new(Line::from(vec![
+                Span::raw(project.name.clone()),
+                Span::raw(format!(" {}", project.session_count)).dim(),
+                Span::raw(format!(" {}", Provider::from_ai_tool(&project.ai_tool).slug())).dim(),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .block(pane_block(format!(" Projets ({}) ", app.projects.len()), app.pane == Pane::Projects))
+        .highlight_style(Style::new().reversed());
+    let mut state = ListState::default().with_selected((!app.projects.is_empty()).then_some(app.project_index));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_sessions(frame: &mut Frame, app: &App, area: Rect) {
+    let focused = app.pane == Pane::Sessions;
+    let Some(sessions) = &app.sessions else {
+        let hint = Paragraph::new("Entrée sur un projet").dim();
+        frame.render_widget(hint.block(pane_block(" Sessions ".to_string(), focused)), area);
+        return;
+    };
+
+    let (items, selected_row) = session_items(sessions);
+    let title = format!(" {} ({}) ", sessions.project.name, sessions.len());
+    let list = List::new(items)
+        .block(pane_block(title, focused))
+        .highlight_style(Style::new().reversed());
+    let mut state = ListState::default().with_selected(selected_row);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Period headers followed by their sessions; row of the selected session
+fn session_items(sessions: &SessionList) -> (Vec<ListItem<'static>>, Option<usize>) {
+    let mut items = Vec::new();
+    let mut selected_row = None;
+    let mut index = 0;
+    for (period, group) in &sessions.groups {
+        items.push(ListItem::new(Line::from(period.label()).bold().fg(Color::Yellow)));
+        for session in group {
+            if index == sessions.selected {
+                selected_row = Some(items.len());
+            }
+            let preview = one_line(&session.last_message_preview);
+            items.push(ListItem::new(Line::from(vec![
+                Span::raw(format!("{:>4} ", session.message_count)).dim(),
+                Span::raw(if preview.is_empty() { session.session_id.clone() } else { preview }),
+            ])));
+            index += 1;
+        }
+    }
+    (items, selected_row)
+}
+
+fn draw_conversation(frame: &mut Frame, app: &mut App, area: Rect) {
+    let focused = app.pane == Pane::Conversation;
+    let pattern = (!app.search.query.is_empty())
+        .then(|| query_regex(&app.search.query).ok())
+        .flatten();
+    let Some(view) = app.conversation.as_mut() else {
+        let hint = Paragraph::new("Entrée sur une session").dim();
+        frame.render_widget(hint.block(pane_block(" Conversation ".to_string(), focused)), area);
+        return;
+    };
+
+    let block = pane_block(format!(" {} · {} ", view.project_name, view.session_id), focused);
+    let inner = block.inner(area);
+    let (lines, starts) = conversation_lines(view, inner.width as usize, pattern.as_ref());
+
+    // Keep the selected message visible, unless scrolled by hand
+    let height = inner.height as usize;
+    if view.follow {
+        let start = starts.get(view.selected).copied().unwrap_or(0);
+        if start < view.scroll || start >= view.scroll + height {
+            view.scroll = start;
+        }
+        view.follow = false;
+    }
+    view.scroll = view.scroll.min(lines.len().saturating_sub(1));
+
+    let paragraph = Paragraph::new(lines).block(block).scroll((view.scroll as u16, 0));
+    frame.render_widget(paragraph, area);
+}
+
+/// Lines of every message, wrapped to `width`, and the first line of each message
+fn conversation_lines(view: &ConversationView, width: usize, pattern: Option<&Regex>) -> (Vec<Line<'static>>, Vec<usize>) {
+    let width = width.max(10);
+    let mut lines = Vec::new();
+    let mut starts = Vec::with_capacity(view.messages.len());
+
+    for (index, message) in view.messages.iter().enumerate() {
+        starts.push(lines.len());
+        let selected = index == view.selected;
+        let expanded = view.expanded.contains(&index);
+
+        let heading = match message {
+            Message::Assistant { model: Some(model), .. } => format!("Assistant ({})", model),
+            Message::Tool { name, .. } => format!("{} Tool {}", if expanded { "▾" } else { "▸" }, name),
+            other => capitalize(role(other)),
+        };
+        let marker = if selected { "▶ " } else { "  " };
+        let mut heading_style = Style::new().bold().fg(role_color(message));
+        if selected {
+            heading_style = heading_style.reversed();
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}{}", marker, heading), heading_style),
+            Span::raw(format!("  {}", format_time(timestamp(message)))).dim(),
+        ]));
+
+        let body = match message {
+            Message::Tool { input, output, .. } if expanded => {
+                let input = serde_json::to_string_pretty(input).unwrap_or_else(|_| input.to_string());
+                match output {
+                    Some(output) => format!("{}\n→ {}", input, output),
+                    None => input,
+                }
+            }
+            Message::Tool { input, .. } => one_line(&input.to_string()),
+            Message::Todo { items, .. } => items
+                .iter()
+                .map(|item| {
+                    let mark = match item.status.as_str() {
+                        "completed" => "[x]",
+                        "in_progress" => "[~]",
+                        _ => "[ ]",
+                    };
+                    format!("{} {}", mark, item.content)
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Message::User { content, .. }
+            | Message::Assistant { content, .. }
+            | Message::System { content, .. }
+            | Message::Thinking { content, .. }
+            | Message::Plan { content, .. } => content.clone(),
+        };
+
+        // A collapsed tool call is one line
+        let collapsed = matches!(message, Message::Tool { .. }) && !expanded;
+        let dim = collapsed || matches!(message, Message::Thinking { .. });
+        for text_line in body.lines() {
+            let wrapped = wrap(text_line, width - 2);
+            let take = if collapsed { 1 } else { wrapped.len() };
+            for segment in wrapped.into_iter().take(take) {
+                let mut line = highlight(&format!("  {}", segment), pattern);
+                if dim {
+                    line = line.dim();
+                }
+                lines.push(line);
+            }
+            if collapsed {
+                break;
+            }
+        }
+        lines.push(Line::default());
+    }
+    (lines, starts)
+}
+
+fn role_color(message: &Message) -> Color {
+    match message {
+        Message::User { .. } => Color::Green,
+        Message::Assistant { .. } => Color::Cyan,
+        Message::Tool { .. } => Color::Magenta,
+        Message::Thinking { .. } | Message::Plan { .. } | Message::Todo { .. } => Color::Blue,
+        Message::System { .. } => Color::Yellow,
+    }
+}
+
+/// Split a line into chunks of at most `width` characters
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    if chars.is_empty() {
+        return vec![String::new()];
+    }
+    chars.chunks(width.max(1)).map(|chunk| chunk.iter().collect()).collect()
+}
+
+/// Search matches in reverse video
+fn highlight(text: &str, pattern: Option<&Regex>) -> Line<'static> {
+    let Some(pattern) = pattern else {
+        return Line::from(text.to_string());
+    };
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for found in pattern.find_iter(text) {
+        spans.push(Span::raw(text[last..found.start()].to_string()));
+        spans.push(Span::raw(found.as_str().to_string()).reversed().fg(Color::Yellow));
+        last = found.end();
+    }
+    spans.push(Span::raw(text[last..].to_string()));
+    Line::from(spans)
+}
+
+fn draw_status(frame: &mut Frame, app: &App, area: Rect) {
+    let line = if app.search.editing {
+        Line::from(vec![
+            Span::raw(format!("/{}", app.search.query)).bold(),
+            Span::raw(format!("  {} résultat(s)", app.search.matches.len())).dim(),
+        ])
+    } else if let Some(status) = &app.status {
+        Line::from(status.clone()).fg(Color::Yellow)
+    } else {
+        let help = match app.pane {
+            Pane::Projects => "j/k déplacer · Entrée ouvrir · r recharger · Tab panneau suivant · q quitter",
+            Pane::Sessions => "j/k déplacer · Entrée ouvrir · Échap retour · q quitter",
+            Pane::Conversation => {
+                "j/k message · ↑/↓ défiler · Entrée outil · o tous les outils · / chercher · n/N résultat · Échap retour"
+            }
+        };
+        Line::from(help).dim()
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_conversation_lines() {
+        let tool = Message::Tool {
+            name: "Read".to_string(),
+            input: serde_json::json!({"file_path": "src/main.rs"}),
+            output: Some("fn main() {}\n// end".to_string()),
+            timestamp: Utc::now(),
+            tool_call_id: None,
+            metadata: None,
+        };
+        let user = Message::User {
+            content: "x".repeat(25),
+            timestamp: Utc::now(),
+            images: Vec::new(),
+            metadata: None,
+        };
+        let mut view = ConversationView::new("demo".to_string(), "s1".to_string(), vec![user, tool]);
+
+        // Wrapped user message (25 chars at width 12 - 2 = 10 → 3 lines), collapsed tool call on one line
+        let (lines, starts) = conversation_lines(&view, 12, None);
+        assert_eq!(starts, vec![0, 5]);
+        assert_eq!(lines.len(), 5 + 3);
+        assert!(lines[5].to_string().contains("▸ Tool Read"));
+
+        view.expanded.insert(1);
+        let (lines, _) = conversation_lines(&view, 80, None);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert!(text.iter().any(|l| l.contains("▾ Tool Read")));
+        assert!(text.iter().any(|l| l.contains("\"file_path\": \"src/main.rs\"")));
+        assert!(text.iter().any(|l| l.contains("→ fn main() {}")));
+        assert!(text.iter().any(|l| l.contains("// end")));
+    }
+
+    #[test]
+    fn test_highlight() {
+        let pattern = query_regex("main").unwrap();
+        let line = highlight("fn Main() -> main", Some(&pattern));
+        assert_eq!(line.spans.len(), 5);
+        assert_eq!(line.spans[1].content, "Main");
+        assert_eq!(line.to_string(), "fn Main() -> main");
+    }
+}
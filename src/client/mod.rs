@@ -0,0 +1,264 @@
+//! Typed client for the hub REST API (`/api/v1`)
+//!
+//! For automations written in Rust (usage reports, bulk exports...). Requests
+//! and responses use the server's own types, so both sides cannot drift.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), cli_session_hub_viewer::client::ClientError> {
+//! use cli_session_hub_viewer::client::HubClient;
+//!
+//! let client = HubClient::new("http://127.0.0.1:3001").with_token("my-token");
+//! for project in client.projects().await? {
+//!     let sessions = client.histories(&project.encoded_name).await?;
+//!     println!("{}: {} sessions", project.name, sessions.len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod stream;
+
+use reqwest::{Method, RequestBuilder, Response};
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::domain::models::{Conversation, ConversationPage, ImageUpload, PageCursor};
+use crate::handlers::abort::AbortResponse;
+use crate::handlers::chat::{ChatRequest, ChatStatusResponse};
+use crate::handlers::histories::{ActiveSessionResponse, HistoryListResponse};
+use crate::handlers::projects::ProjectResponse;
+use crate::handlers::API_V1;
+use crate::history::archive::{ArchiveFormat, ImportReport};
+use crate::history::export::ExportFormat;
+use crate::history::grouping::ConversationSummary;
+use crate::infrastructure::conversation_cache::CacheStats;
+use crate::infrastructure::database::SyncStatus;
+
+pub use stream::{ChatStream, NdjsonDecoder};
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Server returned {status}: {message}")]
+    Status { status: u16, message: String },
+
+    #[error("Invalid response: {0}")]
+    Decode(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Client of one hub server
+#[derive(Debug, Clone)]
+pub struct HubClient {
+    http: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+impl HubClient {
+    /// Client for the server at `base_url` (e.g. `http://127.0.0.1:3001`)
+    pub fn new(base_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            token: None,
+        }
+    }
+
+    /// API token sent as `Authorization: Bearer` (`auth.token` in the server config)
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Use a preconfigured reqwest client (timeouts, proxies...)
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// All projects, most recently updated first
+    pub async fn projects(&self) -> Result<Vec<ProjectResponse>> {
+        self.get_json(self.request(Method::GET, "/projects")).await
+    }
+
+    /// Projects whose name or path contains `query` (case-insensitive)
+    pub async fn search_projects(&self, query: &str) -> Result<Vec<ProjectResponse>> {
+        self.get_json(self.request(Method::GET, "/projects").query(&[("search", query)]))
+            .await
+    }
+
+    /// Session summaries of a project
+    pub async fn histories(&self, encoded_name: &str) -> Result<Vec<ConversationSummary>> {
+        let path = format!("/projects/{}/histories", segment(encoded_name));
+        let response: HistoryListResponse = self.get_json(self.request(Method::GET, &path)).await?;
+        Ok(response.conversations)
+    }
+
+    /// Most recently modified session of a project
+    pub async fn active_session(&self, encoded_name: &str) -> Result<ActiveSessionResponse> {
+        let path = format!("/projects/{}/active-session", segment(encoded_name));
+        self.get_json(self.request(Method::GET, &path)).await
+    }
+
+    /// Every message of a session
+    pub async fn conversation(&self, encoded_name: &str, session_id: &str) -> Result<Conversation> {
+        let path = format!("/projects/{}/histories/{}", segment(encoded_name), segment(session_id));
+        self.get_json(self.request(Method::GET, &path)).await
+    }
+
+    /// One page of a session (`limit` defaults to 100 server-side, capped at 1000)
+    pub async fn conversation_page(
+        &self,
+        encoded_name: &str,
+        session_id: &str,
+        cursor: PageCursor,
+        limit: Option<usize>,
+    ) -> Result<ConversationPage> {
+        let path = format!(
+            "/projects/{}/histories/{}/messages",
+            segment(encoded_name),
+            segment(session_id)
+        );
+        let mut query: Vec<(&str, usize)> = match cursor {
+            PageCursor::Latest => Vec::new(),
+            PageCursor::Before(index) => vec![("before", index)],
+            PageCursor::After(index) => vec![("after", index)],
+            PageCursor::Around(index) => vec![("around", index)],
+        };
+        if let Some(limit) = limit {
+            query.push(("limit", limit));
+        }
+        self.get_json(self.request(Method::GET, &path).query(&query)).await
+    }
+
+    /// Session rendered as Markdown, provider-neutral JSON or single-file HTML
+    /// `theme` (e.g. "dark", "golden") only applies to HTML.
+    pub async fn export(
+        &self,
+        encoded_name: &str,
+        session_id: &str,
+        format: ExportFormat,
+        theme: Option<&str>,
+    ) -> Result<String> {
+        let path = format!(
+            "/projects/{}/histories/{}/export",
+            segment(encoded_name),
+            segment(session_id)
+        );
+        let mut query = vec![("format", format.as_str())];
+        if let Some(theme) = theme {
+            query.push(("theme", theme));
+        }
+        let response = self.send(self.request(Method::GET, &path).query(&query)).await?;
+        Ok(response.text().await?)
+    }
+
+    /// Whole project as an archive (`provider` picks among same-named projects)
+    pub async fn project_archive(
+        &self,
+        encoded_name: &str,
+        provider: Option<&str>,
+        format: ArchiveFormat,
+    ) -> Result<Vec<u8>> {
+        let path = format!("/projects/{}/archive", segment(encoded_name));
+        let mut query = vec![("format", format.as_str())];
+        if let Some(provider) = provider {
+            query.push(("provider", provider));
+        }
+        let response = self.send(self.request(Method::GET, &path).query(&query)).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Import a project archive into the server's read-only `imported` root
+    pub async fn import_project_archive(&self, archive: Vec<u8>) -> Result<ImportReport> {
+        let builder = self
+            .request(Method::POST, "/projects/import")
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .body(archive);
+        self.get_json(builder).await
+    }
+
+    /// Upload an image to attach to a chat request (`ImageUpload::path`)
+    pub async fn upload_image(&self, filename: &str, content_type: &str, bytes: Vec<u8>) -> Result<ImageUpload> {
+        let part = reqwest::multipart::Part::bytes(bytes)
+            .file_name(filename.to_string())
+            .mime_str(content_type)?;
+        let form = reqwest::multipart::Form::new().part("file", part);
+        self.get_json(self.request(Method::POST, "/upload").multipart(form)).await
+    }
+
+    /// Claude CLI availability on the server
+    pub async fn chat_status(&self) -> Result<ChatStatusResponse> {
+        self.get_json(self.request(Method::GET, "/chat/status")).await
+    }
+
+    /// Send a chat message; events are parsed as they arrive
+    /// A request id is generated if missing, so the request can be aborted.
+    pub async fn chat(&self, request: &ChatRequest) -> Result<ChatStream> {
+        let mut request = request.clone();
+        if request.request_id.is_none() {
+            request.request_id = Some(uuid::Uuid::new_v4().to_string());
+        }
+
+        let response = self.send(self.request(Method::POST, "/chat/native").json(&request)).await?;
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+            .or(request.request_id);
+        Ok(ChatStream::new(request_id, Box::pin(response.bytes_stream())))
+    }
+
+    /// Abort a running chat request
+    pub async fn abort(&self, request_id: &str) -> Result<AbortResponse> {
+        let path = format!("/abort/{}", segment(request_id));
+        self.get_json(self.request(Method::POST, &path)).await
+    }
+
+    /// Background sync status
+    pub async fn sync_status(&self) -> Result<SyncStatus> {
+        self.get_json(self.request(Method::GET, "/sync/status")).await
+    }
+
+    /// Conversation cache counters
+    pub async fn cache_stats(&self) -> Result<CacheStats> {
+        self.get_json(self.request(Method::GET, "/cache/stats")).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let builder = self.http.request(method, format!("{}{}{}", self.base_url, API_V1, path));
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
+
+    /// Send, and turn non-2xx answers into `ClientError::Status`
+    async fn send(&self, builder: RequestBuilder) -> Result<Response> {
+        let response = builder.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let message = response.text().await.unwrap_or_default();
+        Err(ClientError::Status {
+            status: status.as_u16(),
+            message,
+        })
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, builder: RequestBuilder) -> Result<T> {
+        let bytes = self.send(builder).await?.bytes().await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+/// Percent-encode a path segment
+fn segment(value: &str) -> String {
+    urlencoding::encode(value).into_owned()
+}
@@ -0,0 +1,118 @@
+//! NDJSON decoding of the chat stream
+
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum::body::Bytes;
+use futures::{Stream, StreamExt};
+
+use crate::cli::claude::executor::StreamResponse;
+use super::ClientError;
+
+/// Splits a byte stream into lines and parses each one as a `StreamResponse`
+#[derive(Debug, Default)]
+pub struct NdjsonDecoder {
+    buffer: Vec<u8>,
+}
+
+impl NdjsonDecoder {
+    /// Feed a chunk, get the events of every line it completes
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<Result<StreamResponse, ClientError>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            events.extend(parse_line(&line));
+        }
+        events
+    }
+
+    /// Event of a last line without trailing newline
+    pub fn finish(&mut self) -> Option<Result<StreamResponse, ClientError>> {
+        let line = std::mem::take(&mut self.buffer);
+        parse_line(&line)
+    }
+}
+
+fn parse_line(line: &[u8]) -> Option<Result<StreamResponse, ClientError>> {
+    let line = line.trim_ascii();
+    if line.is_empty() {
+        return None;
+    }
+    Some(serde_json::from_slice(line).map_err(ClientError::Decode))
+}
+
+type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, reqwest::Error>> + Send>>;
+type EventStream = Pin<Box<dyn Stream<Item = Result<StreamResponse, ClientError>> + Send>>;
+
+/// Events of a running chat request, in order; ends after `done`, `error` or `aborted`
+pub struct ChatStream {
+    /// Id to pass to `HubClient::abort`
+    pub request_id: Option<String>,
+    events: EventStream,
+}
+
+impl ChatStream {
+    pub(crate) fn new(request_id: Option<String>, bytes: ByteStream) -> Self {
+        let state = (bytes, NdjsonDecoder::default(), VecDeque::new(), false);
+        let events = futures::stream::unfold(state, |(mut bytes, mut decoder, mut pending, mut ended)| async move {
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    return Some((event, (bytes, decoder, pending, ended)));
+                }
+                if ended {
+                    return None;
+                }
+                match bytes.next().await {
+                    Some(Ok(chunk)) => pending.extend(decoder.push(&chunk)),
+                    Some(Err(e)) => {
+                        ended = true;
+                        pending.push_back(Err(ClientError::Http(e)));
+                    }
+                    None => {
+                        ended = true;
+                        pending.extend(decoder.finish());
+                    }
+                }
+            }
+        });
+        Self {
+            request_id,
+            events: Box::pin(events),
+        }
+    }
+}
+
+impl Stream for ChatStream {
+    type Item = Result<StreamResponse, ClientError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.as_mut().poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_handles_split_lines() {
+        let mut decoder = NdjsonDecoder::default();
+        let stream = "{\"type\":\"claude_json\",\"data\":{\"type\":\"system\"}}\n\n{\"type\":\"error\",\"error\":\"boom\"}\n{\"type\":\"do";
+
+        let (first, rest) = stream.split_at(20);
+        assert!(decoder.push(first.as_bytes()).is_empty());
+
+        let events = decoder.push(rest.as_bytes());
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], Ok(StreamResponse::ClaudeJson { data }) if data["type"] == "system"));
+        assert!(matches!(&events[1], Ok(StreamResponse::Error { error }) if error == "boom"));
+
+        assert!(decoder.push(b"ne\"}").is_empty());
+        assert!(matches!(decoder.finish(), Some(Ok(StreamResponse::Done))));
+        assert!(decoder.finish().is_none());
+
+        assert!(matches!(decoder.push(b"not json\n").as_slice(), [Err(ClientError::Decode(_))]));
+    }
+}
@@ -0,0 +1,701 @@
+//! Typed application configuration
+//!
+//! Loaded once from a TOML file, then overridden by environment variables,
+//! then validated. Every field has a default, so the file is optional.
+//!
+//! File location: `~/.claude-viewer/config.toml` (or `CLAUDE_VIEWER_CONFIG`).
+//!
+//! ```toml
+//! [server]
+//! host = "127.0.0.1"
+//! port = 3001
+//!
+//! [providers]
+//! claude_dir = "~/.claude/projects"
+//! opencode_dir = "~/.local/share/opencode"
+//! gemini_dir = "~/.gemini/tmp"
+//!
+//! # Additional history roots, scanned together with the default ones
+//! [[providers.roots]]
+//! provider = "claude"                     # claude | opencode | gemini
+//! label = "laptop"
+//! path = "~/backups/laptop/.claude/projects"
+//! read_only = true                        # deletions are refused
+//!
+//! [paths]
+//! data_dir = "~/.claude-viewer"   # database, trash, imports, shares and auth file default to subdirs
+//! upload_dir = "./uploads"
+//! imports_dir = "~/.claude-viewer/imports"   # read-only "imported" root of each provider
+//! shares_dir = "~/.claude-viewer/shares"     # frozen conversations served by share links
+//!
+//! [auth]
+//! token = "..."
+//!
+//! [trash]
+//! retention_days = 30
+//!
+//! [cache]
+//! max_entries = 64        # conversations kept in memory (0 disables the cache)
+//! max_messages = 200000   # total messages across cached conversations
+//!
+//! # Extra secret patterns, on top of the built-in detectors (exports, masked view)
+//! [[redaction.rules]]
+//! name = "internal-host"
+//! pattern = '[a-z0-9-]+\.corp\.example\.com'   # only group 1 is redacted if present
+//! ```
+
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::AiTool;
+use crate::domain::services::redaction::{RedactionRule, Redactor};
+
+/// Environment variable pointing to an alternative config file
+pub const CONFIG_PATH_ENV: &str = "CLAUDE_VIEWER_CONFIG";
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 3001;
+const DEFAULT_RETENTION_DAYS: i64 = 30;
+const DEFAULT_CACHE_MAX_ENTRIES: usize = 64;
+const DEFAULT_CACHE_MAX_MESSAGES: usize = 200_000;
+const REDACTED: &str = "[redacted]";
+
+/// Label of the default root of each provider
+pub const DEFAULT_ROOT_LABEL: &str = "local";
+
+/// Label of the read-only root holding imported project archives
+pub const IMPORTED_ROOT_LABEL: &str = "imported";
+
+static CONFIG: OnceCell<AppConfig> = OnceCell::new();
+static REDACTOR: OnceCell<Redactor> = OnceCell::new();
+
+/// Resolved configuration
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppConfig {
+    pub server: ServerConfig,
+    pub providers: ProvidersConfig,
+    pub paths: PathsConfig,
+    pub auth: AuthSettings,
+    pub trash: TrashSettings,
+    pub cache: CacheSettings,
+    pub redaction: RedactionSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServerConfig {
+    pub host: IpAddr,
+    pub port: u16,
+}
+
+/// History roots of each provider
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProvidersConfig {
+    /// Claude: directory holding `<encoded project>/<session>.jsonl`
+    pub claude_dir: PathBuf,
+    /// OpenCode: data directory holding `storage/{session,message,part}`
+    pub opencode_dir: PathBuf,
+    /// Gemini: directory holding `<hash>/chats/session-*.json`
+    pub gemini_dir: PathBuf,
+    /// Additional named roots (other machines, containers, profiles)
+    pub roots: Vec<HistoryRoot>,
+}
+
+/// A named history directory of a provider
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryRoot {
+    pub ai_tool: AiTool,
+    pub label: String,
+    /// Same layout as the default root (OpenCode: the data directory)
+    pub path: PathBuf,
+    pub read_only: bool,
+}
+
+impl HistoryRoot {
+    /// Directory holding the projects/sessions (see `history::path_utils::get_projects_dir`)
+    /// OpenCode: `<data>/storage/session/global`
+    pub fn projects_dir(&self) -> PathBuf {
+        match self.ai_tool {
+            AiTool::OpenCode => self.path.join("storage").join("session").join("global"),
+            _ => self.path.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PathsConfig {
+    pub data_dir: PathBuf,
+    pub upload_dir: PathBuf,
+    pub database_dir: PathBuf,
+    pub trash_dir: PathBuf,
+    /// Imported project archives, one subdirectory per provider
+    pub imports_dir: PathBuf,
+    /// Snapshots served by share links (`/share/<id>`)
+    pub shares_dir: PathBuf,
+    pub auth_file: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AuthSettings {
+    pub token: Option<String>,
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrashSettings {
+    /// 0 disables automatic purging
+    pub retention_days: i64,
+}
+
+/// Conversation cache bounds (least recently used conversations are evicted first)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CacheSettings {
+    /// 0 disables the cache
+    pub max_entries: usize,
+    pub max_messages: usize,
+}
+
+/// Secret redaction applied to exports and archives (always) and to the masked view
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RedactionSettings {
+    /// User-defined rules, validated on load
+    pub rules: Vec<RedactionRule>,
+}
+
+/// Raw file contents: everything optional, defaults applied on resolve
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawConfig {
+    pub server: RawServer,
+    pub providers: RawProviders,
+    pub paths: RawPaths,
+    pub auth: RawAuth,
+    pub trash: RawTrash,
+    pub cache: RawCache,
+    pub redaction: RawRedaction,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawServer {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawProviders {
+    pub claude_dir: Option<String>,
+    pub opencode_dir: Option<String>,
+    pub gemini_dir: Option<String>,
+    pub roots: Vec<RawRoot>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RawRoot {
+    pub provider: String,
+    pub label: String,
+    pub path: String,
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawPaths {
+    pub data_dir: Option<String>,
+    pub upload_dir: Option<String>,
+    pub database_dir: Option<String>,
+    pub trash_dir: Option<String>,
+    pub imports_dir: Option<String>,
+    pub shares_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawAuth {
+    pub token: Option<String>,
+    pub disabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawTrash {
+    pub retention_days: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawCache {
+    pub max_entries: Option<usize>,
+    pub max_messages: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RawRedaction {
+    pub rules: Vec<RedactionRule>,
+}
+
+impl RawConfig {
+    /// Parse TOML text
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Invalid config: {}", e))
+    }
+
+    /// Apply overrides from a variable lookup (normally `std::env::var`)
+    pub fn apply_env(&mut self, get: impl Fn(&str) -> Option<String>) -> Result<(), String> {
+        let get = |name: &str| get(name).filter(|v| !v.trim().is_empty());
+
+        if let Some(v) = get("CLAUDE_VIEWER_HOST") {
+            self.server.host = Some(v);
+        }
+        if let Some(v) = get("PORT") {
+            self.server.port = Some(v.trim().parse().map_err(|_| format!("PORT: invalid port {:?}", v))?);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_CLAUDE_DIR") {
+            self.providers.claude_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_OPENCODE_DIR") {
+            self.providers.opencode_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_GEMINI_DIR") {
+            self.providers.gemini_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_DATA_DIR") {
+            self.paths.data_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_UPLOAD_DIR") {
+            self.paths.upload_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_DB_PATH") {
+            self.paths.database_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_TRASH_DIR") {
+            self.paths.trash_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_IMPORTS_DIR") {
+            self.paths.imports_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_SHARES_DIR") {
+            self.paths.shares_dir = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_AUTH_TOKEN") {
+            self.auth.token = Some(v);
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_AUTH_DISABLED") {
+            self.auth.disabled = Some(matches!(v.trim(), "1" | "true" | "yes"));
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_TRASH_RETENTION_DAYS") {
+            self.trash.retention_days = Some(
+                v.trim()
+                    .parse()
+                    .map_err(|_| format!("CLAUDE_VIEWER_TRASH_RETENTION_DAYS: invalid number {:?}", v))?,
+            );
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_CACHE_MAX_ENTRIES") {
+            self.cache.max_entries = Some(
+                v.trim()
+                    .parse()
+                    .map_err(|_| format!("CLAUDE_VIEWER_CACHE_MAX_ENTRIES: invalid number {:?}", v))?,
+            );
+        }
+        if let Some(v) = get("CLAUDE_VIEWER_CACHE_MAX_MESSAGES") {
+            self.cache.max_messages = Some(
+                v.trim()
+                    .parse()
+                    .map_err(|_| format!("CLAUDE_VIEWER_CACHE_MAX_MESSAGES: invalid number {:?}", v))?,
+            );
+        }
+        Ok(())
+    }
+
+    /// Fill in defaults, expand `~` and validate
+    pub fn resolve(self, home: &Path, cwd: &Path) -> Result<AppConfig, String> {
+        let path = |value: Option<String>, default: PathBuf| -> Result<PathBuf, String> {
+            match value {
+                Some(v) => expand_path(&v, home, cwd),
+                None => Ok(default),
+            }
+        };
+
+        let host = self.server.host.unwrap_or_else(|| DEFAULT_HOST.to_string());
+        let host: IpAddr = host
+            .trim()
+            .parse()
+            .map_err(|_| format!("server.host: invalid IP address {:?}", host))?;
+        let port = self.server.port.unwrap_or(DEFAULT_PORT);
+        if port == 0 {
+            return Err("server.port: must be between 1 and 65535".to_string());
+        }
+
+        let providers = ProvidersConfig {
+            claude_dir: path(self.providers.claude_dir, home.join(".claude").join("projects"))?,
+            opencode_dir: path(
+                self.providers.opencode_dir,
+                home.join(".local").join("share").join("opencode"),
+            )?,
+            gemini_dir: path(self.providers.gemini_dir, home.join(".gemini").join("tmp"))?,
+            roots: resolve_roots(self.providers.roots, home, cwd)?,
+        };
+
+        let data_dir = path(self.paths.data_dir, home.join(".claude-viewer"))?;
+        let paths = PathsConfig {
+            upload_dir: path(self.paths.upload_dir, cwd.join("uploads"))?,
+            database_dir: path(self.paths.database_dir, data_dir.join("surreal"))?,
+            trash_dir: path(self.paths.trash_dir, data_dir.join("trash"))?,
+            imports_dir: path(self.paths.imports_dir, data_dir.join("imports"))?,
+            shares_dir: path(self.paths.shares_dir, data_dir.join("shares"))?,
+            auth_file: data_dir.join("auth.json"),
+            data_dir,
+        };
+
+        let retention_days = self.trash.retention_days.unwrap_or(DEFAULT_RETENTION_DAYS);
+        if retention_days < 0 {
+            return Err("trash.retention_days: must be >= 0 (0 disables purging)".to_string());
+        }
+
+        Redactor::new(&self.redaction.rules)?;
+
+        let token = self.auth.token.filter(|t| !t.is_empty());
+        if token.as_ref().is_some_and(|t| t.len() < 16) {
+            return Err("auth.token: must be at least 16 characters".to_string());
+        }
+
+        Ok(AppConfig {
+            server: ServerConfig { host, port },
+            providers,
+            paths,
+            auth: AuthSettings {
+                token,
+                disabled: self.auth.disabled.unwrap_or(false),
+            },
+            trash: TrashSettings { retention_days },
+            cache: CacheSettings {
+                max_entries: self.cache.max_entries.unwrap_or(DEFAULT_CACHE_MAX_ENTRIES),
+                max_messages: self.cache.max_messages.unwrap_or(DEFAULT_CACHE_MAX_MESSAGES),
+            },
+            redaction: RedactionSettings {
+                rules: self.redaction.rules,
+            },
+        })
+    }
+}
+
+/// Validate additional roots: known provider, unique non-default label per provider
+fn resolve_roots(raw: Vec<RawRoot>, home: &Path, cwd: &Path) -> Result<Vec<HistoryRoot>, String> {
+    let mut roots: Vec<HistoryRoot> = Vec::new();
+
+    for root in raw {
+        let ai_tool = match root.provider.as_str() {
+            "claude" => AiTool::ClaudeCode,
+            "opencode" => AiTool::OpenCode,
+            "gemini" => AiTool::Gemini,
+            other => {
+                return Err(format!(
+                    "providers.roots: unknown provider {:?} (expected claude, opencode or gemini)",
+                    other
+                ));
+            }
+        };
+
+        let label = root.label.trim().to_string();
+        if label.is_empty() || !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("providers.roots: invalid label {:?} (letters, digits, '-' and '_')", label));
+        }
+        if label == DEFAULT_ROOT_LABEL || label == IMPORTED_ROOT_LABEL || roots.iter().any(|r| r.ai_tool == ai_tool && r.label == label) {
+            return Err(format!("providers.roots: duplicate label {:?} for {}", label, root.provider));
+        }
+
+        let path = expand_path(&root.path, home, cwd)
+            .map_err(|e| format!("providers.roots.{}: {}", label, e))?;
+
+        roots.push(HistoryRoot { ai_tool, label, path, read_only: root.read_only });
+    }
+
+    Ok(roots)
+}
+
+/// Expand `~/` and make relative paths absolute against `cwd`
+fn expand_path(value: &str, home: &Path, cwd: &Path) -> Result<PathBuf, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("empty path".to_string());
+    }
+    let path = match value.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None if value == "~" => home.to_path_buf(),
+        None => PathBuf::from(value),
+    };
+    Ok(if path.is_absolute() { path } else { cwd.join(path) })
+}
+
+impl AppConfig {
+    /// Default config file location
+    pub fn default_path() -> Result<PathBuf, String> {
+        if let Ok(path) = std::env::var(CONFIG_PATH_ENV) {
+            return Ok(PathBuf::from(path));
+        }
+        let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+        Ok(home.join(".claude-viewer").join("config.toml"))
+    }
+
+    /// Load from the config file (if any) and the process environment
+    pub fn load() -> Result<Self, String> {
+        let path = Self::default_path()?;
+        let mut raw = if path.exists() {
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            RawConfig::from_toml(&content).map_err(|e| format!("{}: {}", path.display(), e))?
+        } else {
+            RawConfig::default()
+        };
+
+        raw.apply_env(|name| std::env::var(name).ok())?;
+
+        let home = dirs::home_dir().ok_or("Could not determine home directory")?;
+        let cwd = std::env::current_dir().map_err(|e| format!("Failed to read current directory: {}", e))?;
+        raw.resolve(&home, &cwd)
+    }
+
+    /// OpenCode session files (`storage/session/global`)
+    pub fn opencode_sessions_dir(&self) -> PathBuf {
+        self.providers
+            .opencode_dir
+            .join("storage")
+            .join("session")
+            .join("global")
+    }
+
+    /// Every root of a provider: the default one first, then the additional ones,
+    /// then the imported archives
+    pub fn history_roots(&self, ai_tool: &AiTool) -> Vec<HistoryRoot> {
+        let default_path = match ai_tool {
+            AiTool::ClaudeCode => self.providers.claude_dir.clone(),
+            AiTool::OpenCode => self.providers.opencode_dir.clone(),
+            AiTool::Gemini => self.providers.gemini_dir.clone(),
+        };
+
+        let mut roots = vec![HistoryRoot {
+            ai_tool: ai_tool.clone(),
+            label: DEFAULT_ROOT_LABEL.to_string(),
+            path: default_path,
+            read_only: false,
+        }];
+        roots.extend(self.providers.roots.iter().filter(|r| &r.ai_tool == ai_tool).cloned());
+        roots.push(self.imported_root(ai_tool));
+        roots
+    }
+
+    /// Read-only root where project archives of a provider are unpacked
+    pub fn imported_root(&self, ai_tool: &AiTool) -> HistoryRoot {
+        let provider = match ai_tool {
+            AiTool::ClaudeCode => "claude",
+            AiTool::OpenCode => "opencode",
+            AiTool::Gemini => "gemini",
+        };
+        HistoryRoot {
+            ai_tool: ai_tool.clone(),
+            label: IMPORTED_ROOT_LABEL.to_string(),
+            path: self.paths.imports_dir.join(provider),
+            read_only: true,
+        }
+    }
+
+    /// Built-in detectors plus the configured rules
+    pub fn redactor(&self) -> Redactor {
+        Redactor::new(&self.redaction.rules).unwrap_or_default()
+    }
+
+    /// Copy safe to expose over the API (secrets replaced)
+    pub fn redacted(&self) -> Self {
+        let mut copy = self.clone();
+        copy.auth.token = copy.auth.token.map(|_| REDACTED.to_string());
+        copy
+    }
+}
+
+/// Load and validate the configuration (call once at startup to fail fast)
+pub fn init_config() -> Result<&'static AppConfig, String> {
+    CONFIG.get_or_try_init(AppConfig::load)
+}
+
+/// Current configuration
+/// Falls back to defaults (plus env) if the file is invalid and `init_config` was never called.
+pub fn config() -> &'static AppConfig {
+    CONFIG.get_or_init(|| {
+        AppConfig::load().unwrap_or_else(|e| {
+            tracing::error!("Invalid configuration, using defaults: {}", e);
+            let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/tmp"));
+            let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+            RawConfig::default()
+                .resolve(&home, &cwd)
+                .expect("default configuration is valid")
+        })
+    })
+}
+
+/// Redactor of the current configuration, compiled once
+pub fn redactor() -> &'static Redactor {
+    REDACTOR.get_or_init(|| config().redactor())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolve(raw: RawConfig) -> Result<AppConfig, String> {
+        raw.resolve(Path::new("/home/me"), Path::new("/work"))
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = resolve(RawConfig::default()).unwrap();
+        assert_eq!(config.server.port, 3001);
+        assert_eq!(config.server.host.to_string(), "127.0.0.1");
+        assert_eq!(config.providers.claude_dir, PathBuf::from("/home/me/.claude/projects"));
+        assert_eq!(config.providers.opencode_dir, PathBuf::from("/home/me/.local/share/opencode"));
+        assert_eq!(config.providers.gemini_dir, PathBuf::from("/home/me/.gemini/tmp"));
+        assert_eq!(config.paths.upload_dir, PathBuf::from("/work/uploads"));
+        assert_eq!(config.paths.database_dir, PathBuf::from("/home/me/.claude-viewer/surreal"));
+        assert_eq!(config.paths.trash_dir, PathBuf::from("/home/me/.claude-viewer/trash"));
+        assert_eq!(config.paths.imports_dir, PathBuf::from("/home/me/.claude-viewer/imports"));
+        assert_eq!(config.paths.shares_dir, PathBuf::from("/home/me/.claude-viewer/shares"));
+        assert_eq!(config.trash.retention_days, 30);
+        assert_eq!(config.cache.max_entries, 64);
+        assert_eq!(
+            config.opencode_sessions_dir(),
+            PathBuf::from("/home/me/.local/share/opencode/storage/session/global")
+        );
+    }
+
+    #[test]
+    fn test_toml_and_env_overrides() {
+        let mut raw = RawConfig::from_toml(
+            r#"
+            [server]
+            port = 8080
+            [paths]
+            data_dir = "~/viewer"
+            upload_dir = "files"
+            "#,
+        )
+        .unwrap();
+        raw.apply_env(|name| match name {
+            "PORT" => Some("9000".to_string()),
+            "CLAUDE_VIEWER_CLAUDE_DIR" => Some("/srv/claude".to_string()),
+            _ => None,
+        })
+        .unwrap();
+
+        let config = resolve(raw).unwrap();
+        assert_eq!(config.server.port, 9000);
+        assert_eq!(config.providers.claude_dir, PathBuf::from("/srv/claude"));
+        assert_eq!(config.paths.data_dir, PathBuf::from("/home/me/viewer"));
+        assert_eq!(config.paths.database_dir, PathBuf::from("/home/me/viewer/surreal"));
+        assert_eq!(config.paths.upload_dir, PathBuf::from("/work/files"));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        assert!(RawConfig::from_toml("[server]\nunknown = 1").is_err());
+        assert!(RawConfig::default().apply_env(|n| (n == "PORT").then(|| "http".to_string())).is_err());
+
+        let mut raw = RawConfig::default();
+        raw.server.host = Some("localhost:80".to_string());
+        assert!(resolve(raw).unwrap_err().contains("server.host"));
+
+        let mut raw = RawConfig::default();
+        raw.server.port = Some(0);
+        assert!(resolve(raw).is_err());
+
+        let mut raw = RawConfig::default();
+        raw.trash.retention_days = Some(-1);
+        assert!(resolve(raw).is_err());
+
+        let mut raw = RawConfig::default();
+        raw.auth.token = Some("short".to_string());
+        assert!(resolve(raw).is_err());
+    }
+
+    #[test]
+    fn test_additional_roots() {
+        let raw = RawConfig::from_toml(
+            r#"
+            [[providers.roots]]
+            provider = "claude"
+            label = "laptop"
+            path = "~/backup/claude"
+            read_only = true
+
+            [[providers.roots]]
+            provider = "opencode"
+            label = "ci-runner"
+            path = "/mnt/ci/opencode"
+            "#,
+        )
+        .unwrap();
+        let config = resolve(raw).unwrap();
+
+        let claude = config.history_roots(&AiTool::ClaudeCode);
+        assert_eq!(claude.len(), 3);
+        assert_eq!(claude[0].label, DEFAULT_ROOT_LABEL);
+        assert!(!claude[0].read_only);
+        assert_eq!(claude[1].label, "laptop");
+        assert_eq!(claude[1].path, PathBuf::from("/home/me/backup/claude"));
+        assert!(claude[1].read_only);
+        assert_eq!(claude[2].label, IMPORTED_ROOT_LABEL);
+        assert_eq!(claude[2].path, PathBuf::from("/home/me/.claude-viewer/imports/claude"));
+        assert!(claude[2].read_only);
+
+        let opencode = config.history_roots(&AiTool::OpenCode);
+        assert_eq!(opencode[1].projects_dir(), PathBuf::from("/mnt/ci/opencode/storage/session/global"));
+        assert_eq!(config.history_roots(&AiTool::Gemini).len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_roots() {
+        for toml in [
+            "[[providers.roots]]\nprovider = \"cursor\"\nlabel = \"a\"\npath = \"/x\"",
+            "[[providers.roots]]\nprovider = \"claude\"\nlabel = \"local\"\npath = \"/x\"",
+            "[[providers.roots]]\nprovider = \"claude\"\nlabel = \"imported\"\npath = \"/x\"",
+            "[[providers.roots]]\nprovider = \"claude\"\nlabel = \"a b\"\npath = \"/x\"",
+            "[[providers.roots]]\nprovider = \"claude\"\nlabel = \"a\"\npath = \"/x\"\n[[providers.roots]]\nprovider = \"claude\"\nlabel = \"a\"\npath = \"/y\"",
+        ] {
+            let raw = RawConfig::from_toml(toml).unwrap();
+            assert!(resolve(raw).is_err(), "should reject: {}", toml);
+        }
+    }
+
+    #[test]
+    fn test_redaction_rules() {
+        let raw = RawConfig::from_toml(
+            r#"
+            [[redaction.rules]]
+            name = "internal-host"
+            pattern = '[a-z0-9-]+\.corp\.example\.com'
+            "#,
+        )
+        .unwrap();
+        let config = resolve(raw).unwrap();
+        assert_eq!(config.redaction.rules.len(), 1);
+        assert_eq!(
+            config.redactor().redact("ssh ci-01.corp.example.com"),
+            "ssh [redacted:internal-host]"
+        );
+
+        let raw = RawConfig::from_toml("[[redaction.rules]]\nname = \"broken\"\npattern = \"(\"").unwrap();
+        assert!(resolve(raw).unwrap_err().contains("redaction.rules.broken"));
+    }
+
+    #[test]
+    fn test_redacted_hides_token() {
+        let mut raw = RawConfig::default();
+        raw.auth.token = Some("0123456789abcdef0123".to_string());
+        let config = resolve(raw).unwrap().redacted();
+        assert_eq!(config.auth.token.as_deref(), Some(REDACTED));
+    }
+}
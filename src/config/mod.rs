@@ -1,2 +1,8 @@
-// Old boilerplate config disabled (using sea_orm which was removed)
-// pub mod app_config;
+//! Application configuration (TOML file + environment overrides)
+//! Server-only: file system paths, ports and credentials never reach the WASM client.
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod app_config;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use app_config::{config, init_config, AppConfig, HistoryRoot};
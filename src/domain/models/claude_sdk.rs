@@ -1,12 +1,17 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use super::run_settings::RunSettings;
+use super::stream_event::StreamEvent;
+
 /// Response wrapper from backend stream (NDJSON format)
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum StreamResponse {
     /// Claude SDK message
     ClaudeJson { data: SDKMessage },
+    /// Provider-neutral event (every provider, see `StreamEvent`)
+    Event { event: StreamEvent },
     /// Error message
     Error { error: String },
     /// Stream completed successfully
@@ -127,6 +132,10 @@ pub struct ChatRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub working_directory: Option<String>,
 
+    /// Encoded project name; the server resolves its real path as working directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
+
     /// Permission mode: "default", "plan", "acceptEdits"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub permission_mode: Option<String>,
@@ -142,6 +151,18 @@ pub struct ChatRequest {
     /// CLI provider to use (claude, opencode, gemini)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cli_provider: Option<String>,
+
+    /// Session whose condensed transcript opens this new chat (provider handoff)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handoff_from: Option<String>,
+
+    /// Fork `handoff_from` after this message (0-based) instead of handing it off whole
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fork_at: Option<usize>,
+
+    /// Per-run CLI settings (model, max turns, ...), sent as top-level fields
+    #[serde(flatten)]
+    pub settings: RunSettings,
 }
 
 impl ChatRequest {
@@ -153,10 +174,14 @@ impl ChatRequest {
             session_id: None,
             allowed_tools: None,
             working_directory: None,
+            project_name: None,
             permission_mode: Some("default".to_string()),
             hide_user_message: None,
             image_paths: Vec::new(),
             cli_provider: None,
+            handoff_from: None,
+            fork_at: None,
+            settings: RunSettings::default(),
         }
     }
 
@@ -172,6 +197,12 @@ impl ChatRequest {
         self
     }
 
+    /// Set the project (working directory resolved server-side)
+    pub fn with_project_name(mut self, project_name: String) -> Self {
+        self.project_name = Some(project_name);
+        self
+    }
+
     /// Set image paths for vision capabilities
     pub fn with_images(mut self, image_paths: Vec<String>) -> Self {
         self.image_paths = image_paths;
@@ -201,4 +232,22 @@ impl ChatRequest {
         self.cli_provider = Some(cli_provider);
         self
     }
+
+    /// Open the chat on the transcript of another session (needs the project name)
+    pub fn with_handoff_from(mut self, session_id: String) -> Self {
+        self.handoff_from = Some(session_id);
+        self
+    }
+
+    /// Fork the `handoff_from` session after one of its messages
+    pub fn with_fork_at(mut self, message_index: usize) -> Self {
+        self.fork_at = Some(message_index);
+        self
+    }
+
+    /// Set the run settings
+    pub fn with_run_settings(mut self, settings: RunSettings) -> Self {
+        self.settings = settings;
+        self
+    }
 }
@@ -19,6 +19,7 @@ pub const MAX_IMAGES_PER_MESSAGE: usize = 20;
 /// Image attachment for chat messages
 /// Contains both URL for display and path for CLI integration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 pub struct ImageAttachment {
     pub id: String,
     pub url: String,           // URL for frontend display (/api/images/{id})
@@ -34,6 +35,7 @@ pub struct ImageAttachment {
 
 /// Image upload response (extended)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 pub struct ImageUpload {
     pub id: String,
     pub filename: String,
@@ -23,6 +23,7 @@ pub enum StreamChunk {
 
 /// Log level for system messages
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 #[serde(rename_all = "lowercase")]
 pub enum LogLevel {
     Debug,
@@ -33,6 +34,7 @@ pub enum LogLevel {
 
 /// Additional metadata for messages
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 pub struct MessageMetadata {
     #[serde(default)]
     pub source: Option<String>, // "claude_code" or "opencode"
@@ -42,6 +44,7 @@ pub struct MessageMetadata {
 
 /// TODO item for TodoWrite tool
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 pub struct TodoItem {
     pub content: String,
     pub status: String, // "pending", "in_progress", "completed"
@@ -50,6 +53,7 @@ pub struct TodoItem {
 
 /// Message in a conversation (supports both Claude Code and OpenCode formats)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum Message {
     User {
@@ -114,7 +118,49 @@ pub enum Message {
 
 /// Conversation (collection of messages)
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 pub struct Conversation {
     pub session_id: String,
     pub messages: Vec<Message>,
 }
+
+/// Position of a page of messages in a conversation (indexes are message numbers, 0 = oldest)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "index", rename_all = "lowercase")]
+pub enum PageCursor {
+    /// The most recent messages
+    Latest,
+    /// Messages strictly before this index (scrolling up)
+    Before(usize),
+    /// Messages from this index onwards (scrolling down)
+    After(usize),
+    /// Messages centered on this index (jump to message)
+    Around(usize),
+}
+
+/// A window of a conversation: `messages[0]` is message number `start` of `total`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
+pub struct ConversationPage {
+    pub session_id: String,
+    pub messages: Vec<Message>,
+    pub start: usize,
+    pub total: usize,
+}
+
+impl ConversationPage {
+    /// Index right after the last message of the page
+    pub fn end(&self) -> usize {
+        self.start + self.messages.len()
+    }
+
+    /// Cursor of the previous page (None = first message reached)
+    pub fn before_cursor(&self) -> Option<PageCursor> {
+        (self.start > 0).then_some(PageCursor::Before(self.start))
+    }
+
+    /// Cursor of the next page (None = last message reached)
+    pub fn after_cursor(&self) -> Option<PageCursor> {
+        (self.end() < self.total).then(|| PageCursor::After(self.end()))
+    }
+}
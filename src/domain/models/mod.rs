@@ -15,13 +15,19 @@ pub mod search;
 pub mod ai_execution;
 pub mod image;
 pub mod claude_sdk;
+pub mod share;
+pub mod run_settings;
+pub mod stream_event;
 
-pub use project::{Project, ApiProject, AiTool};
-pub use session::{Session, ApiSession, SessionStatus};
-pub use message::{Conversation, LogLevel, Message, MessageMetadata, PermissionMode, StreamChunk, TodoItem};
+pub use project::{is_gemini_hash, Project, ApiProject, AiTool};
+pub use session::{group_by_period, Session, ApiSession, SessionPeriod, SessionStatus};
+pub use message::{Conversation, ConversationPage, LogLevel, Message, MessageMetadata, PageCursor, PermissionMode, StreamChunk, TodoItem};
 pub use search::*;
 pub use ai_execution::*;
 pub use image::*;
+pub use share::{CreateShareRequest, ShareSummary, SHARE_ROUTE};
+pub use run_settings::{RunSetting, RunSettings};
+pub use stream_event::StreamEvent;
 pub use claude_sdk::{
     AssistantMessage, ChatRequest, ContentItem, SDKMessage, StreamResponse, ToolError,
 };
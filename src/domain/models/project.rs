@@ -2,13 +2,19 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
 /// AI tool type for multi AI support
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
 pub enum AiTool {
     ClaudeCode,
     OpenCode,
     Gemini,
 }
 
+/// Gemini project directories are named after a hex SHA-256 of the project path
+pub fn is_gemini_hash(value: &str) -> bool {
+    value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 /// Project information for database storage
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Project {
@@ -32,4 +38,7 @@ pub struct ApiProject {
     pub session_count: i32,
     pub ai_tool: AiTool,
     pub encoded_name: String,
+    /// Labels of the extra history roots the project was found in
+    #[serde(default)]
+    pub roots: Vec<String>,
 }
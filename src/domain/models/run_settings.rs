@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+/// Per-run CLI settings of a chat request ("run settings" panel of the chat input)
+/// Each one maps to a flag of the provider CLI; see [`RunSetting::supported_by`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
+pub struct RunSettings {
+    /// Model name or alias, as the CLI expects it ("sonnet", "gemini-2.5-pro", "anthropic/claude-sonnet-4")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Maximum number of agent turns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
+    /// Text appended to the provider's system prompt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub append_system_prompt: Option<String>,
+    /// Tools the CLI may not use
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disallowed_tools: Vec<String>,
+    /// Directories the CLI may access besides the working directory (server paths)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_dirs: Vec<String>,
+    /// MCP servers configuration file (server path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mcp_config: Option<String>,
+}
+
+impl RunSettings {
+    /// Settings given in these options
+    pub fn requested(&self) -> Vec<RunSetting> {
+        let mut requested = Vec::new();
+        if self.model.is_some() {
+            requested.push(RunSetting::Model);
+        }
+        if self.max_turns.is_some() {
+            requested.push(RunSetting::MaxTurns);
+        }
+        if self.append_system_prompt.is_some() {
+            requested.push(RunSetting::AppendSystemPrompt);
+        }
+        if !self.disallowed_tools.is_empty() {
+            requested.push(RunSetting::DisallowedTools);
+        }
+        if !self.additional_dirs.is_empty() {
+            requested.push(RunSetting::AdditionalDirs);
+        }
+        if self.mcp_config.is_some() {
+            requested.push(RunSetting::McpConfig);
+        }
+        requested
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requested().is_empty()
+    }
+}
+
+/// One run setting, to check it against a provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunSetting {
+    Model,
+    MaxTurns,
+    AppendSystemPrompt,
+    DisallowedTools,
+    AdditionalDirs,
+    McpConfig,
+}
+
+impl RunSetting {
+    pub const ALL: [RunSetting; 6] = [
+        RunSetting::Model,
+        RunSetting::MaxTurns,
+        RunSetting::AppendSystemPrompt,
+        RunSetting::DisallowedTools,
+        RunSetting::AdditionalDirs,
+        RunSetting::McpConfig,
+    ];
+
+    /// Label shown in the panel and in errors
+    pub fn label(&self) -> &'static str {
+        match self {
+            RunSetting::Model => "Modèle",
+            RunSetting::MaxTurns => "Tours max.",
+            RunSetting::AppendSystemPrompt => "Prompt système additionnel",
+            RunSetting::DisallowedTools => "Outils interdits",
+            RunSetting::AdditionalDirs => "Dossiers supplémentaires",
+            RunSetting::McpConfig => "Configuration MCP",
+        }
+    }
+
+    /// Whether a provider's CLI ("claude", "opencode", "gemini") has a flag for this setting
+    /// Claude: all; OpenCode: `--model`; Gemini: `--model`, `--include-directories`.
+    pub fn supported_by(&self, provider: &str) -> bool {
+        match provider {
+            "claude" => true,
+            "opencode" => matches!(self, RunSetting::Model),
+            "gemini" => matches!(self, RunSetting::Model | RunSetting::AdditionalDirs),
+            _ => false,
+        }
+    }
+}
+
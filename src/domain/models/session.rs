@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 
 /// Session status
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -27,6 +27,67 @@ pub struct Session {
     pub last_time: String,
 }
 
+/// Period a session is listed under in the sidebar (and `hub tui`), by last update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SessionPeriod {
+    Today,
+    Yesterday,
+    LastWeek,
+    LastMonth,
+    Older,
+}
+
+impl SessionPeriod {
+    /// Every period, most recent first
+    pub const ALL: [SessionPeriod; 5] = [
+        SessionPeriod::Today,
+        SessionPeriod::Yesterday,
+        SessionPeriod::LastWeek,
+        SessionPeriod::LastMonth,
+        SessionPeriod::Older,
+    ];
+
+    pub fn of(updated_at: &DateTime<Utc>, now: &DateTime<Utc>) -> Self {
+        let date = updated_at.date_naive();
+        let today = now.date_naive();
+        if date == today {
+            SessionPeriod::Today
+        } else if date == today - Duration::days(1) {
+            SessionPeriod::Yesterday
+        } else if *updated_at >= *now - Duration::days(7) {
+            SessionPeriod::LastWeek
+        } else if *updated_at >= *now - Duration::days(30) {
+            SessionPeriod::LastMonth
+        } else {
+            SessionPeriod::Older
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionPeriod::Today => "Aujourd'hui",
+            SessionPeriod::Yesterday => "Hier",
+            SessionPeriod::LastWeek => "7 derniers jours",
+            SessionPeriod::LastMonth => "30 derniers jours",
+            SessionPeriod::Older => "Plus ancien",
+        }
+    }
+}
+
+/// Split items into the five periods, in `SessionPeriod::ALL` order (empty groups kept)
+pub fn group_by_period<T>(
+    items: Vec<T>,
+    updated_at: impl Fn(&T) -> DateTime<Utc>,
+    now: &DateTime<Utc>,
+) -> Vec<(SessionPeriod, Vec<T>)> {
+    let mut groups: Vec<(SessionPeriod, Vec<T>)> = SessionPeriod::ALL.iter().map(|p| (*p, Vec::new())).collect();
+    for item in items {
+        let period = SessionPeriod::of(&updated_at(&item), now);
+        groups[period as usize].1.push(item);
+    }
+    groups
+}
+
 /// Conversation summary matching TypeScript webui API
 /// ConversationSummary from shared/types.ts
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+/// Public route of a share (outside `/api`, no login required)
+pub const SHARE_ROUTE: &str = "/share";
+
+/// Options of a new share link
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
+pub struct CreateShareRequest {
+    /// claude, opencode or gemini; first provider holding the session if omitted
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// First message shared (index, as in the message pages); default 0
+    #[serde(default)]
+    pub start: Option<usize>,
+    /// Index after the last message shared; default: end of the conversation
+    #[serde(default)]
+    pub end: Option<usize>,
+    /// Lifetime of the link, 1 to 8760 hours; never expires if omitted
+    #[serde(default)]
+    pub expires_in_hours: Option<u32>,
+}
+
+/// An active share link (the frozen conversation itself is not included)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
+pub struct ShareSummary {
+    /// Unguessable id, also the secret of the link
+    pub id: String,
+    /// Path of the public page: `/share/<id>`
+    pub url: String,
+    /// "claude", "opencode" or "gemini"
+    pub provider: String,
+    pub encoded_name: String,
+    pub project_name: String,
+    pub session_id: String,
+    /// Index of the first shared message in the session
+    pub start: usize,
+    pub message_count: usize,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ShareSummary {
+    /// Path of the public page of a share
+    pub fn url_of(id: &str) -> String {
+        format!("{}/{}", SHARE_ROUTE, id)
+    }
+}
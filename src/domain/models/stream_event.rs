@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Provider-neutral chat stream event
+/// Each CLI's JSON output is converted into these by the stream adapter of its
+/// provider (`cli::<provider>::stream`), so the chat renders every provider alike.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(not(target_arch = "wasm32"), derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamEvent {
+    /// The CLI created or resumed a session
+    SessionStarted {
+        session_id: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cwd: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        /// Tools available to the model (when the CLI lists them)
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tools: Vec<String>,
+    },
+    /// Answer text, appended to the current assistant message
+    TextDelta { text: String },
+    /// Reasoning shown apart from the answer
+    Thinking { text: String },
+    /// The model called a tool
+    ToolCall {
+        /// Call ID, matched by the tool result
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        name: String,
+        #[serde(default)]
+        input: Value,
+    },
+    /// Output of a tool call
+    ToolResult {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        output: String,
+        #[serde(default)]
+        is_error: bool,
+    },
+    /// A tool call was refused until the user allows these patterns (Claude)
+    PermissionRequired { tool: String, patterns: Vec<String> },
+    /// Tokens used by the run, sent just before `Result`
+    Usage { input_tokens: u64, output_tokens: u64 },
+    /// End of the run
+    Result {
+        success: bool,
+        /// Why the run failed
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        duration_ms: Option<u64>,
+    },
+}
@@ -1,10 +1,18 @@
-use crate::domain::models::AiExecutionRequest;
+use crate::domain::models::{AiExecutionRequest, StreamEvent};
 use crate::cli::claude::detection::{CliType, detect_cli};
+use crate::cli::gemini::stream::GeminiStreamAdapter;
+use crate::cli::traits::StreamAdapter;
+use std::collections::VecDeque;
 use std::process::Stdio;
-use tokio::process::Command;
+use tokio::io::{AsyncBufReadExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdout, Command};
+use tokio::task::JoinHandle;
 use futures::stream::{self, Stream};
 use std::pin::Pin;
 
+/// Last lines of stderr kept for the error of a failed run
+const STDERR_TAIL_LINES: usize = 20;
+
 /// Bridge for executing Gemini CLI commands
 pub struct GeminiBridge {
     executable_path: std::path::PathBuf,
@@ -22,18 +30,16 @@ impl GeminiBridge {
         })
     }
 
-    /// Execute a Gemini command and return streaming response
+    /// Execute a Gemini command and stream its events as the CLI prints them
+    /// The stream ends with an error when the process fails.
     pub async fn execute_gemini(
         &self,
         request: AiExecutionRequest,
-    ) -> Result<Pin<Box<dyn Stream<Item = Result<String, String>> + Send>>, String> {
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<StreamEvent, String>> + Send>>, String> {
         let mut cmd = Command::new(&self.executable_path);
 
-        // Build Gemini command arguments
-        cmd.arg("code")
-            .arg("--output-format")
-            .arg("stream-json")
-            .arg("--verbose");
+        // One JSON event per line, normalized by GeminiStreamAdapter
+        cmd.arg("--output-format").arg("stream-json");
 
         // Set working directory to project path
         cmd.current_dir(&request.project_path);
@@ -43,28 +49,72 @@ impl GeminiBridge {
             cmd.arg("-p").arg(prompt);
         }
 
-        // Set up streaming
+        // Set up streaming; dropping the stream (client gone) stops the CLI
         cmd.stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
 
-        let child = cmd.spawn()
+        let mut child = cmd.spawn()
             .map_err(|e| format!("Failed to spawn Gemini process: {}", e))?;
+        let stdout = child.stdout.take()
+            .ok_or_else(|| "Failed to capture Gemini stdout".to_string())?;
+        let stderr = child.stderr.take()
+            .ok_or_else(|| "Failed to capture Gemini stderr".to_string())?;
 
-        // For now, execute synchronously and return result as a single-item stream
-        // TODO: Implement proper streaming for Gemini
-        let output = child.wait_with_output().await
-            .map_err(|e| format!("Failed to wait for Gemini process: {}", e))?;
+        // Drained alongside stdout, so a full stderr pipe never blocks the CLI
+        let stderr_tail = tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            let mut tail = VecDeque::with_capacity(STDERR_TAIL_LINES);
+            while let Ok(Some(line)) = lines.next_line().await {
+                if tail.len() == STDERR_TAIL_LINES {
+                    tail.pop_front();
+                }
+                tail.push_back(line);
+            }
+            Vec::from(tail).join("\n")
+        });
 
-        let result = if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            Ok(stdout.to_string())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Gemini process failed: {}", stderr))
+        let state = GeminiStream {
+            lines: BufReader::new(stdout).lines(),
+            adapter: GeminiStreamAdapter,
+            pending: VecDeque::new(),
+            child,
+            stderr_tail,
         };
-
-        let stream = stream::once(async move { result });
+        let stream = stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            loop {
+                if let Some(event) = state.pending.pop_front() {
+                    return Some((Ok(event), Some(state)));
+                }
+                match state.lines.next_line().await {
+                    Ok(Some(line)) => state.pending.extend(state.adapter.parse_line(&line)),
+                    // Output closed: report a failed exit, with the end of what the CLI printed on stderr
+                    Ok(None) => {
+                        return match state.child.wait().await {
+                            Ok(status) if status.success() => None,
+                            Ok(_) => {
+                                let stderr = state.stderr_tail.await.unwrap_or_default();
+                                Some((Err(format!("Gemini process failed: {}", stderr)), None))
+                            }
+                            Err(e) => Some((Err(format!("Failed to wait for Gemini process: {}", e)), None)),
+                        };
+                    }
+                    Err(e) => return Some((Err(format!("Failed to read Gemini output: {}", e)), None)),
+                }
+            }
+        });
         Ok(Box::pin(stream))
     }
 }
 
+/// Reading state of a running Gemini process
+struct GeminiStream {
+    lines: Lines<BufReader<ChildStdout>>,
+    adapter: GeminiStreamAdapter,
+    /// Events of the last line not yet yielded
+    pending: VecDeque<StreamEvent>,
+    child: Child,
+    /// Last lines of stderr, once the process has closed it
+    stderr_tail: JoinHandle<String>,
+}
@@ -5,6 +5,10 @@
 pub mod opencode_parser;
 pub use opencode_parser::*;
 
+// Secret detection for exports and masked rendering
+pub mod redaction;
+pub use redaction::{RedactionRule, Redactor};
+
 // Process bridges (Server-only - use tokio for process spawning)
 #[cfg(not(target_arch = "wasm32"))]
 pub mod opencode_bridge;
@@ -8,12 +8,13 @@ use axum::{
     response::IntoResponse,
     Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::cli::claude::session_manager::get_session_manager;
 
 /// Response for abort requests
-#[derive(Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct AbortResponse {
     pub success: bool,
     pub message: String,
@@ -22,6 +23,16 @@ pub struct AbortResponse {
 
 /// POST /api/abort/:request_id
 /// Abort a running chat request
+#[utoipa::path(
+    post,
+    path = "/abort/{request_id}",
+    tag = "chat",
+    params(("request_id" = String, Path, description = "Request id sent with the chat request")),
+    responses(
+        (status = 200, description = "Process aborted", body = AbortResponse),
+        (status = 404, description = "No running process for this request", body = AbortResponse),
+    )
+)]
 pub async fn abort_handler(
     Path(request_id): Path<String>,
 ) -> impl IntoResponse {
@@ -57,11 +68,17 @@ pub async fn abort_handler(
 
 /// GET /api/processes/active
 /// Get count of active processes
-#[derive(Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ActiveProcessesResponse {
     pub count: usize,
 }
 
+#[utoipa::path(
+    get,
+    path = "/sessions/active",
+    tag = "chat",
+    responses((status = 200, description = "Number of running CLI processes", body = ActiveProcessesResponse))
+)]
 pub async fn active_processes_handler() -> Json<ActiveProcessesResponse> {
     let session_manager = get_session_manager();
     let count = session_manager.active_process_count().await;
@@ -71,12 +88,22 @@ pub async fn active_processes_handler() -> Json<ActiveProcessesResponse> {
 
 /// DELETE /api/sessions/:session_id
 /// Remove a session
-#[derive(Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SessionDeleteResponse {
     pub success: bool,
     pub message: String,
 }
 
+#[utoipa::path(
+    delete,
+    path = "/sessions/{session_id}",
+    tag = "chat",
+    params(("session_id" = String, Path, description = "Session id")),
+    responses(
+        (status = 200, description = "Session removed", body = SessionDeleteResponse),
+        (status = 404, description = "Session not found", body = SessionDeleteResponse),
+    )
+)]
 pub async fn delete_session_handler(
     Path(session_id): Path<String>,
 ) -> impl IntoResponse {
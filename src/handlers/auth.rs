@@ -0,0 +1,284 @@
+//! Authentication handlers and middleware
+//!
+//! - `auth_middleware` guards every route except the login page, auth endpoints and static assets
+//! - POST /api/auth/login: form login (password or token), sets the session cookie
+//! - POST /api/auth/logout: drops the session
+//! - GET  /api/auth/session: session status and CSRF token for scripted clients
+
+use axum::{
+    extract::Request,
+    http::{header, HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Redirect, Response},
+    Form, Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::infrastructure::auth::{
+    auth_config, constant_time_eq, create_session, get_session, remove_session, AuthSession,
+    CSRF_HEADER, SESSION_COOKIE, SESSION_TTL,
+};
+
+/// Paths reachable without being logged in
+/// `/share/`: share links, the unguessable id being the credential
+const PUBLIC_PREFIXES: &[&str] = &["/assets/", "/wasm/", "/_dioxus", "/api/auth/", "/api/v1/auth/", "/share/"];
+const PUBLIC_PATHS: &[&str] = &["/login", "/favicon.ico"];
+
+/// Delay applied to failed logins to slow down guessing
+const FAILED_LOGIN_DELAY: std::time::Duration = std::time::Duration::from_millis(750);
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LoginForm {
+    /// Password or API token
+    pub secret: String,
+    /// Where to redirect after login
+    #[serde(default)]
+    pub next: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuthSessionResponse {
+    pub authenticated: bool,
+    pub csrf_token: Option<String>,
+}
+
+/// Require a valid session cookie or bearer token, plus CSRF protection on
+/// state-changing requests authenticated by cookie.
+pub async fn auth_middleware(request: Request, next: Next) -> Response {
+    let config = auth_config();
+    if config.disabled {
+        return next.run(request).await;
+    }
+
+    let path = request.uri().path().to_string();
+    let method = request.method().clone();
+    let headers = request.headers();
+
+    if is_public_path(&path) {
+        if !is_safe_method(&method) && !is_same_origin(headers) {
+            tracing::warn!("Rejected cross-origin {} {}", method, path);
+            return StatusCode::FORBIDDEN.into_response();
+        }
+        return next.run(request).await;
+    }
+
+    // Bearer token: not sent automatically by browsers, so no CSRF check needed
+    if let Some(token) = bearer_token(headers) {
+        if config.check_token(token) {
+            return next.run(request).await;
+        }
+        tracing::warn!("Invalid API token for {} {}", method, path);
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    }
+
+    if let Some(session) = session_from_headers(headers) {
+        if !is_safe_method(&method) && !csrf_ok(headers, &session) {
+            tracing::warn!("CSRF check failed for {} {}", method, path);
+            return (StatusCode::FORBIDDEN, "CSRF check failed").into_response();
+        }
+        return next.run(request).await;
+    }
+
+    if path.starts_with("/api/") || !is_safe_method(&method) {
+        return (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+    }
+
+    let target = request.uri().path_and_query().map(|pq| pq.as_str()).unwrap_or("/");
+    Redirect::to(&format!("/login?next={}", urlencoding::encode(target))).into_response()
+}
+
+/// POST /api/auth/login
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    tag = "auth",
+    request_body(content = LoginForm, content_type = "application/x-www-form-urlencoded"),
+    responses((status = 303, description = "Redirect to `next` with the session cookie, or back to the login page"))
+)]
+pub async fn login_handler(Form(form): Form<LoginForm>) -> Response {
+    let next = sanitize_next(&form.next);
+
+    if !auth_config().check_secret(&form.secret) {
+        tracing::warn!("Failed login attempt");
+        tokio::time::sleep(FAILED_LOGIN_DELAY).await;
+        let location = format!("/login?error=1&next={}", urlencoding::encode(&next));
+        return Redirect::to(&location).into_response();
+    }
+
+    let session = create_session();
+    tracing::info!("User logged in");
+
+    let cookie = format!(
+        "{}={}; Path=/; HttpOnly; SameSite=Strict; Max-Age={}",
+        SESSION_COOKIE,
+        session.id,
+        SESSION_TTL.as_secs()
+    );
+    ([(header::SET_COOKIE, cookie)], Redirect::to(&next)).into_response()
+}
+
+/// POST /api/auth/logout
+#[utoipa::path(
+    post,
+    path = "/auth/logout",
+    tag = "auth",
+    responses((status = 303, description = "Session dropped, redirect to the login page"))
+)]
+pub async fn logout_handler(headers: HeaderMap) -> Response {
+    if let Some(id) = cookie_value(&headers, SESSION_COOKIE) {
+        remove_session(id);
+    }
+    let cookie = format!("{}=; Path=/; HttpOnly; SameSite=Strict; Max-Age=0", SESSION_COOKIE);
+    ([(header::SET_COOKIE, cookie)], Redirect::to("/login")).into_response()
+}
+
+/// GET /api/auth/session
+#[utoipa::path(
+    get,
+    path = "/auth/session",
+    tag = "auth",
+    responses((status = 200, description = "Session status and CSRF token", body = AuthSessionResponse))
+)]
+pub async fn auth_session_handler(headers: HeaderMap) -> Json<AuthSessionResponse> {
+    let session = session_from_headers(&headers);
+    Json(AuthSessionResponse {
+        authenticated: session.is_some() || auth_config().disabled,
+        csrf_token: session.map(|s| s.csrf_token),
+    })
+}
+
+fn is_public_path(path: &str) -> bool {
+    PUBLIC_PATHS.contains(&path) || PUBLIC_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
+
+fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+}
+
+fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn session_from_headers(headers: &HeaderMap) -> Option<AuthSession> {
+    cookie_value(headers, SESSION_COOKIE).and_then(get_session)
+}
+
+/// Either the session's CSRF token is echoed in a header, or the request comes from our own origin
+fn csrf_ok(headers: &HeaderMap, session: &AuthSession) -> bool {
+    let token_ok = headers
+        .get(CSRF_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), session.csrf_token.as_bytes()));
+    token_ok || is_same_origin(headers)
+}
+
+/// Origin (or Referer) host matches the Host header
+fn is_same_origin(headers: &HeaderMap) -> bool {
+    let Some(host) = headers.get(header::HOST).and_then(|v| v.to_str().ok()) else {
+        return false;
+    };
+    let source = headers
+        .get(header::ORIGIN)
+        .or_else(|| headers.get(header::REFERER))
+        .and_then(|v| v.to_str().ok());
+
+    source
+        .and_then(|url| url.split_once("://"))
+        .map(|(_, rest)| rest.split('/').next().unwrap_or_default())
+        .is_some_and(|origin_host| origin_host.eq_ignore_ascii_case(host))
+}
+
+/// Only allow local redirects after login
+fn sanitize_next(next: &str) -> String {
+    if next.starts_with('/') && !next.starts_with("//") && !next.contains('\\') {
+        next.to_string()
+    } else {
+        "/".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn headers(pairs: &[(header::HeaderName, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.append(name.clone(), HeaderValue::from_str(value).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn test_public_paths() {
+        assert!(is_public_path("/login"));
+        assert!(is_public_path("/api/auth/login"));
+        assert!(is_public_path("/api/v1/auth/login"));
+        assert!(is_public_path("/assets/bundle.css"));
+        assert!(is_public_path("/share/0123abcd"));
+        assert!(!is_public_path("/api/projects"));
+        assert!(!is_public_path("/api/v1/projects"));
+        assert!(!is_public_path("/api/delete_session"));
+        assert!(!is_public_path("/trash"));
+    }
+
+    #[test]
+    fn test_cookie_value() {
+        let map = headers(&[(header::COOKIE, "theme=dark; hub_session=abc123")]);
+        assert_eq!(cookie_value(&map, SESSION_COOKIE), Some("abc123"));
+        assert_eq!(cookie_value(&map, "missing"), None);
+    }
+
+    #[test]
+    fn test_same_origin() {
+        let same = headers(&[(header::HOST, "localhost:8080"), (header::ORIGIN, "http://localhost:8080")]);
+        assert!(is_same_origin(&same));
+
+        let referer = headers(&[(header::HOST, "localhost:8080"), (header::REFERER, "http://localhost:8080/trash")]);
+        assert!(is_same_origin(&referer));
+
+        let cross = headers(&[(header::HOST, "localhost:8080"), (header::ORIGIN, "http://evil.example")]);
+        assert!(!is_same_origin(&cross));
+
+        let missing = headers(&[(header::HOST, "localhost:8080")]);
+        assert!(!is_same_origin(&missing));
+    }
+
+    #[test]
+    fn test_csrf_token_header() {
+        let session = create_session();
+        let map = headers(&[(header::HOST, "localhost"), (header::HeaderName::from_static(CSRF_HEADER), &session.csrf_token)]);
+        assert!(csrf_ok(&map, &session));
+
+        let wrong = headers(&[(header::HOST, "localhost"), (header::HeaderName::from_static(CSRF_HEADER), "nope")]);
+        assert!(!csrf_ok(&wrong, &session));
+        remove_session(&session.id);
+    }
+
+    #[test]
+    fn test_sanitize_next() {
+        assert_eq!(sanitize_next("/trash"), "/trash");
+        assert_eq!(sanitize_next("//evil.example"), "/");
+        assert_eq!(sanitize_next("https://evil.example"), "/");
+        assert_eq!(sanitize_next("/\\evil.example"), "/");
+        assert_eq!(sanitize_next(""), "/");
+    }
+}
@@ -0,0 +1,17 @@
+//! Conversation cache statistics handler
+
+use axum::Json;
+
+use crate::infrastructure::conversation_cache::{conversation_cache, CacheStats};
+
+/// GET /api/cache/stats
+/// Occupancy, hits, misses, evictions and invalidations of the conversation cache
+#[utoipa::path(
+    get,
+    path = "/cache/stats",
+    tag = "server",
+    responses((status = 200, description = "Conversation cache statistics", body = CacheStats))
+)]
+pub async fn cache_stats_handler() -> Json<CacheStats> {
+    Json(conversation_cache().stats())
+}
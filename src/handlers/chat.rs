@@ -1,6 +1,11 @@
-//! Native chat handler using Claude CLI directly
+//! Native chat handler using the provider CLIs directly
 //!
-//! This handler spawns the Claude CLI and streams NDJSON responses.
+//! This handler spawns the selected CLI (Claude by default) and streams NDJSON
+//! responses. Each CLI's JSON output goes through the stream adapter of its
+//! provider, so every line sent is a provider-neutral `StreamEvent`.
+//! With `handoff_from`, a new chat opens on the condensed transcript of another
+//! session (see `history::handoff`); adding `fork_at` forks that session after
+//! one of its messages (see `history::forks`).
 
 use axum::{
     body::Body,
@@ -10,7 +15,8 @@ use axum::{
     Extension,
 };
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use utoipa::ToSchema;
 use tokio_stream::wrappers::ReceiverStream;
 use uuid::Uuid;
 
@@ -19,10 +25,19 @@ use crate::cli::claude::{
     session_manager::{get_session_manager, RequestId},
     detection::{detect_claude_cli, CliDetectionResult},
 };
-use crate::cli::traits::CliExecutor;
+use crate::cli::gemini::executor::GeminiExecutor;
+use crate::cli::opencode::executor::OpenCodeExecutor;
+use crate::cli::traits::{CliExecutor, CliProvider, ExecutorError};
+use crate::domain::models::{AiTool, RunSettings, StreamEvent};
+use crate::history::{find_conversation_file, load_conversation_file};
+use crate::history::forks::{
+    can_fork_natively, discard_copy, prepare_claude_fork, record_fork, track_copy, ForkMethod, SessionFork,
+};
+use crate::history::handoff::{handoff_prompt, load_transcript, opening_message, transcript_budget};
+use crate::history::path_guard::validate_segment;
 
 /// Chat request payload (aligned with domain::models::ChatRequest)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
 pub struct ChatRequest {
     /// Message/prompt to send
     pub message: String,
@@ -35,6 +50,9 @@ pub struct ChatRequest {
     /// Working directory for file operations
     #[serde(default)]
     pub working_directory: Option<String>,
+    /// Encoded project name; its real path (from the session files) takes precedence
+    #[serde(default)]
+    pub project_name: Option<String>,
     /// Allowed tools (optional)
     #[serde(default)]
     pub allowed_tools: Option<Vec<String>>,
@@ -50,6 +68,27 @@ pub struct ChatRequest {
     /// CLI provider to use (claude, opencode, gemini)
     #[serde(default)]
     pub cli_provider: Option<String>,
+    /// Session of `project_name` whose condensed transcript opens a new chat
+    /// (handoff to `cli_provider`); `session_id` is then ignored
+    #[serde(default)]
+    pub handoff_from: Option<String>,
+    /// Fork `handoff_from` after this message (0-based) instead of handing it
+    /// off whole: Claude resumes a copy natively, other providers get a transcript
+    #[serde(default)]
+    pub fork_at: Option<usize>,
+    /// Model, max turns, system prompt, disallowed tools, extra directories, MCP config
+    #[serde(flatten)]
+    pub settings: RunSettings,
+}
+
+impl ChatRequest {
+    /// Request with only a message (other fields default)
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            ..Self::default()
+        }
+    }
 }
 
 /// Chat handler application state
@@ -80,7 +119,26 @@ impl ChatHandlerState {
 }
 
 /// POST /api/chat/native
-/// Native chat handler that calls Claude CLI directly
+/// Native chat handler that calls the selected CLI directly
+#[utoipa::path(
+    post,
+    path = "/chat/native",
+    tag = "chat",
+    request_body = ChatRequest,
+    responses(
+        (
+            status = 200,
+            description = "NDJSON stream, one `StreamResponse` per line, ending with `done`",
+            body = StreamResponse,
+            content_type = "application/x-ndjson",
+            headers(("X-Request-Id" = String, description = "Request id to pass to /abort")),
+        ),
+        (status = 400, description = "Unknown provider, handoff without `project_name`, invalid fork point, or run setting not supported by the CLI"),
+        (status = 404, description = "Handoff or fork source session not found"),
+        (status = 500, description = "The CLI could not be started"),
+        (status = 503, description = "Claude CLI, or the selected CLI, not available on the server"),
+    )
+)]
 pub async fn chat_handler(
     Extension(state): Extension<ChatHandlerState>,
     Json(request): Json<ChatRequest>,
@@ -90,22 +148,47 @@ pub async fn chat_handler(
         .clone()
         .unwrap_or_else(|| Uuid::new_v4().to_string());
 
+    let provider = match request.cli_provider.as_deref() {
+        None => CliProvider::Claude,
+        Some(slug) => slug.parse::<CliProvider>().map_err(|e| {
+            tracing::warn!(request_id = %request_id, "{}", e);
+            StatusCode::BAD_REQUEST
+        })?,
+    };
+
     tracing::info!(
         request_id = %request_id,
+        provider = %provider,
         session_id = ?request.session_id,
+        handoff_from = ?request.handoff_from,
+        fork_at = ?request.fork_at,
         working_directory = ?request.working_directory,
         "Starting chat request"
     );
 
-    // Build execute options
-    let mut options = ExecuteOptions::new(&request.message);
-
-    if let Some(session_id) = &request.session_id {
-        options = options.with_session_id(session_id);
-    }
+    // Build execute options (a handoff or a fork starts a new session)
+    let mut fork = None;
+    let mut options = match (&request.handoff_from, request.fork_at) {
+        (Some(source_session_id), Some(fork_at)) => {
+            let (options, pending) = fork_options(&request, source_session_id, fork_at, provider).await?;
+            fork = Some(pending);
+            options
+        }
+        (Some(source_session_id), None) => {
+            ExecuteOptions::new(handoff_message(&request, source_session_id, None).await?)
+        }
+        (None, Some(_)) => {
+            tracing::warn!(request_id = %request_id, "fork_at without handoff_from");
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        (None, None) => match &request.session_id {
+            Some(session_id) => ExecuteOptions::new(request.message.clone()).with_session_id(session_id),
+            None => ExecuteOptions::new(request.message.clone()),
+        },
+    };
 
-    if let Some(cwd) = &request.working_directory {
-        options = options.with_working_directory(PathBuf::from(cwd));
+    if let Some(cwd) = resolve_working_directory(&request) {
+        options = options.with_working_directory(cwd);
     }
 
     if let Some(tools) = request.allowed_tools {
@@ -143,12 +226,31 @@ pub async fn chat_handler(
         }
     }
 
+    options = apply_run_settings(options, &request.settings).map_err(|status| {
+        if let Some(pending) = &fork {
+            pending.discard_copy();
+        }
+        status
+    })?;
+
     // Create executor and spawn process
-    let executor = ClaudeExecutor::new(&state.cli_detection);
+    let executor = executor_for(provider, &state).await.map_err(|e| {
+        tracing::error!(request_id = %request_id, "{} CLI not available: {}", provider, e);
+        if let Some(pending) = &fork {
+            pending.discard_copy();
+        }
+        StatusCode::SERVICE_UNAVAILABLE
+    })?;
 
     let mut process = executor.execute(options).await.map_err(|e| {
-        tracing::error!(request_id = %request_id, "Failed to execute Claude CLI: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
+        tracing::error!(request_id = %request_id, "Failed to execute {} CLI: {}", provider, e);
+        if let Some(pending) = &fork {
+            pending.discard_copy();
+        }
+        match e {
+            ExecutorError::NotSupported(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
     })?;
 
     // Register process for abort handling
@@ -162,23 +264,20 @@ pub async fn chat_handler(
     // Spawn task to stream output
     let req_id_clone = request_id.clone();
     tokio::spawn(async move {
-        while let Some(result) = process.recv().await {
+        let mut adapter = provider.stream_adapter();
+        'lines: while let Some(result) = process.recv().await {
             match result {
                 Ok(line) => {
-                    // Parse and re-serialize as StreamResponse
-                    let response = if let Ok(json_value) = serde_json::from_str::<serde_json::Value>(&line) {
-                        StreamResponse::claude_json(json_value)
-                    } else {
-                        // If not valid JSON, wrap as error
-                        tracing::warn!(request_id = %req_id_clone, "Received non-JSON line: {}", line);
-                        continue;
-                    };
-
-                    if let Ok(ndjson) = response.to_ndjson() {
-                        let line_with_newline = format!("{}\n", ndjson);
-                        if tx.send(Ok(line_with_newline)).await.is_err() {
-                            tracing::debug!(request_id = %req_id_clone, "Client disconnected");
-                            break;
+                    for event in adapter.parse_line(&line) {
+                        // The new session's ID is known: the fork can be recorded
+                        if let (StreamEvent::SessionStarted { session_id, .. }, Some(pending)) = (&event, fork.as_mut()) {
+                            pending.record(session_id);
+                        }
+                        if let Ok(ndjson) = StreamResponse::event(event).to_ndjson() {
+                            if tx.send(Ok(format!("{}\n", ndjson))).await.is_err() {
+                                tracing::debug!(request_id = %req_id_clone, "Client disconnected");
+                                break 'lines;
+                            }
                         }
                     }
                 }
@@ -193,6 +292,10 @@ pub async fn chat_handler(
             }
         }
 
+        if let Some(pending) = &fork {
+            pending.discard_copy();
+        }
+
         // Send done message
         let done_response = StreamResponse::done();
         if let Ok(ndjson) = done_response.to_ndjson() {
@@ -221,9 +324,230 @@ pub async fn chat_handler(
     Ok(response)
 }
 
+/// Run settings of a request on the execute options
+/// Blank values are ignored; directories and the MCP config must exist on the server.
+fn apply_run_settings(mut options: ExecuteOptions, settings: &RunSettings) -> Result<ExecuteOptions, StatusCode> {
+    let text = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+
+    if let Some(model) = text(&settings.model) {
+        options = options.with_model(model);
+    }
+    if let Some(max_turns) = settings.max_turns {
+        options = options.with_max_turns(max_turns);
+    }
+    if let Some(prompt) = text(&settings.append_system_prompt) {
+        options = options.with_append_system_prompt(prompt);
+    }
+    if !settings.disallowed_tools.is_empty() {
+        options = options.with_disallowed_tools(settings.disallowed_tools.clone());
+    }
+    for dir in &settings.additional_dirs {
+        let dir = PathBuf::from(dir);
+        if !dir.is_absolute() || !dir.is_dir() {
+            tracing::warn!("Additional directory does not exist: {}", dir.display());
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        options = options.with_additional_dir(dir);
+    }
+    if let Some(config) = text(&settings.mcp_config).map(PathBuf::from) {
+        if !config.is_absolute() || !config.is_file() {
+            tracing::warn!("MCP config file does not exist: {}", config.display());
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        options = options.with_mcp_config(config);
+    }
+    Ok(options)
+}
+
+/// Executor of a provider; Claude uses the CLI detected at startup
+async fn executor_for(provider: CliProvider, state: &ChatHandlerState) -> Result<Box<dyn CliExecutor>, ExecutorError> {
+    Ok(match provider {
+        CliProvider::Claude => Box::new(ClaudeExecutor::new(&state.cli_detection)),
+        CliProvider::OpenCode => Box::new(OpenCodeExecutor::auto_detect().await?),
+        CliProvider::Gemini => Box::new(GeminiExecutor::auto_detect().await?),
+    })
+}
+
+/// Project of a handoff or fork source, checked before any file access
+fn source_project(request: &ChatRequest, source_session_id: &str) -> Result<String, StatusCode> {
+    let Some(project_name) = request.project_name.clone() else {
+        tracing::warn!("Handoff from {} without project_name", source_session_id);
+        return Err(StatusCode::BAD_REQUEST);
+    };
+    if validate_segment(&project_name).is_err() || validate_segment(source_session_id).is_err() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(project_name)
+}
+
+/// Opening message of a handoff: the source session's transcript, then the user's message
+/// With `until`, the transcript stops before that message (fork).
+async fn handoff_message(
+    request: &ChatRequest,
+    source_session_id: &str,
+    until: Option<usize>,
+) -> Result<String, StatusCode> {
+    let project_name = source_project(request, source_session_id)?;
+    let source = source_session_id.to_string();
+    let budget = transcript_budget(&request.message);
+    let transcript =
+        tokio::task::spawn_blocking(move || load_transcript(&project_name, &source, until, budget))
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map_err(|e| {
+                tracing::warn!("Handoff source unavailable: {}", e);
+                StatusCode::NOT_FOUND
+            })?;
+
+    tracing::info!(
+        handoff_from = %source_session_id,
+        transcript_bytes = transcript.len(),
+        "Opening chat on a session transcript"
+    );
+    Ok(handoff_prompt(&transcript, &request.message))
+}
+
+/// Options of a fork of `source_session_id` after message `fork_at`
+/// A Claude session of the default root continued with Claude is resumed natively
+/// from a truncated copy; anything else starts from a transcript cut at the fork point.
+async fn fork_options(
+    request: &ChatRequest,
+    source_session_id: &str,
+    fork_at: usize,
+    provider: CliProvider,
+) -> Result<(ExecuteOptions, PendingFork), StatusCode> {
+    let project_name = source_project(request, source_session_id)?;
+    let source = source_session_id.to_string();
+    let (source_file, until) = tokio::task::spawn_blocking(move || {
+        let (ai_tool, file_path) = find_conversation_file(&project_name, &source).ok_or(StatusCode::NOT_FOUND)?;
+        let until = fork_end(&ai_tool, &file_path, &source, fork_at)?;
+        Ok::<_, StatusCode>(((ai_tool, file_path), until))
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
+
+    let mut pending = PendingFork {
+        parent_session_id: source_session_id.to_string(),
+        parent_message: fork_at,
+        provider,
+        method: ForkMethod::Transcript,
+        temp_file: None,
+        recorded: false,
+    };
+
+    match source_file {
+        (AiTool::ClaudeCode, file_path) if provider == CliProvider::Claude && can_fork_natively(&file_path) => {
+            let source = tokio::task::spawn_blocking(move || prepare_claude_fork(&file_path, fork_at, track_copy))
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+                .map_err(|e| {
+                    tracing::warn!("Cannot fork {}: {}", source_session_id, e);
+                    StatusCode::BAD_REQUEST
+                })?;
+            pending.method = ForkMethod::Native;
+            pending.temp_file = source.temp_file;
+            let options = ExecuteOptions::new(opening_message(&request.message))
+                .with_session_id(source.resume_id)
+                .with_fork_session();
+            Ok((options, pending))
+        }
+        _ => {
+            let message = handoff_message(request, source_session_id, Some(until)).await?;
+            Ok((ExecuteOptions::new(message), pending))
+        }
+    }
+}
+
+/// Number of parent messages a fork after message `fork_at` starts from
+/// A fork point past the last message of the session is a bad request.
+fn fork_end(ai_tool: &AiTool, file_path: &Path, session_id: &str, fork_at: usize) -> Result<usize, StatusCode> {
+    let history = load_conversation_file(ai_tool, file_path, session_id).map_err(|e| {
+        tracing::warn!("Fork source unavailable: {}", e);
+        StatusCode::NOT_FOUND
+    })?;
+    let total = history.messages.len();
+    fork_at.checked_add(1).filter(|until| *until <= total).ok_or_else(|| {
+        tracing::warn!("Invalid fork point {} of {} ({} messages)", fork_at, session_id, total);
+        StatusCode::BAD_REQUEST
+    })
+}
+
+/// A fork waiting for its session ID, which the CLI reports once started
+/// (`StreamEvent::SessionStarted`)
+struct PendingFork {
+    parent_session_id: String,
+    parent_message: usize,
+    provider: CliProvider,
+    method: ForkMethod,
+    /// Truncated copy of the parent, deleted when the CLI exits
+    temp_file: Option<PathBuf>,
+    recorded: bool,
+}
+
+impl PendingFork {
+    /// Record the fork once (the first new session ID reported wins)
+    fn record(&mut self, session_id: &str) {
+        let is_copy = self
+            .temp_file
+            .as_deref()
+            .and_then(|file| file.file_stem())
+            .is_some_and(|stem| stem == session_id);
+        if self.recorded || is_copy {
+            return;
+        }
+        self.recorded = true;
+
+        let fork = SessionFork {
+            session_id: session_id.to_string(),
+            parent_session_id: self.parent_session_id.clone(),
+            parent_message: self.parent_message,
+            provider: self.provider.executable_name().to_string(),
+            method: self.method,
+            created_at: chrono::Utc::now(),
+        };
+        tracing::info!(session_id, parent = %fork.parent_session_id, "Recording session fork");
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = record_fork(fork) {
+                tracing::warn!("Failed to record session fork: {}", e);
+            }
+        });
+    }
+
+    fn discard_copy(&self) {
+        if let Some(file) = &self.temp_file {
+            discard_copy(file);
+        }
+    }
+}
+
+/// Working directory for a chat request
+/// The project's real path wins over a client-provided directory; directories
+/// that do not exist are dropped rather than guessed.
+fn resolve_working_directory(request: &ChatRequest) -> Option<PathBuf> {
+    use crate::history::project_identity::resolve_any_project_path;
+
+    let from_project = request.project_name.as_deref().and_then(|name| {
+        let resolved = resolve_any_project_path(name).map(|(_, path)| PathBuf::from(path));
+        if resolved.is_none() {
+            tracing::warn!("Could not determine the working directory of project {}", name);
+        }
+        resolved
+    });
+
+    from_project
+        .or_else(|| request.working_directory.as_ref().map(PathBuf::from))
+        .filter(|dir| {
+            let exists = dir.is_dir();
+            if !exists {
+                tracing::warn!("Working directory does not exist: {}", dir.display());
+            }
+            exists
+        })
+}
+
 /// GET /api/chat/status
 /// Check if Claude CLI is available
-#[derive(Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ChatStatusResponse {
     pub available: bool,
     pub cli_path: Option<String>,
@@ -231,6 +555,12 @@ pub struct ChatStatusResponse {
     pub cli_type: Option<String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/chat/status",
+    tag = "chat",
+    responses((status = 200, description = "Detected Claude CLI", body = ChatStatusResponse))
+)]
 pub async fn chat_status_handler(
     Extension(state): Extension<ChatHandlerState>,
 ) -> Json<ChatStatusResponse> {
@@ -242,7 +572,42 @@ pub async fn chat_status_handler(
     })
 }
 
+/// GET /api/chat/status when the Claude CLI was not found at startup
+pub async fn chat_unavailable_status_handler() -> Json<ChatStatusResponse> {
+    Json(ChatStatusResponse {
+        available: false,
+        cli_path: None,
+        cli_version: None,
+        cli_type: None,
+    })
+}
+
+/// POST /api/chat/native when the Claude CLI was not found at startup
+pub async fn chat_unavailable_handler() -> (StatusCode, &'static str) {
+    (StatusCode::SERVICE_UNAVAILABLE, "Claude CLI not available")
+}
+
 /// Check CLI availability (for initialization)
 pub async fn check_cli_available() -> Result<ChatHandlerState, String> {
     ChatHandlerState::new().await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fork_end() {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/claude_tool_session.jsonl");
+        let end = |fork_at| fork_end(&AiTool::ClaudeCode, &fixture, "fixture-tools", fork_at);
+
+        assert_eq!(end(0), Ok(1));
+        assert_eq!(end(3), Ok(4));
+        // Past the last message, or so large that the end overflows
+        assert_eq!(end(4), Err(StatusCode::BAD_REQUEST));
+        assert_eq!(end(usize::MAX), Err(StatusCode::BAD_REQUEST));
+
+        let missing = fixture.with_file_name("missing.jsonl");
+        assert_eq!(fork_end(&AiTool::ClaudeCode, &missing, "missing", 0), Err(StatusCode::NOT_FOUND));
+    }
+}
@@ -0,0 +1,21 @@
+//! Read-only configuration view
+
+use axum::Json;
+
+use crate::config::{config, AppConfig};
+
+/// GET /api/config
+/// Effective configuration (file + env overrides) with secrets redacted
+#[utoipa::path(
+    get,
+    path = "/config",
+    tag = "server",
+    responses((
+        status = 200,
+        description = "Effective configuration, same layout as config.toml",
+        content_type = "application/json",
+    ))
+)]
+pub async fn config_handler() -> Json<AppConfig> {
+    Json(config().redacted())
+}
@@ -4,17 +4,18 @@
 //! SurrealDB and Claude's JSONL files.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use utoipa::IntoParams;
 
-use crate::history::{load_conversation, ContentBlock};
-use crate::domain::models::{Conversation, Message};
-use chrono::{DateTime, Utc};
+use crate::history::load_conversation;
+use crate::history::message_index::{load_conversation_page, DEFAULT_PAGE_SIZE};
+use crate::domain::models::{Conversation, ConversationPage, Message, PageCursor};
 use crate::infrastructure::database::{Database, ConversationRepository};
 
 /// State for conversation handlers
@@ -42,7 +43,7 @@ pub async fn delete_conversation_handler(
     tracing::info!(session_id = %session_id, "Deleting conversation");
 
     // Delete from database and get source files
-    match ConversationRepository::hard_delete(&state.db, &session_id).await {
+    match ConversationRepository::hard_delete(&state.db, &session_id, None).await {
         Ok(source_files) => {
             let mut files_deleted = Vec::new();
             let mut files_failed = Vec::new();
@@ -197,6 +198,20 @@ pub struct GetConversationPath {
 /// GET /api/projects/{encoded_name}/histories/{session_id}
 /// Retrieve full conversation history with all messages
 /// Returns domain::models::Conversation format for frontend compatibility
+#[utoipa::path(
+    get,
+    path = "/projects/{encoded_name}/histories/{session_id}",
+    tag = "histories",
+    params(
+        ("encoded_name" = String, Path, description = "Encoded project name"),
+        ("session_id" = String, Path, description = "Session id"),
+    ),
+    responses(
+        (status = 200, description = "Every message of the conversation", body = Conversation),
+        (status = 404, description = "Conversation not found", body = String, content_type = "text/plain"),
+        (status = 500, description = "Session file could not be parsed", body = String, content_type = "text/plain"),
+    )
+)]
 pub async fn get_conversation_handler(
     Path(params): Path<GetConversationPath>,
 ) -> Result<Json<Conversation>, (StatusCode, String)> {
@@ -215,50 +230,7 @@ pub async fn get_conversation_handler(
             );
 
             // Convert ConversationHistory to domain::models::Conversation
-            let messages: Vec<Message> = history
-                .messages
-                .into_iter()
-                .map(|m| {
-                    let timestamp = m
-                        .timestamp
-                        .as_ref()
-                        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(Utc::now);
-
-                    let content_text = m
-                        .content
-                        .iter()
-                        .filter_map(|c| match c {
-                            ContentBlock::Text { text } => Some(text.clone()),
-                            _ => None,
-                        })
-                        .collect::<Vec<_>>()
-                        .join("\n");
-
-                    match m.role.as_str() {
-                        "user" | "human" => Message::User {
-                            content: content_text,
-                            timestamp,
-                            images: vec![],
-                            metadata: None,
-                        },
-                        "assistant" => Message::Assistant {
-                            content: content_text,
-                            timestamp,
-                            model: None,
-                            images: vec![],
-                            metadata: None,
-                        },
-                        _ => Message::System {
-                            content: content_text,
-                            timestamp,
-                            level: None,
-                            metadata: None,
-                        },
-                    }
-                })
-                .collect();
+            let messages: Vec<Message> = history.messages.into_iter().map(Message::from).collect();
 
             let conversation = Conversation {
                 session_id: history.session_id,
@@ -282,3 +254,75 @@ pub async fn get_conversation_handler(
         }
     }
 }
+
+/// Query parameters for GET conversation messages (at most one cursor)
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MessagePageQuery {
+    /// Messages strictly before this index
+    pub before: Option<usize>,
+    /// Messages from this index onwards
+    pub after: Option<usize>,
+    /// Messages centered on this index
+    pub around: Option<usize>,
+    /// Page size (default 100, capped at 1000)
+    pub limit: Option<usize>,
+}
+
+impl MessagePageQuery {
+    fn cursor(&self) -> PageCursor {
+        match (self.before, self.after, self.around) {
+            (Some(index), _, _) => PageCursor::Before(index),
+            (_, Some(index), _) => PageCursor::After(index),
+            (_, _, Some(index)) => PageCursor::Around(index),
+            _ => PageCursor::Latest,
+        }
+    }
+}
+
+/// GET /api/projects/{encoded_name}/histories/{session_id}/messages?before=&after=&around=&limit=
+/// Retrieve one page of a conversation (latest messages without cursor)
+/// Only the lines of the page are read from Claude session files.
+#[utoipa::path(
+    get,
+    path = "/projects/{encoded_name}/histories/{session_id}/messages",
+    tag = "histories",
+    params(
+        ("encoded_name" = String, Path, description = "Encoded project name"),
+        ("session_id" = String, Path, description = "Session id"),
+        MessagePageQuery,
+    ),
+    responses(
+        (status = 200, description = "One page of the conversation", body = ConversationPage),
+        (status = 404, description = "Conversation not found", body = String, content_type = "text/plain"),
+        (status = 500, description = "Session file could not be parsed", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn get_conversation_page_handler(
+    Path(params): Path<GetConversationPath>,
+    Query(query): Query<MessagePageQuery>,
+) -> Result<Json<ConversationPage>, (StatusCode, String)> {
+    let cursor = query.cursor();
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+
+    match load_conversation_page(&params.encoded_name, &params.session_id, cursor, limit) {
+        Ok(page) => Ok(Json(ConversationPage {
+            session_id: page.session_id,
+            messages: page.messages.into_iter().map(Message::from).collect(),
+            start: page.start,
+            total: page.total,
+        })),
+        Err(e) => {
+            tracing::error!(
+                session_id = %params.session_id,
+                "Failed to load conversation page: {}", e
+            );
+
+            if e.contains("not found") || e.contains("No such file") {
+                Err((StatusCode::NOT_FOUND, format!("Conversation not found: {}", e)))
+            } else {
+                Err((StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to load conversation: {}", e)))
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Conversation export handler (Markdown, JSON, HTML download)
+
+use axum::{
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use super::conversation::GetConversationPath;
+use crate::history::export::{export_conversation, ExportFormat, ExportedConversation};
+use crate::history::{find_conversation_file, load_conversation_file};
+use crate::shared::hooks::Theme;
+
+/// Query parameters for GET conversation export
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    /// markdown (default), json or html
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Web UI theme applied to HTML exports (dark, light, golden, pistachio, dark-golden, dark-pistachio)
+    pub theme: Option<String>,
+}
+
+/// GET /api/projects/{encoded_name}/histories/{session_id}/export?format=&theme=
+/// Download a conversation, tool calls and results included
+#[utoipa::path(
+    get,
+    path = "/projects/{encoded_name}/histories/{session_id}/export",
+    tag = "histories",
+    params(
+        ("encoded_name" = String, Path, description = "Encoded project name"),
+        ("session_id" = String, Path, description = "Session id"),
+        ExportQuery,
+    ),
+    responses(
+        (status = 200, description = "Markdown document, provider-neutral JSON or single-file HTML page", content(
+            (String = "text/markdown"),
+            (ExportedConversation = "application/json"),
+            (String = "text/html"),
+        )),
+        (status = 404, description = "Conversation not found", body = String, content_type = "text/plain"),
+        (status = 500, description = "Session file could not be parsed", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn export_conversation_handler(
+    Path(params): Path<GetConversationPath>,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    let (ai_tool, file_path) = find_conversation_file(&params.encoded_name, &params.session_id)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Conversation not found: {}", params.session_id)))?;
+
+    let history = load_conversation_file(&ai_tool, &file_path, &params.session_id).map_err(|e| {
+        tracing::error!(session_id = %params.session_id, "Failed to load conversation for export: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to load conversation: {}", e))
+    })?;
+
+    let theme: Theme = query.theme.as_deref().unwrap_or("dark").parse().unwrap_or(Theme::Dark);
+    let body = export_conversation(&history, &ai_tool, query.format, &theme)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
+    tracing::info!(
+        session_id = %params.session_id,
+        format = ?query.format,
+        "Conversation exported"
+    );
+
+    let disposition = format!(
+        "attachment; filename=\"{}.{}\"",
+        download_name(&params.session_id),
+        query.format.extension()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, query.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// Session id reduced to characters safe in a Content-Disposition filename
+fn download_name(session_id: &str) -> String {
+    let name: String = session_id
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    if name.trim_matches('.').is_empty() {
+        "conversation".to_string()
+    } else {
+        name
+    }
+}
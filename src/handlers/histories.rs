@@ -3,17 +3,18 @@ use axum::{
     http::StatusCode,
     Json,
 };
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::history::grouping::ConversationSummary;
 use crate::history::list_project_summaries;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct HistoryListResponse {
     pub conversations: Vec<ConversationSummary>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ActiveSessionResponse {
     pub session_id: Option<String>,
     pub project_name: String,
@@ -22,6 +23,16 @@ pub struct ActiveSessionResponse {
 
 /// GET /api/projects/:encoded_name/histories
 /// List all conversation summaries for a project (like claude-code-webui)
+#[utoipa::path(
+    get,
+    path = "/projects/{encoded_name}/histories",
+    tag = "histories",
+    params(("encoded_name" = String, Path, description = "Encoded project name")),
+    responses(
+        (status = 200, description = "Conversation summaries of the project", body = HistoryListResponse),
+        (status = 500, description = "Session files could not be read"),
+    )
+)]
 pub async fn list_histories_handler(
     Path(encoded_name): Path<String>,
 ) -> Result<Json<HistoryListResponse>, StatusCode> {
@@ -38,6 +49,16 @@ pub async fn list_histories_handler(
 
 /// GET /api/projects/:encoded_name/active-session
 /// Get the most recently modified session for a project (to resume it)
+#[utoipa::path(
+    get,
+    path = "/projects/{encoded_name}/active-session",
+    tag = "histories",
+    params(("encoded_name" = String, Path, description = "Encoded project name")),
+    responses(
+        (status = 200, description = "Most recent session, if any", body = ActiveSessionResponse),
+        (status = 500, description = "Session files could not be read"),
+    )
+)]
 pub async fn get_active_session_handler(
     Path(encoded_name): Path<String>,
 ) -> Result<Json<ActiveSessionResponse>, StatusCode> {
@@ -14,6 +14,33 @@ pub mod conversation;
 /// SSE handler for real-time CLI → Web synchronization
 pub mod sse;
 
+/// Background history → database sync status
+pub mod sync;
+
+/// Conversation cache statistics
+pub mod cache;
+
+/// Conversation download as Markdown, JSON or HTML
+pub mod export;
+
+/// Whole-project archive download and import
+pub mod project_archive;
+
+/// Read-only share links and their public page
+pub mod shares;
+
+/// Login, logout and the auth middleware
+pub mod auth;
+
+/// Read-only view of the effective configuration
+pub mod config;
+
+/// OpenAPI document and stream event JSON Schema
+pub mod openapi;
+
+/// Route table shared by the fullstack and standalone servers
+pub mod router;
+
 pub use projects::list_projects_handler;
 pub use histories::{list_histories_handler, get_active_session_handler};
 pub use chat_proxy::{chat_proxy_handler, abort_proxy_handler};
@@ -25,7 +52,25 @@ pub use abort::{abort_handler, active_processes_handler, delete_session_handler}
 pub use conversation::{
     delete_conversation_handler, archive_conversation_handler,
     update_conversation_handler, get_conversation_handler,
-    ConversationHandlerState,
+    get_conversation_page_handler, ConversationHandlerState,
 };
 
 pub use sse::sse_session_handler;
+
+pub use sync::sync_status_handler;
+
+pub use cache::cache_stats_handler;
+
+pub use export::export_conversation_handler;
+
+pub use project_archive::{export_project_archive_handler, import_project_archive_handler};
+
+pub use shares::{create_share_handler, list_shares_handler, revoke_share_handler, shared_page_handler};
+
+pub use auth::{auth_middleware, login_handler, logout_handler, auth_session_handler};
+
+pub use config::config_handler;
+
+pub use openapi::{openapi_handler, stream_event_schema, stream_schema_handler, ApiDoc};
+
+pub use router::{build_router, serve_api, RouterOptions, API_LEGACY, API_V1};
@@ -0,0 +1,110 @@
+//! OpenAPI document of the v1 REST API
+//!
+//! - GET /api/v1/openapi.json: OpenAPI 3.1 document, generated from the handler
+//!   annotations and the `ToSchema` derives of the request/response types
+//! - GET /api/v1/schema/stream-event.json: JSON Schema of one line of the
+//!   NDJSON stream returned by `POST /api/v1/chat/native`
+
+use axum::Json;
+use serde_json::{json, Value};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::cli::claude::executor::StreamResponse;
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "CLI Session Hub API",
+        version = "1.0.0",
+        description = "Browse Claude Code, OpenCode and Gemini CLI histories and chat with the Claude CLI.\n\n\
+            Authenticate with `Authorization: Bearer <token>` (see `auth.token` in config.toml).",
+    ),
+    servers((url = "/api/v1", description = "Version 1")),
+    paths(
+        super::projects::list_projects_handler,
+        super::project_archive::export_project_archive_handler,
+        super::project_archive::import_project_archive_handler,
+        super::histories::list_histories_handler,
+        super::histories::get_active_session_handler,
+        super::conversation::get_conversation_handler,
+        super::conversation::get_conversation_page_handler,
+        super::export::export_conversation_handler,
+        super::shares::create_share_handler,
+        super::shares::list_shares_handler,
+        super::shares::revoke_share_handler,
+        super::sse::sse_session_handler,
+        super::chat::chat_handler,
+        super::chat::chat_status_handler,
+        super::abort::abort_handler,
+        super::abort::active_processes_handler,
+        super::abort::delete_session_handler,
+        crate::infrastructure::api::upload::upload_image,
+        crate::infrastructure::api::upload::get_image,
+        super::auth::login_handler,
+        super::auth::logout_handler,
+        super::auth::auth_session_handler,
+        super::sync::sync_status_handler,
+        super::cache::cache_stats_handler,
+        super::config::config_handler,
+    ),
+    components(schemas(StreamResponse)),
+    modifiers(&BearerToken),
+    tags(
+        (name = "projects", description = "Projects found in the provider history directories"),
+        (name = "histories", description = "Sessions and their messages"),
+        (name = "shares", description = "Read-only links to redacted conversation snapshots, served on /share/{id}"),
+        (name = "chat", description = "Claude CLI execution (NDJSON stream)"),
+        (name = "uploads", description = "Images attached to chat messages"),
+        (name = "auth", description = "Browser login; scripts use a bearer token instead"),
+        (name = "server", description = "Background services and configuration"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Bearer token security scheme, required on every operation
+struct BearerToken;
+
+impl Modify for BearerToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+        openapi.security = Some(vec![SecurityRequirement::new("bearer", Vec::<String>::new())]);
+    }
+}
+
+/// JSON Schema (2020-12) of a chat stream line
+pub fn stream_event_schema() -> Value {
+    let schema = ApiDoc::openapi()
+        .components
+        .and_then(|components| components.schemas.get("StreamResponse").cloned())
+        .and_then(|schema| serde_json::to_value(schema).ok())
+        .unwrap_or_else(|| json!({}));
+
+    let mut document = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": "/api/v1/schema/stream-event.json",
+        "title": "StreamResponse",
+        "description": "One line of the application/x-ndjson body of POST /api/v1/chat/native. \
+            The stream ends with a `done`, `error` or `aborted` event.",
+    });
+    if let (Some(document), Value::Object(schema)) = (document.as_object_mut(), schema) {
+        for (key, value) in schema {
+            document.entry(key).or_insert(value);
+        }
+    }
+    document
+}
+
+/// GET /api/v1/openapi.json
+pub async fn openapi_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// GET /api/v1/schema/stream-event.json
+pub async fn stream_schema_handler() -> Json<Value> {
+    Json(stream_event_schema())
+}
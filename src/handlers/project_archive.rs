@@ -0,0 +1,131 @@
+//! Whole-project archives: download (`.tar.zst` or zip) and import
+
+use axum::{
+    body::Bytes,
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::config::config;
+use crate::domain::models::AiTool;
+use crate::history::archive::{export_project, import_archive, ArchiveFormat, ImportReport};
+use crate::history::path_guard::validate_segment;
+use crate::history::path_utils::find_project_dirs;
+
+/// Query parameters for GET project archive
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ArchiveQuery {
+    /// claude, opencode or gemini; first provider holding the project if omitted
+    pub provider: Option<String>,
+    /// tar.zst (default) or zip
+    #[serde(default)]
+    pub format: ArchiveFormat,
+}
+
+/// GET /api/projects/{encoded_name}/archive?provider=&format=
+/// Download every session file of a project, the images they reference and a manifest
+#[utoipa::path(
+    get,
+    path = "/projects/{encoded_name}/archive",
+    tag = "projects",
+    params(
+        ("encoded_name" = String, Path, description = "Encoded project name"),
+        ArchiveQuery,
+    ),
+    responses(
+        (status = 200, description = "Archive with manifest.json, files/ and images/", content(
+            (Vec<u8> = "application/zstd"),
+            (Vec<u8> = "application/zip"),
+        )),
+        (status = 400, description = "Unknown provider or invalid project name", body = String, content_type = "text/plain"),
+        (status = 404, description = "Project not found", body = String, content_type = "text/plain"),
+        (status = 500, description = "History files could not be read", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn export_project_archive_handler(
+    Path(encoded_name): Path<String>,
+    Query(query): Query<ArchiveQuery>,
+) -> Result<Response, (StatusCode, String)> {
+    validate_segment(&encoded_name).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let ai_tool = match query.provider.as_deref() {
+        Some("claude") => AiTool::ClaudeCode,
+        Some("opencode") => AiTool::OpenCode,
+        Some("gemini") => AiTool::Gemini,
+        Some(other) => return Err((StatusCode::BAD_REQUEST, format!("Unknown provider: {}", other))),
+        None => [AiTool::ClaudeCode, AiTool::OpenCode, AiTool::Gemini]
+            .into_iter()
+            .find(|ai_tool| !find_project_dirs(ai_tool, &encoded_name).is_empty())
+            .ok_or_else(|| (StatusCode::NOT_FOUND, format!("Project not found: {}", encoded_name)))?,
+    };
+    if find_project_dirs(&ai_tool, &encoded_name).is_empty() {
+        return Err((StatusCode::NOT_FOUND, format!("Project not found: {}", encoded_name)));
+    }
+
+    let format = query.format;
+    let name = encoded_name.clone();
+    let body = tokio::task::spawn_blocking(move || export_project(config(), &ai_tool, &name, format))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(|e| {
+            tracing::error!(project = %encoded_name, "Failed to archive project: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, e)
+        })?;
+
+    tracing::info!(project = %encoded_name, format = format.as_str(), bytes = body.len(), "Project archived");
+
+    let disposition = format!(
+        "attachment; filename=\"{}.{}\"",
+        download_name(&encoded_name),
+        format.as_str()
+    );
+    Ok((
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+/// POST /api/projects/import
+/// Unpack a project archive into the read-only `imported` history root
+#[utoipa::path(
+    post,
+    path = "/projects/import",
+    tag = "projects",
+    request_body(content = Vec<u8>, description = "Archive made by GET /projects/{encoded_name}/archive (.tar.zst or zip)", content_type = "application/octet-stream"),
+    responses(
+        (status = 200, description = "Project imported", body = ImportReport),
+        (status = 400, description = "Invalid archive: unknown format, checksum mismatch, unexpected file...", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn import_project_archive_handler(body: Bytes) -> Result<Json<ImportReport>, (StatusCode, String)> {
+    let report = tokio::task::spawn_blocking(move || import_archive(config(), &body))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?
+        .map_err(|e| {
+            tracing::warn!("Project archive refused: {}", e);
+            (StatusCode::BAD_REQUEST, e)
+        })?;
+    Ok(Json(report))
+}
+
+/// Encoded project name reduced to characters safe in a Content-Disposition filename
+fn download_name(encoded_name: &str) -> String {
+    let name: String = encoded_name
+        .trim_start_matches('-')
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        .collect();
+    if name.is_empty() {
+        "project".to_string()
+    } else {
+        name
+    }
+}
@@ -5,17 +5,20 @@ use axum::{
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
 use crate::domain::models::AiTool;
 use crate::history::{list_projects, ProjectInfo};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ProjectsQuery {
+    /// Case-insensitive filter on project name and path
     #[serde(default)]
     pub search: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ProjectResponse {
     pub name: String,
     pub path: String,
@@ -23,6 +26,7 @@ pub struct ProjectResponse {
     pub ai_tool: AiTool,
     pub encoded_name: String,
     pub last_updated: DateTime<Utc>,
+    pub roots: Vec<String>,
 }
 
 impl From<ProjectInfo> for ProjectResponse {
@@ -34,12 +38,23 @@ impl From<ProjectInfo> for ProjectResponse {
             ai_tool: info.ai_tool,
             encoded_name: info.encoded_name,
             last_updated: info.last_updated,
+            roots: info.roots,
         }
     }
 }
 
 /// GET /api/projects
 /// List all projects from filesystem
+#[utoipa::path(
+    get,
+    path = "/projects",
+    tag = "projects",
+    params(ProjectsQuery),
+    responses(
+        (status = 200, description = "Projects, most recently updated first", body = Vec<ProjectResponse>),
+        (status = 500, description = "History directories could not be read"),
+    )
+)]
 pub async fn list_projects_handler(
     Query(params): Query<ProjectsQuery>,
 ) -> Result<Json<Vec<ProjectResponse>>, StatusCode> {
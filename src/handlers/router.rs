@@ -0,0 +1,207 @@
+//! HTTP routes shared by both entry points
+//!
+//! `main.rs` (Dioxus fullstack) and `bin/server.rs` (API only) build their API
+//! with [`build_router`], so they expose exactly the same routes. What differs
+//! between them is passed as [`RouterOptions`]:
+//! - capabilities detected at startup (the Claude CLI may be missing: chat
+//!   routes then answer 503 instead of disappearing)
+//! - features of the entry point (authentication, permissive CORS)
+//!
+//! Every route exists under `/api/v1` (documented in `openapi.rs`) and under
+//! the original unversioned `/api` prefix. The public page of share links,
+//! `/share/{id}`, is the only route outside of them.
+//!
+//! NOTE: Axum 0.8 uses {param} syntax instead of :param
+
+use std::net::SocketAddr;
+
+use axum::{
+    extract::DefaultBodyLimit,
+    middleware,
+    routing::{delete, get, post},
+    Extension, Router,
+};
+use tower_http::cors::CorsLayer;
+
+use crate::domain::models::SHARE_ROUTE;
+use crate::infrastructure::api::upload::{debug_upload, get_image, upload_image};
+use crate::infrastructure::auth::init_auth;
+use crate::infrastructure::database::start_sync_service;
+use crate::infrastructure::trash::start_trash_purger;
+
+use super::{
+    abort_handler, active_processes_handler, auth_middleware, auth_session_handler, cache_stats_handler,
+    chat::{chat_unavailable_handler, chat_unavailable_status_handler},
+    chat_handler, chat_status_handler, config_handler, create_share_handler, delete_session_handler,
+    export_conversation_handler, export_project_archive_handler, get_active_session_handler,
+    import_project_archive_handler, get_conversation_handler, get_conversation_page_handler,
+    list_histories_handler, list_projects_handler, list_shares_handler, login_handler, logout_handler,
+    revoke_share_handler, shared_page_handler, sse_session_handler, sync_status_handler, ChatHandlerState,
+    openapi::{openapi_handler, stream_schema_handler},
+};
+
+/// Request body limit (uploads): 10MB = MAX_IMAGE_SIZE
+const BODY_LIMIT: usize = 10 * 1024 * 1024;
+
+/// Request body limit of project archive imports: 1GB
+const ARCHIVE_BODY_LIMIT: usize = 1024 * 1024 * 1024;
+
+/// What the entry point provides to the router
+#[derive(Clone)]
+pub struct RouterOptions {
+    /// Claude CLI found at startup; `None` keeps the chat routes but answers 503
+    pub chat: Option<ChatHandlerState>,
+    /// Require a login or API token on every route (see `auth_middleware`)
+    pub auth: bool,
+    /// Permissive CORS, for an API server used from another origin
+    pub cors: bool,
+}
+
+impl Default for RouterOptions {
+    fn default() -> Self {
+        Self {
+            chat: None,
+            auth: true,
+            cors: false,
+        }
+    }
+}
+
+/// Current API version, documented by `/api/v1/openapi.json`
+pub const API_V1: &str = "/api/v1";
+
+/// Unversioned prefix still used by the web UI, same handlers as v1
+pub const API_LEGACY: &str = "/api";
+
+/// Add every API route to `base` (an empty router, or the Dioxus app router)
+///
+/// Routes are served under `/api/v1` and, unchanged, under `/api`.
+/// The auth layer wraps `base` too, so pages and server functions are protected.
+pub fn build_router(base: Router, options: RouterOptions) -> Router {
+    let chat_available = options.chat.is_some();
+    let mut router = base
+        // API description (v1 only)
+        .route(&format!("{API_V1}/openapi.json"), get(openapi_handler))
+        .route(&format!("{API_V1}/schema/stream-event.json"), get(stream_schema_handler))
+        // Public read-only page of a share link (see auth_middleware)
+        .route(&format!("{SHARE_ROUTE}/{{id}}"), get(shared_page_handler));
+    router = api_routes(router, API_V1, chat_available);
+    router = api_routes(router, API_LEGACY, chat_available);
+
+    match options.chat {
+        Some(chat_state) => {
+            tracing::info!("Chat handler state initialized successfully - chat features enabled");
+            // Chat state as Extension (NOT with_state)
+            router = router.layer(Extension(chat_state));
+        }
+        None => {
+            tracing::warn!("Claude CLI not available: chat features disabled, but history viewing works");
+        }
+    }
+
+    router = router.layer(DefaultBodyLimit::max(BODY_LIMIT));
+
+    // Auth must wrap every route, including server functions and pages
+    if options.auth {
+        router = router.layer(middleware::from_fn(auth_middleware));
+    }
+    if options.cors {
+        router = router.layer(CorsLayer::permissive());
+    }
+    router
+}
+
+/// Run the API-only server on `addr` until it stops (`bin/server.rs`, `hub serve`)
+///
+/// Starts the background services the fullstack server starts too: credentials,
+/// history → database sync and the recycle bin purger. Expects `init_config`.
+pub async fn serve_api(addr: SocketAddr) -> Result<(), String> {
+    // Chat routes answer 503 without the Claude CLI
+    let chat = match ChatHandlerState::new().await {
+        Ok(state) => Some(state),
+        Err(e) => {
+            tracing::warn!("Failed to initialize chat handler: {}", e);
+            None
+        }
+    };
+
+    // Load credentials early so a generated token shows up in the startup logs
+    init_auth();
+
+    // Keep SurrealDB in sync with history files
+    start_sync_service();
+
+    // Purge recycle bin items past their retention period
+    start_trash_purger();
+
+    // Same API as the fullstack server, without the Dioxus pages
+    let app = build_router(
+        Router::new(),
+        RouterOptions {
+            chat,
+            auth: true,
+            cors: true,
+        },
+    );
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| format!("Failed to bind {}: {}", addr, e))?;
+    tracing::info!("Server running on http://{}", addr);
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| format!("Server error: {}", e))
+}
+
+/// Routes of one API prefix
+fn api_routes(router: Router, prefix: &str, chat_available: bool) -> Router {
+    let path = |route: &str| format!("{prefix}{route}");
+    let router = router
+        // Projects & Histories routes (stateless)
+        .route(&path("/projects"), get(list_projects_handler))
+        .route(&path("/projects/{encoded_name}/histories"), get(list_histories_handler))
+        .route(&path("/projects/{encoded_name}/archive"), get(export_project_archive_handler))
+        .route(
+            &path("/projects/import"),
+            post(import_project_archive_handler).layer(DefaultBodyLimit::max(ARCHIVE_BODY_LIMIT)),
+        )
+        .route(&path("/projects/{encoded_name}/active-session"), get(get_active_session_handler))
+        // Conversation details (stateless)
+        .route(&path("/projects/{encoded_name}/histories/{session_id}"), get(get_conversation_handler))
+        .route(&path("/projects/{encoded_name}/histories/{session_id}/messages"), get(get_conversation_page_handler))
+        .route(&path("/projects/{encoded_name}/histories/{session_id}/export"), get(export_conversation_handler))
+        // Share links (frozen, redacted copies served on /share/{id})
+        .route(&path("/projects/{encoded_name}/histories/{session_id}/shares"), post(create_share_handler))
+        .route(&path("/shares"), get(list_shares_handler))
+        .route(&path("/shares/{id}"), delete(revoke_share_handler))
+        // SSE endpoint for real-time CLI → Web sync
+        .route(&path("/sse/{encoded_name}/{session_id}"), get(sse_session_handler))
+        // Background history → database sync
+        .route(&path("/sync/status"), get(sync_status_handler))
+        .route(&path("/cache/stats"), get(cache_stats_handler))
+        // Effective configuration (secrets redacted)
+        .route(&path("/config"), get(config_handler))
+        // Authentication (public, see auth_middleware)
+        .route(&path("/auth/login"), post(login_handler))
+        .route(&path("/auth/logout"), post(logout_handler))
+        .route(&path("/auth/session"), get(auth_session_handler))
+        // Image upload routes
+        .route(&path("/upload"), post(upload_image))
+        .route(&path("/upload-debug"), post(debug_upload)) // Debug endpoint without Multipart extractor
+        .route(&path("/images/{image_id}"), get(get_image))
+        // Running CLI processes (session manager, no chat state needed)
+        .route(&path("/abort/{request_id}"), post(abort_handler))
+        .route(&path("/sessions/active"), get(active_processes_handler))
+        .route(&path("/sessions/{session_id}"), delete(delete_session_handler));
+
+    // Without the Claude CLI the chat routes stay, and answer 503
+    if chat_available {
+        router
+            .route(&path("/chat/native"), post(chat_handler))
+            .route(&path("/chat/status"), get(chat_status_handler))
+    } else {
+        router
+            .route(&path("/chat/native"), post(chat_unavailable_handler))
+            .route(&path("/chat/status"), get(chat_unavailable_status_handler))
+    }
+}
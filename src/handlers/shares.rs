@@ -0,0 +1,178 @@
+//! Share links: frozen, redacted conversation snapshots
+//!
+//! - POST   /api/projects/{encoded_name}/histories/{session_id}/shares: create
+//! - GET    /api/shares: active shares
+//! - DELETE /api/shares/{id}: revoke
+//! - GET    /share/{id}: the public read-only page (no login, see `auth_middleware`)
+
+use axum::{
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+
+use super::conversation::GetConversationPath;
+use crate::config::app_config::redactor;
+use crate::config::config;
+use crate::domain::models::{AiTool, CreateShareRequest, ShareSummary};
+use crate::history::export::{normalize, render, ExportFormat};
+use crate::history::path_guard::validate_segment;
+use crate::history::path_utils::find_session_file;
+use crate::history::{find_conversation_file, load_conversation_file};
+use crate::infrastructure::shares::{expiry, message_range, ShareStore};
+use crate::shared::hooks::Theme;
+
+/// Content-Security-Policy of the public share page
+const SHARE_CSP: &str = "default-src 'none'; style-src 'unsafe-inline'; img-src data:";
+
+/// Query parameters of the public share page
+#[derive(Debug, Deserialize)]
+pub struct SharePageQuery {
+    /// html (default), markdown or json
+    pub format: Option<ExportFormat>,
+    /// Web UI theme of the HTML page
+    pub theme: Option<String>,
+}
+
+/// Freeze a session (or a range of its messages) into the share store
+/// Shared by the REST handler and the `create_share` server function.
+pub fn share_session(
+    encoded_name: &str,
+    session_id: &str,
+    request: &CreateShareRequest,
+) -> Result<ShareSummary, (StatusCode, String)> {
+    validate_segment(encoded_name).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    validate_segment(session_id).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let expires_at = expiry(Utc::now(), request.expires_in_hours).map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    let not_found = || (StatusCode::NOT_FOUND, format!("Conversation not found: {}", session_id));
+    let (ai_tool, file_path) = match request.provider.as_deref() {
+        None => find_conversation_file(encoded_name, session_id).ok_or_else(not_found)?,
+        Some(provider) => {
+            let ai_tool = match provider {
+                "claude" => AiTool::ClaudeCode,
+                "opencode" => AiTool::OpenCode,
+                "gemini" => AiTool::Gemini,
+                other => return Err((StatusCode::BAD_REQUEST, format!("Unknown provider: {}", other))),
+            };
+            let (_, file_path) = find_session_file(&ai_tool, encoded_name, session_id).ok_or_else(not_found)?;
+            (ai_tool, file_path)
+        }
+    };
+
+    let history = load_conversation_file(&ai_tool, &file_path, session_id).map_err(|e| {
+        tracing::error!(session_id = %session_id, "Failed to load conversation to share: {}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to load conversation: {}", e))
+    })?;
+    let range = message_range(history.messages.len(), request.start, request.end)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+
+    ShareStore::open_default()
+        .and_then(|store| {
+            let upload_dir = &config().paths.upload_dir;
+            store.create(normalize(&history, &ai_tool), encoded_name, range, expires_at, redactor(), upload_dir)
+        })
+        .map(|share| share.summary())
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+/// POST /api/projects/{encoded_name}/histories/{session_id}/shares
+/// Create a read-only link to a redacted copy of the conversation
+#[utoipa::path(
+    post,
+    path = "/projects/{encoded_name}/histories/{session_id}/shares",
+    tag = "shares",
+    params(
+        ("encoded_name" = String, Path, description = "Encoded project name"),
+        ("session_id" = String, Path, description = "Session id"),
+    ),
+    request_body = CreateShareRequest,
+    responses(
+        (status = 201, description = "Share created; the page is served at `url`", body = ShareSummary),
+        (status = 400, description = "Invalid message range, provider or lifetime", body = String, content_type = "text/plain"),
+        (status = 404, description = "Conversation not found", body = String, content_type = "text/plain"),
+        (status = 500, description = "Session file could not be parsed or the share written", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn create_share_handler(
+    Path(params): Path<GetConversationPath>,
+    Json(request): Json<CreateShareRequest>,
+) -> Result<(StatusCode, Json<ShareSummary>), (StatusCode, String)> {
+    let share = tokio::task::spawn_blocking(move || share_session(&params.encoded_name, &params.session_id, &request))
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))??;
+    Ok((StatusCode::CREATED, Json(share)))
+}
+
+/// GET /api/shares
+/// Active share links, most recent first (expired ones are deleted)
+#[utoipa::path(
+    get,
+    path = "/shares",
+    tag = "shares",
+    responses(
+        (status = 200, description = "Active shares", body = Vec<ShareSummary>),
+        (status = 500, description = "Share store could not be read", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn list_shares_handler() -> Result<Json<Vec<ShareSummary>>, (StatusCode, String)> {
+    ShareStore::open_default()
+        .and_then(|store| store.list(Utc::now()))
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))
+}
+
+/// DELETE /api/shares/{id}
+/// Revoke a share: the link stops working immediately
+#[utoipa::path(
+    delete,
+    path = "/shares/{id}",
+    tag = "shares",
+    params(("id" = String, Path, description = "Share id")),
+    responses(
+        (status = 204, description = "Share revoked"),
+        (status = 404, description = "Unknown share", body = String, content_type = "text/plain"),
+    )
+)]
+pub async fn revoke_share_handler(Path(id): Path<String>) -> Result<StatusCode, (StatusCode, String)> {
+    ShareStore::open_default()
+        .and_then(|store| store.revoke(&id))
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| (StatusCode::NOT_FOUND, e))
+}
+
+/// GET /share/{id}?format=&theme=
+/// Public page of a share: self-contained HTML by default, or Markdown / JSON
+/// Unknown, revoked and expired shares all answer 404.
+pub async fn shared_page_handler(Path(id): Path<String>, Query(query): Query<SharePageQuery>) -> Response {
+    let share = match ShareStore::open_default().and_then(|store| store.get(&id, Utc::now())) {
+        Ok(Some(share)) => share,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Lien de partage inconnu, expiré ou révoqué").into_response(),
+        Err(e) => {
+            tracing::error!("Failed to read share: {}", e);
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read share").into_response();
+        }
+    };
+
+    let format = query.format.unwrap_or(ExportFormat::Html);
+    let theme: Theme = query.theme.as_deref().unwrap_or("dark").parse().unwrap_or(Theme::Dark);
+    match render(&share.conversation, format, &theme, &share.images) {
+        Ok(body) => (
+            [
+                (header::CONTENT_TYPE, format.content_type()),
+                // The id is the secret: keep it out of caches, indexes and referrers
+                (header::CACHE_CONTROL, "no-store"),
+                (header::REFERRER_POLICY, "no-referrer"),
+                // Shared content is untrusted: no scripts, no requests, only inline styles and images
+                (header::CONTENT_SECURITY_POLICY, SHARE_CSP),
+                (header::HeaderName::from_static("x-robots-tag"), "noindex, nofollow"),
+            ],
+            body,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
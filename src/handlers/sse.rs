@@ -7,15 +7,17 @@ use axum::{
 };
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher, EventKind};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use std::convert::Infallible;
-use std::path::PathBuf;
 use std::time::Duration;
 use tokio_stream::wrappers::ReceiverStream;
 
+use crate::domain::models::AiTool;
+use crate::history::path_utils::find_session_file;
 use crate::history::{load_conversation, ContentBlock};
 
 /// SSE event data for new messages
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct SseMessage {
     pub event_type: String,
     pub session_id: String,
@@ -23,7 +25,7 @@ pub struct SseMessage {
     pub new_messages: Vec<MessageData>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct MessageData {
     pub role: String,
     pub content: String,
@@ -39,6 +41,21 @@ pub struct SseSubscribePath {
 
 /// SSE endpoint that watches a specific session for changes
 /// GET /api/sse/{encoded_name}/{session_id}
+#[utoipa::path(
+    get,
+    path = "/sse/{encoded_name}/{session_id}",
+    tag = "histories",
+    params(
+        ("encoded_name" = String, Path, description = "Encoded project name"),
+        ("session_id" = String, Path, description = "Session id"),
+    ),
+    responses((
+        status = 200,
+        description = "Server-sent events, one `SseMessage` JSON per event",
+        body = SseMessage,
+        content_type = "text/event-stream",
+    ))
+)]
 pub async fn sse_session_handler(
     Path(params): Path<SseSubscribePath>,
 ) -> impl IntoResponse {
@@ -72,23 +89,16 @@ async fn watch_session_file(
     encoded_name: String,
     session_id: String,
 ) {
-    // Build path to JSONL file
-    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-    let jsonl_path = PathBuf::from(&home)
-        .join(".claude")
-        .join("projects")
-        .join(&encoded_name)
-        .join(format!("{}.jsonl", session_id));
-
-    tracing::info!("Watching file: {:?}", jsonl_path);
-
-    if !jsonl_path.exists() {
-        tracing::error!("Session file not found: {:?}", jsonl_path);
+    // Locate the JSONL file by project name (any history root, no path decoding)
+    let Some((_, jsonl_path)) = find_session_file(&AiTool::ClaudeCode, &encoded_name, &session_id) else {
+        tracing::error!("Session file not found: {}/{}", encoded_name, session_id);
         let _ = tx.send(Ok(Event::default()
             .event("error")
             .data("Session file not found"))).await;
         return;
-    }
+    };
+
+    tracing::info!("Watching file: {:?}", jsonl_path);
 
     // Get initial message count
     let mut last_message_count = match load_conversation(&encoded_name, &session_id) {
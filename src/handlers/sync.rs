@@ -0,0 +1,17 @@
+//! Background sync status handler
+
+use axum::Json;
+
+use crate::infrastructure::database::{sync_status, SyncStatus};
+
+/// GET /api/sync/status
+/// Current state of the history → SurrealDB background sync
+#[utoipa::path(
+    get,
+    path = "/sync/status",
+    tag = "server",
+    responses((status = 200, description = "Background sync status", body = SyncStatus))
+)]
+pub async fn sync_status_handler() -> Json<SyncStatus> {
+    Json(sync_status())
+}
@@ -0,0 +1,363 @@
+//! Session forks: new sessions branched from a message of another session
+//!
+//! Claude forks natively when the parent is in the writable default root: the
+//! parent file is copied up to the chosen message into a temporary session,
+//! which `claude --resume <temp> --fork-session` continues under a new ID.
+//! Other providers, and parents in other roots, start from a transcript of the
+//! parent cut at that message (see `history::handoff`).
+//!
+//! The CLIs do not record where a session came from, so every fork is kept in
+//! `<data_dir>/forks.json` and the session list shows them under their parent.
+//! The registry also lists the temporary copies until they are deleted: sync
+//! skips them, and copies left by a crash are removed at startup.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use super::parser::jsonl_entry_message;
+use super::path_guard::ensure_writable;
+use super::path_utils::get_history_roots;
+use super::types::ClaudeJsonlEntry;
+use crate::domain::models::AiTool;
+
+static REGISTRY: Lazy<RwLock<ForkRegistry>> = Lazy::new(|| {
+    let registry = ForkRegistry::load(&registry_file()).unwrap_or_else(|e| {
+        tracing::warn!("Failed to load fork registry: {}", e);
+        ForkRegistry::default()
+    });
+    RwLock::new(registry)
+});
+
+/// How a fork was seeded with its parent's history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkMethod {
+    /// `--resume --fork-session` on a truncated copy (Claude)
+    Native,
+    /// Condensed transcript as the first message
+    Transcript,
+}
+
+/// A session started from a message of another session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionFork {
+    pub session_id: String,
+    pub parent_session_id: String,
+    /// Index of the last parent message the fork starts from (0-based)
+    pub parent_message: usize,
+    /// Provider slug of the fork ("claude", "opencode", "gemini")
+    pub provider: String,
+    pub method: ForkMethod,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Known forks, by session ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ForkRegistry {
+    #[serde(default)]
+    forks: BTreeMap<String, SessionFork>,
+    /// Truncated copies of native forks not deleted yet
+    #[serde(default)]
+    copies: BTreeSet<PathBuf>,
+}
+
+impl ForkRegistry {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize fork registry: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    pub fn insert(&mut self, fork: SessionFork) {
+        self.forks.insert(fork.session_id.clone(), fork);
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<&SessionFork> {
+        self.forks.get(session_id)
+    }
+
+    pub fn add_copy(&mut self, path: &Path) {
+        self.copies.insert(path.to_path_buf());
+    }
+
+    pub fn remove_copy(&mut self, path: &Path) {
+        self.copies.remove(path);
+    }
+
+    pub fn is_copy(&self, path: &Path) -> bool {
+        self.copies.contains(path)
+    }
+
+    pub fn len(&self) -> usize {
+        self.forks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.forks.is_empty()
+    }
+}
+
+/// Location of the registry file
+pub fn registry_file() -> PathBuf {
+    crate::config::config().paths.data_dir.join("forks.json")
+}
+
+/// Remember a fork and persist the registry
+pub fn record_fork(fork: SessionFork) -> Result<(), String> {
+    let mut registry = REGISTRY
+        .write()
+        .map_err(|_| "Fork registry lock poisoned".to_string())?;
+    registry.insert(fork);
+    registry.save(&registry_file())
+}
+
+/// Where a session was forked from, if it is a fork
+pub fn fork_of(session_id: &str) -> Option<SessionFork> {
+    REGISTRY.read().ok()?.get(session_id).cloned()
+}
+
+/// Remember a truncated copy before it is written
+pub fn track_copy(path: &Path) -> Result<(), String> {
+    let mut registry = REGISTRY
+        .write()
+        .map_err(|_| "Fork registry lock poisoned".to_string())?;
+    registry.add_copy(path);
+    registry.save(&registry_file())
+}
+
+/// Delete a truncated copy once the CLI has forked it
+pub fn discard_copy(path: &Path) {
+    if let Err(e) = fs::remove_file(path) {
+        tracing::warn!("Failed to remove fork copy {}: {}", path.display(), e);
+    }
+    let Ok(mut registry) = REGISTRY.write() else {
+        return;
+    };
+    registry.remove_copy(path);
+    if let Err(e) = registry.save(&registry_file()) {
+        tracing::warn!("Failed to save fork registry: {}", e);
+    }
+}
+
+/// Whether a session file is a truncated copy (not a session of its own)
+pub fn is_fork_copy(path: &Path) -> bool {
+    REGISTRY.read().is_ok_and(|registry| registry.is_copy(path))
+}
+
+/// Delete the copies left by a server that stopped before the CLI exited
+pub fn remove_stale_copies() {
+    let Ok(mut registry) = REGISTRY.write() else {
+        return;
+    };
+    if registry.copies.is_empty() {
+        return;
+    }
+    for path in std::mem::take(&mut registry.copies) {
+        tracing::info!("Removing stale fork copy {}", path.display());
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                tracing::warn!("Failed to remove fork copy {}: {}", path.display(), e);
+            }
+            _ => {}
+        }
+    }
+    if let Err(e) = registry.save(&registry_file()) {
+        tracing::warn!("Failed to save fork registry: {}", e);
+    }
+}
+
+/// Whether a Claude session can be forked natively
+/// The truncated copy is written next to the parent, where the CLI finds it:
+/// only the writable default root qualifies, not additional or imported roots.
+pub fn can_fork_natively(file_path: &Path) -> bool {
+    get_history_roots(&AiTool::ClaudeCode)
+        .into_iter()
+        .next()
+        .filter(|root| ensure_writable(root).is_ok())
+        .is_some_and(|root| file_path.starts_with(root.projects_dir()))
+}
+
+/// Claude session to resume with `--fork-session`
+#[derive(Debug)]
+pub struct ClaudeForkSource {
+    /// Session ID passed to `--resume`
+    pub resume_id: String,
+    /// Truncated copy to delete once the CLI has forked it
+    pub temp_file: Option<PathBuf>,
+}
+
+/// Prepare a native fork of the Claude session in `file_path` after message `fork_at`
+/// Forking after the last message resumes the session itself; otherwise a
+/// truncated copy is written next to it under a temporary ID, once `track`
+/// (see [`track_copy`]) has recorded it.
+pub fn prepare_claude_fork(
+    file_path: &Path,
+    fork_at: usize,
+    track: impl FnOnce(&Path) -> Result<(), String>,
+) -> Result<ClaudeForkSource, String> {
+    let content = fs::read_to_string(file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    let total = count_claude_messages(&content);
+    if fork_at >= total {
+        return Err(format!("Message {} out of range ({} messages)", fork_at, total));
+    }
+
+    let session_id = file_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| format!("Invalid session file: {}", file_path.display()))?;
+    if fork_at + 1 == total {
+        return Ok(ClaudeForkSource { resume_id: session_id.to_string(), temp_file: None });
+    }
+
+    let temp_id = uuid::Uuid::new_v4().to_string();
+    let temp_file = file_path.with_file_name(format!("{}.jsonl", temp_id));
+    track(&temp_file)?;
+    fs::write(&temp_file, truncate_claude_session(&content, fork_at + 1, &temp_id))
+        .map_err(|e| format!("Failed to write {}: {}", temp_file.display(), e))?;
+    Ok(ClaudeForkSource { resume_id: temp_id, temp_file: Some(temp_file) })
+}
+
+/// Lines of a Claude session up to its `keep`-th message, moved to `session_id`
+/// Messages are counted like the viewer counts them, so `keep` matches what the
+/// user sees; entries between messages (summaries, snapshots) are kept.
+pub fn truncate_claude_session(content: &str, keep: usize, session_id: &str) -> String {
+    let mut kept = 0;
+    let mut output = String::new();
+
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        if is_message_line(line) {
+            if kept == keep {
+                break;
+            }
+            kept += 1;
+        }
+
+        let line = match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(mut json) if json.get("sessionId").is_some() => {
+                json["sessionId"] = serde_json::Value::String(session_id.to_string());
+                json.to_string()
+            }
+            _ => line.to_string(),
+        };
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
+
+fn count_claude_messages(content: &str) -> usize {
+    content.lines().filter(|line| is_message_line(line)).count()
+}
+
+fn is_message_line(line: &str) -> bool {
+    serde_json::from_str::<ClaudeJsonlEntry>(line)
+        .ok()
+        .and_then(|entry| jsonl_entry_message(&entry))
+        .is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION: &str = concat!(
+        "{\"type\":\"summary\",\"summary\":\"Parser work\"}\n",
+        "{\"type\":\"user\",\"sessionId\":\"parent\",\"uuid\":\"u1\",\"message\":{\"role\":\"user\",\"content\":\"Fix the parser\"}}\n",
+        "{\"sessionId\":\"parent\",\"uuid\":\"a1\",\"message\":{\"id\":\"m1\",\"role\":\"assistant\",\"content\":[{\"type\":\"text\",\"text\":\"Done\"}]}}\n",
+        "{\"type\":\"system\",\"sessionId\":\"parent\",\"uuid\":\"s1\"}\n",
+        "\n",
+        "{\"type\":\"user\",\"sessionId\":\"parent\",\"uuid\":\"u2\",\"message\":{\"role\":\"user\",\"content\":\"Now add tests\"}}\n",
+    );
+
+    #[test]
+    fn test_truncate_claude_session() {
+        assert_eq!(count_claude_messages(SESSION), 3);
+
+        let truncated = truncate_claude_session(SESSION, 2, "fork");
+        let lines: Vec<&str> = truncated.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].contains("Parser work"));
+        assert!(lines[2].contains("\"Done\""));
+        assert!(!truncated.contains("Now add tests"));
+        assert!(!truncated.contains("\"parent\""));
+        assert_eq!(count_claude_messages(&truncated), 2);
+
+        assert_eq!(truncate_claude_session(SESSION, 3, "fork").lines().count(), 5);
+    }
+
+    #[test]
+    fn test_prepare_claude_fork() {
+        let dir = std::env::temp_dir().join(format!("forks-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("parent.jsonl");
+        fs::write(&file, SESSION).unwrap();
+
+        let mut registry = ForkRegistry::default();
+        let mut track = |path: &Path| -> Result<(), String> {
+            registry.add_copy(path);
+            Ok(())
+        };
+
+        let last = prepare_claude_fork(&file, 2, &mut track).unwrap();
+        assert_eq!(last.resume_id, "parent");
+        assert!(last.temp_file.is_none());
+        assert!(prepare_claude_fork(&file, 3, &mut track).is_err());
+
+        let middle = prepare_claude_fork(&file, 0, &mut track).unwrap();
+        let temp_file = middle.temp_file.unwrap();
+        assert_eq!(temp_file, dir.join(format!("{}.jsonl", middle.resume_id)));
+        assert!(registry.is_copy(&temp_file));
+        assert!(!registry.is_copy(&file));
+        assert!(prepare_claude_fork(&file, 0, |_| Err("no registry".to_string())).is_err());
+        assert_eq!(count_claude_messages(&fs::read_to_string(&temp_file).unwrap()), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("fork-registry-{}", uuid::Uuid::new_v4()));
+        let file = dir.join("forks.json");
+
+        let fork = SessionFork {
+            session_id: "child".to_string(),
+            parent_session_id: "parent".to_string(),
+            parent_message: 4,
+            provider: "claude".to_string(),
+            method: ForkMethod::Native,
+            created_at: Utc::now(),
+        };
+        let mut registry = ForkRegistry::default();
+        registry.insert(fork.clone());
+        registry.add_copy(&dir.join("copy.jsonl"));
+        registry.save(&file).unwrap();
+
+        let loaded = ForkRegistry::load(&file).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get("child"), Some(&fork));
+        assert!(loaded.get("parent").is_none());
+        assert!(loaded.is_copy(&dir.join("copy.jsonl")));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
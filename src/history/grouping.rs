@@ -10,7 +10,7 @@ use super::parser::ConversationFile;
 use crate::shared::logging;
 
 /// Summary of a conversation for listing purposes (lightweight)
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 pub struct ConversationSummary {
     pub session_id: String,
     pub start_time: String,
@@ -90,6 +90,25 @@ pub fn group_conversations(conversation_files: Vec<ConversationFile>) -> Vec<Con
     summaries
 }
 
+/// Group conversations, keeping forks out of the deduplication
+/// A fork repeats its parent's messages up to the fork point: forked after the
+/// last message, it would otherwise hide its parent as an earlier version.
+pub fn group_conversations_with_forks(
+    conversation_files: Vec<ConversationFile>,
+    is_fork: impl Fn(&str) -> bool,
+) -> Vec<ConversationSummary> {
+    let (forks, others): (Vec<_>, Vec<_>) = conversation_files
+        .into_iter()
+        .partition(|conv| is_fork(&conv.session_id));
+
+    let mut summaries = group_conversations(others);
+    if !forks.is_empty() {
+        summaries.extend(forks.iter().map(create_conversation_summary));
+        summaries.sort_by(|a, b| b.last_time.cmp(&a.last_time));
+    }
+    summaries
+}
+
 /// Create a ConversationSummary from a ConversationFile
 fn create_conversation_summary(conversation_file: &ConversationFile) -> ConversationSummary {
     ConversationSummary {
@@ -211,6 +230,21 @@ mod tests {
         assert_eq!(grouped.len(), 2);
     }
 
+    #[test]
+    fn test_forks_do_not_hide_their_parent() {
+        let conversations = vec![
+            make_conv_file("parent", vec!["msg-a", "msg-b"]),
+            make_conv_file("fork", vec!["msg-a", "msg-b", "msg-c"]), // Forked after the last message
+        ];
+
+        assert_eq!(group_conversations(conversations.clone()).len(), 1);
+
+        let grouped = group_conversations_with_forks(conversations, |id| id == "fork");
+        let mut ids: Vec<&str> = grouped.iter().map(|s| s.session_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["fork", "parent"]);
+    }
+
     #[test]
     fn test_analyze_relationships() {
         let conversations = vec![
@@ -0,0 +1,277 @@
+//! Transcript handoff: continue a session with another provider
+//!
+//! A CLI cannot resume another provider's session, so the conversation is
+//! condensed into a plain-text transcript that opens a new chat: prompts and
+//! answers verbatim, file edits and commands summarized in one line, long tool
+//! output trimmed, and the oldest turns dropped to fit a size budget.
+//!
+//! The budget is in bytes: the CLIs get the prompt as one argument, and Linux
+//! caps each argument at 128 KiB (`MAX_ARG_STRLEN`).
+
+use serde_json::Value;
+
+use super::export::{normalize, ExportedBlock, ExportedConversation, ExportedMessage};
+use super::{find_conversation_file, load_conversation_file};
+
+/// Largest handoff prompt (transcript and the user's message), in bytes of UTF-8
+pub const MAX_PROMPT_BYTES: usize = 100_000;
+
+/// Characters kept from one tool output
+const TOOL_OUTPUT_CHARS: usize = 800;
+
+/// Characters kept from the input of an unknown tool
+const TOOL_INPUT_CHARS: usize = 200;
+
+/// Room left for the "messages omitted" note
+const OMITTED_NOTE_BYTES: usize = 64;
+
+/// Transcript budget that keeps the handoff prompt of `message` within [`MAX_PROMPT_BYTES`]
+pub fn transcript_budget(message: &str) -> usize {
+    MAX_PROMPT_BYTES.saturating_sub(handoff_prompt("", message).len())
+}
+
+/// Load a session and condense it (see [`build_transcript`])
+/// With `until`, only the messages before that index are used (forks).
+pub fn load_transcript(
+    encoded_name: &str,
+    session_id: &str,
+    until: Option<usize>,
+    budget_bytes: usize,
+) -> Result<String, String> {
+    let (ai_tool, file_path) = find_conversation_file(encoded_name, session_id)
+        .ok_or_else(|| format!("Conversation not found: {}", session_id))?;
+    let mut history = load_conversation_file(&ai_tool, &file_path, session_id)?;
+    if let Some(until) = until {
+        if until > history.messages.len() {
+            return Err(format!("Message {} out of range ({} messages)", until, history.messages.len()));
+        }
+        history.messages.truncate(until);
+    }
+    Ok(build_transcript(&normalize(&history, &ai_tool), budget_bytes))
+}
+
+/// Condensed transcript of a conversation, at most `budget_bytes` long
+/// The first prompt (usually the task) is kept when it fits in a quarter of the
+/// budget; the most recent turns fill the rest.
+pub fn build_transcript(conversation: &ExportedConversation, budget_bytes: usize) -> String {
+    let header = format!(
+        "Transcript of a previous {} session (id {}) in {}.\n\
+         Continue the work from where it stopped. The edits listed below are already applied to the files.\n",
+        provider_name(&conversation.provider),
+        conversation.session_id,
+        conversation.project_path,
+    );
+
+    let turns: Vec<String> = conversation.messages.iter().filter_map(render_message).collect();
+    // Each turn is preceded by a newline
+    let cost = |turn: &str| turn.len() + 1;
+    let mut remaining = budget_bytes.saturating_sub(header.len() + OMITTED_NOTE_BYTES);
+
+    let first = turns
+        .first()
+        .filter(|turn| turn.starts_with("## User") && cost(turn.as_str()) <= remaining / 4);
+    if let Some(first) = first {
+        remaining -= cost(first.as_str());
+    }
+    let skip = usize::from(first.is_some());
+
+    let mut recent = Vec::new();
+    for turn in turns[skip..].iter().rev() {
+        let bytes = cost(turn.as_str());
+        if bytes > remaining {
+            break;
+        }
+        remaining -= bytes;
+        recent.push(turn.as_str());
+    }
+    recent.reverse();
+
+    let mut transcript = header;
+    if let Some(first) = first {
+        transcript.push('\n');
+        transcript.push_str(first);
+    }
+    let omitted = turns.len() - skip - recent.len();
+    if omitted > 0 {
+        transcript.push_str(&format!("\n[{} earlier messages omitted]\n", omitted));
+    }
+    for turn in recent {
+        transcript.push('\n');
+        transcript.push_str(turn);
+    }
+    transcript
+}
+
+/// Opening message of the new chat: the transcript, then the user's message
+pub fn handoff_prompt(transcript: &str, message: &str) -> String {
+    format!("{}\n---\n\n{}", transcript.trim_end(), opening_message(message))
+}
+
+/// The user's message, or a default when they sent none
+pub fn opening_message(message: &str) -> &str {
+    match message.trim() {
+        "" => "Continue from where the previous session stopped.",
+        message => message,
+    }
+}
+
+fn provider_name(slug: &str) -> &str {
+    match slug {
+        "claude" => "Claude Code",
+        "opencode" => "OpenCode",
+        "gemini" => "Gemini CLI",
+        other => other,
+    }
+}
+
+/// One turn of the transcript (None when nothing is left to show)
+fn render_message(message: &ExportedMessage) -> Option<String> {
+    let lines: Vec<String> = message.blocks.iter().map(render_block).collect();
+    if lines.is_empty() {
+        return None;
+    }
+
+    let only_tool_output = message.blocks.iter().all(|b| matches!(b, ExportedBlock::ToolResult { .. }));
+    let title = match message.role.as_str() {
+        _ if only_tool_output => "Tool output",
+        "user" => "User",
+        "assistant" => "Assistant",
+        "system" => "System",
+        other => other,
+    };
+    Some(format!("## {}\n{}\n", title, lines.join("\n")))
+}
+
+fn render_block(block: &ExportedBlock) -> String {
+    match block {
+        ExportedBlock::Text { text } => text.trim().to_string(),
+        ExportedBlock::ToolCall { name, input } => summarize_tool_call(name, input),
+        ExportedBlock::ToolResult { content } => trim_chars(content.trim(), TOOL_OUTPUT_CHARS),
+        ExportedBlock::Image { path } => format!("[image: {}]", path),
+    }
+}
+
+/// One line per tool call: edits and commands by their target, others by their input
+fn summarize_tool_call(name: &str, input: &Value) -> String {
+    let field = |keys: &[&str]| keys.iter().find_map(|key| input.get(*key).and_then(Value::as_str));
+    let path = field(&["file_path", "filePath", "absolute_path", "path", "notebook_path"]);
+
+    match (name.to_lowercase().as_str(), path) {
+        ("edit" | "multiedit" | "replace" | "str_replace" | "notebookedit" | "patch", Some(path)) => {
+            format!("[edited {}]", path)
+        }
+        ("write" | "write_file" | "create", Some(path)) => format!("[wrote {}]", path),
+        ("read" | "read_file" | "view", Some(path)) => format!("[read {}]", path),
+        ("bash" | "shell" | "run_shell_command", _) => match field(&["command", "cmd"]) {
+            Some(command) => format!("[ran `{}`]", trim_chars(command.trim(), TOOL_INPUT_CHARS)),
+            None => format!("[tool {}]", name),
+        },
+        _ => format!("[tool {}: {}]", name, trim_chars(&input.to_string(), TOOL_INPUT_CHARS)),
+    }
+}
+
+/// First `max` characters, with a note of how much was cut
+fn trim_chars(text: &str, max: usize) -> String {
+    let total = text.chars().count();
+    if total <= max {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max).collect();
+    format!("{}… [{} characters trimmed]", kept, total - max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    use crate::history::export::EXPORT_FORMAT_VERSION;
+
+    fn message(role: &str, blocks: Vec<ExportedBlock>) -> ExportedMessage {
+        ExportedMessage { role: role.to_string(), timestamp: None, blocks }
+    }
+
+    fn text(text: &str) -> ExportedBlock {
+        ExportedBlock::Text { text: text.to_string() }
+    }
+
+    fn conversation(messages: Vec<ExportedMessage>) -> ExportedConversation {
+        ExportedConversation {
+            format_version: EXPORT_FORMAT_VERSION,
+            session_id: "s1".to_string(),
+            provider: "claude".to_string(),
+            project_name: "demo".to_string(),
+            project_path: "/tmp/demo".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            messages,
+        }
+    }
+
+    #[test]
+    fn test_summarizes_edits_and_trims_output() {
+        let transcript = build_transcript(
+            &conversation(vec![
+                message("user", vec![text("Fix the parser")]),
+                message(
+                    "assistant",
+                    vec![
+                        text("Done."),
+                        ExportedBlock::ToolCall {
+                            name: "Edit".to_string(),
+                            input: json!({"file_path": "src/parser.rs", "old_string": "a", "new_string": "b"}),
+                        },
+                        ExportedBlock::ToolCall {
+                            name: "Bash".to_string(),
+                            input: json!({"command": "cargo test"}),
+                        },
+                    ],
+                ),
+                message("user", vec![ExportedBlock::ToolResult { content: "x".repeat(2000) }]),
+            ]),
+            MAX_PROMPT_BYTES,
+        );
+
+        assert!(transcript.starts_with("Transcript of a previous Claude Code session (id s1) in /tmp/demo."));
+        assert!(transcript.contains("## User\nFix the parser\n"));
+        assert!(transcript.contains("[edited src/parser.rs]\n[ran `cargo test`]"));
+        assert!(!transcript.contains("old_string"));
+        assert!(transcript.contains("## Tool output\n"));
+        assert!(transcript.contains("… [1200 characters trimmed]"));
+    }
+
+    #[test]
+    fn test_budget_keeps_first_prompt_and_latest_turns() {
+        let mut messages = vec![message("user", vec![text("The task")])];
+        for i in 0..50 {
+            messages.push(message("assistant", vec![text(&format!("step {} {}", i, "y".repeat(400)))]));
+        }
+
+        let transcript = build_transcript(&conversation(messages), 1_000);
+        assert!(transcript.len() <= 1_000);
+        assert!(transcript.contains("The task"));
+        assert!(transcript.contains("step 49"));
+        assert!(!transcript.contains("step 0 "));
+        assert!(transcript.contains("earlier messages omitted]"));
+    }
+
+    #[test]
+    fn test_prompt_fits_argument_limit() {
+        // 3 bytes per character: a character budget would overflow
+        let messages = (0..200).map(|i| message("assistant", vec![text(&format!("{} {}", i, "日本語".repeat(300)))])).collect();
+        let request = "続けてください".repeat(100);
+
+        let transcript = build_transcript(&conversation(messages), transcript_budget(&request));
+        let prompt = handoff_prompt(&transcript, &request);
+        assert!(prompt.len() <= MAX_PROMPT_BYTES);
+        assert!(prompt.len() > MAX_PROMPT_BYTES - 5_000);
+        assert!(transcript.contains("199 "));
+    }
+
+    #[test]
+    fn test_handoff_prompt() {
+        assert_eq!(handoff_prompt("T\n", ""), "T\n---\n\nContinue from where the previous session stopped.");
+        assert_eq!(handoff_prompt("T", "Now add tests"), "T\n---\n\nNow add tests");
+    }
+}
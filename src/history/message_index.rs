@@ -0,0 +1,379 @@
+//! Per-session message index
+//!
+//! Huge sessions are served page by page instead of in one payload. For Claude
+//! JSONL files the index records the byte range of every line that yields a
+//! message, so a page only reads and parses its own lines:
+//! - entries are keyed by file path and checked against modification time + size
+//! - at most `MAX_INDEXED_FILES` files are indexed, the least recently used go first
+//! - a file that only grew (the CLI appends) is indexed from the previous end
+//! - a trailing line still being written is left out until it parses
+//!
+//! OpenCode and Gemini sessions are single JSON documents (or message
+//! directories): they are parsed whole and sliced.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use once_cell::sync::Lazy;
+
+use crate::domain::models::{AiTool, PageCursor};
+use super::parser::{jsonl_entry_message, parse_json_session_file};
+use super::path_utils::find_session_file;
+use super::types::{ClaudeJsonlEntry, Message};
+
+/// Page size used when the caller does not ask for one
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Upper bound of a page, whatever the caller asks
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Session files whose index is kept in memory
+const MAX_INDEXED_FILES: usize = 256;
+
+static MESSAGE_INDEXES: Lazy<Mutex<IndexCache>> = Lazy::new(|| Mutex::new(IndexCache::default()));
+
+/// Indexes by file path, least recently used evicted first
+#[derive(Default)]
+struct IndexCache {
+    entries: HashMap<String, (Arc<SessionIndex>, u64)>,
+    /// Use tick → key, oldest first
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+impl IndexCache {
+    fn get(&mut self, key: &str) -> Option<Arc<SessionIndex>> {
+        let (index, last_used) = self.entries.get_mut(key)?;
+        self.tick += 1;
+        self.recency.remove(&*last_used);
+        *last_used = self.tick;
+        self.recency.insert(self.tick, key.to_string());
+        Some(Arc::clone(index))
+    }
+
+    fn insert(&mut self, key: String, index: Arc<SessionIndex>, capacity: usize) {
+        self.tick += 1;
+        if let Some((_, last_used)) = self.entries.insert(key.clone(), (index, self.tick)) {
+            self.recency.remove(&last_used);
+        }
+        self.recency.insert(self.tick, key);
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+}
+
+/// Byte ranges of the message lines of one Claude session file
+#[derive(Debug, Clone)]
+struct SessionIndex {
+    modified: Option<SystemTime>,
+    size: u64,
+    /// End of the last indexed line (next append starts here)
+    indexed_len: u64,
+    session_id: Option<String>,
+    lines: Vec<(u64, usize)>,
+}
+
+/// A window of a session's messages: `messages[0]` is message number `start` of `total`
+#[derive(Debug, Clone)]
+pub struct MessagePage {
+    pub session_id: String,
+    pub messages: Vec<Message>,
+    pub start: usize,
+    pub total: usize,
+}
+
+/// Message range selected by a cursor, clamped to the session
+pub fn page_range(cursor: PageCursor, limit: usize, total: usize) -> Range<usize> {
+    let limit = limit.clamp(1, MAX_PAGE_SIZE);
+    match cursor {
+        PageCursor::Latest => total.saturating_sub(limit)..total,
+        PageCursor::Before(index) => {
+            let end = index.min(total);
+            end.saturating_sub(limit)..end
+        }
+        PageCursor::After(index) => {
+            let start = index.min(total);
+            start..(start + limit).min(total)
+        }
+        PageCursor::Around(index) => {
+            let start = index
+                .saturating_sub(limit / 2)
+                .min(total.saturating_sub(limit));
+            start..(start + limit).min(total)
+        }
+    }
+}
+
+/// Load one page of a session, searching every provider like `load_conversation`
+pub fn load_conversation_page(
+    encoded_name: &str,
+    session_id: &str,
+    cursor: PageCursor,
+    limit: usize,
+) -> Result<MessagePage, String> {
+    let ai_tools = [AiTool::ClaudeCode, AiTool::OpenCode, AiTool::Gemini];
+
+    for ai_tool in &ai_tools {
+        let Some((_, file_path)) = find_session_file(ai_tool, encoded_name, session_id) else {
+            continue;
+        };
+
+        return match ai_tool {
+            AiTool::ClaudeCode => jsonl_page(&file_path, cursor, limit),
+            AiTool::OpenCode | AiTool::Gemini => {
+                let history = parse_json_session_file(&file_path, session_id, ai_tool)?;
+                let total = history.messages.len();
+                let range = page_range(cursor, limit, total);
+                Ok(MessagePage {
+                    session_id: history.session_id,
+                    start: range.start,
+                    messages: history.messages[range].to_vec(),
+                    total,
+                })
+            }
+        };
+    }
+
+    Err(format!("Session not found: {}", session_id))
+}
+
+/// Read one page of a Claude JSONL file through its index
+pub fn jsonl_page(file_path: &Path, cursor: PageCursor, limit: usize) -> Result<MessagePage, String> {
+    let index = session_index(file_path)?;
+    let total = index.lines.len();
+    let range = page_range(cursor, limit, total);
+
+    let mut file = File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut messages = Vec::with_capacity(range.len());
+    let mut buf = Vec::new();
+    for &(offset, len) in &index.lines[range.clone()] {
+        buf.resize(len, 0);
+        file.seek(SeekFrom::Start(offset))
+            .and_then(|_| file.read_exact(&mut buf))
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let message = serde_json::from_slice::<ClaudeJsonlEntry>(&buf)
+            .ok()
+            .and_then(|entry| jsonl_entry_message(&entry))
+            .ok_or_else(|| format!("Session file changed while reading: {}", file_path.display()))?;
+        messages.push(message);
+    }
+
+    let session_id = index.session_id.clone().unwrap_or_else(|| {
+        file_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("unknown")
+            .to_string()
+    });
+
+    Ok(MessagePage {
+        session_id,
+        messages,
+        start: range.start,
+        total,
+    })
+}
+
+/// Number of messages of a Claude JSONL file (indexes it if needed)
+pub fn jsonl_message_count(file_path: &Path) -> Result<usize, String> {
+    Ok(session_index(file_path)?.lines.len())
+}
+
+/// Up-to-date index of a session file
+fn session_index(file_path: &Path) -> Result<Arc<SessionIndex>, String> {
+    let metadata = fs::metadata(file_path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let modified = metadata.modified().ok();
+    let size = metadata.len();
+    let key = file_path.to_string_lossy().to_string();
+
+    let previous = MESSAGE_INDEXES.lock().unwrap().get(&key);
+    let index = match previous {
+        Some(index) if index.modified == modified && index.size == size => return Ok(index),
+        // Appended since last time: only the new lines are read
+        Some(index) if size > index.indexed_len && ends_at_line_boundary(file_path, index.indexed_len) => {
+            extend_index(file_path, (*index).clone(), modified, size)?
+        }
+        _ => extend_index(file_path, SessionIndex::empty(), modified, size)?,
+    };
+
+    tracing::debug!("Indexed {} messages of {}", index.lines.len(), file_path.display());
+    let index = Arc::new(index);
+    MESSAGE_INDEXES.lock().unwrap().insert(key, Arc::clone(&index), MAX_INDEXED_FILES);
+    Ok(index)
+}
+
+impl SessionIndex {
+    fn empty() -> Self {
+        Self {
+            modified: None,
+            size: 0,
+            indexed_len: 0,
+            session_id: None,
+            lines: Vec::new(),
+        }
+    }
+}
+
+/// True if the byte before `offset` is a newline (the indexed prefix is made of whole lines)
+fn ends_at_line_boundary(file_path: &Path, offset: u64) -> bool {
+    if offset == 0 {
+        return true;
+    }
+    let mut byte = [0u8; 1];
+    File::open(file_path)
+        .and_then(|mut file| {
+            file.seek(SeekFrom::Start(offset - 1))?;
+            file.read_exact(&mut byte)
+        })
+        .map(|_| byte[0] == b'\n')
+        .unwrap_or(false)
+}
+
+/// Index the lines found after `index.indexed_len`
+fn extend_index(
+    file_path: &Path,
+    mut index: SessionIndex,
+    modified: Option<SystemTime>,
+    size: u64,
+) -> Result<SessionIndex, String> {
+    let mut file = File::open(file_path).map_err(|e| format!("Failed to open file: {}", e))?;
+    file.seek(SeekFrom::Start(index.indexed_len))
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut reader = BufReader::new(file);
+
+    let mut offset = index.indexed_len;
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader
+            .read_until(b'\n', &mut line)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        let complete = line.ends_with(b"\n");
+        let entry = serde_json::from_slice::<ClaudeJsonlEntry>(&line).ok();
+
+        // A last line without newline that does not parse is still being written
+        if !complete && entry.is_none() {
+            break;
+        }
+        if let Some(entry) = entry {
+            if index.session_id.is_none() {
+                index.session_id = entry.session_id.clone();
+            }
+            if jsonl_entry_message(&entry).is_some() {
+                index.lines.push((offset, line.len()));
+            }
+        }
+        offset += read as u64;
+    }
+
+    index.indexed_len = offset;
+    index.modified = modified;
+    index.size = size;
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claude_line(uuid: &str, role: &str, text: &str) -> String {
+        format!(
+            "{{\"type\":\"{}\",\"uuid\":\"{}\",\"sessionId\":\"s1\",\"timestamp\":\"2025-01-01T10:00:00Z\",\"message\":{{\"role\":\"{}\",\"content\":\"{}\"}}}}\n",
+            role, uuid, role, text
+        )
+    }
+
+    fn text_of(message: &Message) -> String {
+        match &message.content[0] {
+            super::super::types::ContentBlock::Text { text } => text.clone(),
+            _ => String::new(),
+        }
+    }
+
+    #[test]
+    fn test_page_range() {
+        assert_eq!(page_range(PageCursor::Latest, 10, 25), 15..25);
+        assert_eq!(page_range(PageCursor::Latest, 10, 5), 0..5);
+        assert_eq!(page_range(PageCursor::Before(15), 10, 25), 5..15);
+        assert_eq!(page_range(PageCursor::Before(3), 10, 25), 0..3);
+        assert_eq!(page_range(PageCursor::After(20), 10, 25), 20..25);
+        assert_eq!(page_range(PageCursor::After(40), 10, 25), 25..25);
+        assert_eq!(page_range(PageCursor::Around(12), 10, 25), 7..17);
+        assert_eq!(page_range(PageCursor::Around(2), 10, 25), 0..10);
+        assert_eq!(page_range(PageCursor::Around(24), 10, 25), 15..25);
+        assert_eq!(page_range(PageCursor::Latest, 0, 25), 24..25);
+    }
+
+    #[test]
+    fn test_index_cache_evicts_least_recently_used() {
+        let mut cache = IndexCache::default();
+        let index = Arc::new(SessionIndex::empty());
+        cache.insert("a".to_string(), Arc::clone(&index), 2);
+        cache.insert("b".to_string(), Arc::clone(&index), 2);
+        assert!(cache.get("a").is_some());
+
+        cache.insert("c".to_string(), Arc::clone(&index), 2);
+        assert!(cache.get("b").is_none());
+        assert!(cache.get("a").is_some() && cache.get("c").is_some());
+
+        // Re-indexing a file replaces its entry
+        cache.insert("a".to_string(), index, 2);
+        assert_eq!((cache.entries.len(), cache.recency.len()), (2, 2));
+    }
+
+    #[test]
+    fn test_jsonl_pages_follow_appends() {
+        let dir = std::env::temp_dir().join(format!("message-index-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("s1.jsonl");
+
+        let mut content = String::new();
+        for i in 0..10 {
+            content.push_str(&claude_line(&format!("u{}", i), "user", &format!("message {}", i)));
+            // Non-message entries are not indexed
+            content.push_str("{\"type\":\"summary\",\"summary\":\"x\"}\n");
+        }
+        fs::write(&file, &content).unwrap();
+
+        let page = jsonl_page(&file, PageCursor::Latest, 4).unwrap();
+        assert_eq!(page.session_id, "s1");
+        assert_eq!((page.start, page.total), (6, 10));
+        assert_eq!(text_of(&page.messages[0]), "message 6");
+
+        let older = jsonl_page(&file, PageCursor::Before(page.start), 4).unwrap();
+        assert_eq!(older.start, 2);
+        assert_eq!(text_of(&older.messages[3]), "message 5");
+
+        // A half-written line is ignored, then picked up once complete
+        let line = claude_line("u10", "assistant", "message 10");
+        content.push_str(&line[..20]);
+        fs::write(&file, &content).unwrap();
+        assert_eq!(jsonl_message_count(&file).unwrap(), 10);
+
+        content.push_str(&line[20..]);
+        fs::write(&file, &content).unwrap();
+        let latest = jsonl_page(&file, PageCursor::Latest, 1).unwrap();
+        assert_eq!(latest.total, 11);
+        assert_eq!(text_of(&latest.messages[0]), "message 10");
+
+        // A rewrite is indexed from scratch
+        fs::write(&file, claude_line("u0", "user", "rewritten")).unwrap();
+        let rewritten = jsonl_page(&file, PageCursor::Around(5), 10).unwrap();
+        assert_eq!(rewritten.total, 1);
+        assert_eq!(text_of(&rewritten.messages[0]), "rewritten");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
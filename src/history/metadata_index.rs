@@ -0,0 +1,341 @@
+//! Session metadata index
+//!
+//! Listing a project used to re-read every session file. The index keeps the
+//! `ConversationFile` summary of each session, keyed by file path and checked
+//! against a fingerprint (modification time + size):
+//! - unchanged files are served from the index
+//! - changed or new files are re-parsed, in parallel (rayon)
+//! - OpenCode messages live outside the session file, so the fingerprint also
+//!   includes the modification time of the session's message directory
+//!
+//! The index is stored in `<data_dir>/metadata_index.json`. Entries of deleted
+//! files are dropped when it is saved; a background thread saves it every
+//! 30 seconds when something changed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, UNIX_EPOCH};
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::AiTool;
+use super::parsers::claude::parse_history_file_metadata;
+use super::parsers::gemini::parse_gemini_session_metadata;
+use super::parsers::opencode::parse_opencode_session_metadata;
+use super::types::ConversationFile;
+
+/// Bumped when `ConversationFile` or the fingerprint changes: older files are discarded
+const INDEX_VERSION: u32 = 1;
+
+const SAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+static METADATA_INDEX: Lazy<MetadataIndex> = Lazy::new(|| {
+    let index = MetadataIndex::open(index_file());
+    std::thread::spawn(|| loop {
+        std::thread::sleep(SAVE_INTERVAL);
+        if let Err(e) = METADATA_INDEX.save() {
+            tracing::warn!("Failed to save metadata index: {}", e);
+        }
+    });
+    index
+});
+
+/// Identity of a file's content as seen by the filesystem
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub mtime_ms: u64,
+    pub size: u64,
+    /// OpenCode: modification time of the session's message directory
+    #[serde(default)]
+    pub extra_mtime_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    fingerprint: Fingerprint,
+    summary: ConversationFile,
+}
+
+#[derive(Serialize, Deserialize)]
+struct IndexFile {
+    version: u32,
+    entries: Vec<(String, IndexEntry)>,
+}
+
+/// Session summaries by file path
+pub struct MetadataIndex {
+    path: Option<PathBuf>,
+    entries: DashMap<String, IndexEntry>,
+    dirty: AtomicBool,
+}
+
+impl MetadataIndex {
+    /// Index that is never persisted (tests, benchmarks)
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            entries: DashMap::new(),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Load the index stored at `path`; a missing or outdated file gives an empty index
+    pub fn open(path: PathBuf) -> Self {
+        let entries = match load_entries(&path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Ignoring metadata index {}: {}", path.display(), e);
+                DashMap::new()
+            }
+        };
+        tracing::info!("Metadata index loaded: {} sessions", entries.len());
+        Self {
+            path: Some(path),
+            entries,
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Summary of one session file, re-parsed only if the file changed
+    pub fn summary(&self, ai_tool: &AiTool, file: &Path) -> Option<ConversationFile> {
+        let fingerprint = fingerprint(ai_tool, file)?;
+        let key = file.to_string_lossy().to_string();
+
+        if let Some(entry) = self.entries.get(&key) {
+            if entry.fingerprint == fingerprint {
+                return Some(entry.summary.clone());
+            }
+        }
+
+        let summary = parse_summary(ai_tool, file)?;
+        self.entries.insert(key, IndexEntry { fingerprint, summary: summary.clone() });
+        self.dirty.store(true, Ordering::Relaxed);
+        Some(summary)
+    }
+
+    /// Summaries of several session files, parsed in parallel
+    pub fn summaries(&self, ai_tool: &AiTool, files: &[PathBuf]) -> Vec<ConversationFile> {
+        files
+            .par_iter()
+            .filter_map(|file| self.summary(ai_tool, file))
+            .collect()
+    }
+
+    /// Summaries of every session of a project directory
+    pub fn project_summaries(&self, ai_tool: &AiTool, project_dir: &Path) -> Result<Vec<ConversationFile>, String> {
+        let files = session_files(ai_tool, project_dir)?;
+        Ok(self.summaries(ai_tool, &files))
+    }
+
+    /// Write the index to disk if it changed, dropping entries of deleted files
+    pub fn save(&self) -> Result<(), String> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if !self.dirty.swap(false, Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        self.entries.retain(|file, _| Path::new(file).exists());
+        let file = IndexFile {
+            version: INDEX_VERSION,
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| (entry.key().clone(), entry.value().clone()))
+                .collect(),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_vec(&file)
+            .map_err(|e| format!("Failed to serialize metadata index: {}", e))?;
+        // Write then rename, so a crash never leaves a truncated index
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+        fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+
+        tracing::debug!("Metadata index saved: {} sessions", file.entries.len());
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Shared index, loaded on first use
+pub fn metadata_index() -> &'static MetadataIndex {
+    &METADATA_INDEX
+}
+
+/// Location of the index file
+pub fn index_file() -> PathBuf {
+    crate::config::config().paths.data_dir.join("metadata_index.json")
+}
+
+fn load_entries(path: &Path) -> Result<DashMap<String, IndexEntry>, String> {
+    if !path.exists() {
+        return Ok(DashMap::new());
+    }
+    let content = fs::read(path).map_err(|e| format!("Failed to read: {}", e))?;
+    let file: IndexFile = serde_json::from_slice(&content).map_err(|e| format!("Invalid index: {}", e))?;
+    if file.version != INDEX_VERSION {
+        return Err(format!("Index version {} (expected {})", file.version, INDEX_VERSION));
+    }
+    Ok(file.entries.into_iter().collect())
+}
+
+/// Session files of a project directory, for each provider's layout
+pub fn session_files(ai_tool: &AiTool, project_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let dir = match ai_tool {
+        AiTool::Gemini => project_dir.join("chats"),
+        _ => project_dir.to_path_buf(),
+    };
+    let entries = match fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        // A Gemini project without chats/ simply has no sessions
+        Err(_) if matches!(ai_tool, AiTool::Gemini) => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read project directory: {}", e)),
+    };
+
+    Ok(entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_session_file(ai_tool, path))
+        .collect())
+}
+
+fn is_session_file(ai_tool: &AiTool, path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    match ai_tool {
+        AiTool::ClaudeCode => name.ends_with(".jsonl"),
+        AiTool::OpenCode => name.starts_with("ses_") && name.ends_with(".json"),
+        AiTool::Gemini => name.starts_with("session-") && name.ends_with(".json"),
+    }
+}
+
+/// storage/session/<project>/ses_x.json → storage/message
+fn opencode_message_base(session_file: &Path) -> Option<PathBuf> {
+    Some(session_file.parent()?.parent()?.parent()?.join("message"))
+}
+
+fn mtime_ms(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Current fingerprint of a session file (None if it cannot be read)
+pub fn fingerprint(ai_tool: &AiTool, file: &Path) -> Option<Fingerprint> {
+    let metadata = fs::metadata(file).ok()?;
+    let extra_mtime_ms = match ai_tool {
+        AiTool::OpenCode => {
+            let session_id = file.file_stem()?.to_str()?;
+            opencode_message_base(file)
+                .and_then(|base| fs::metadata(base.join(session_id)).ok())
+                .map(|m| mtime_ms(&m))
+        }
+        _ => None,
+    };
+    Some(Fingerprint {
+        mtime_ms: mtime_ms(&metadata),
+        size: metadata.len(),
+        extra_mtime_ms,
+    })
+}
+
+/// Parse a session summary; unreadable Claude files are skipped (and not cached)
+fn parse_summary(ai_tool: &AiTool, file: &Path) -> Option<ConversationFile> {
+    match ai_tool {
+        AiTool::ClaudeCode => match parse_history_file_metadata(file) {
+            Ok(summary) => Some(summary),
+            Err(e) => {
+                tracing::warn!("Failed to parse metadata {}: {}", file.display(), e);
+                None
+            }
+        },
+        AiTool::OpenCode => Some(parse_opencode_session_metadata(file, opencode_message_base(file).as_deref())),
+        AiTool::Gemini => Some(parse_gemini_session_metadata(file)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("metadata-index-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn claude_line(uuid: &str, text: &str) -> String {
+        format!(
+            "{{\"type\":\"user\",\"uuid\":\"{}\",\"sessionId\":\"s1\",\"timestamp\":\"2025-01-01T10:00:00Z\",\"message\":{{\"role\":\"user\",\"content\":\"{}\"}}}}\n",
+            uuid, text
+        )
+    }
+
+    #[test]
+    fn test_reuses_and_invalidates_entries() {
+        let dir = temp_project("invalidate");
+        let file = dir.join("s1.jsonl");
+        fs::write(&file, claude_line("u1", "hello")).unwrap();
+
+        let index = MetadataIndex::in_memory();
+        let first = index.project_summaries(&AiTool::ClaudeCode, &dir).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].message_count, 1);
+        assert_eq!(index.len(), 1);
+
+        // A stale entry is served as long as the fingerprint matches
+        index.entries.get_mut(file.to_str().unwrap()).unwrap().summary.last_message_preview = "cached".into();
+        assert_eq!(index.summary(&AiTool::ClaudeCode, &file).unwrap().last_message_preview, "cached");
+
+        // Appending changes the size: the file is parsed again
+        let mut content = fs::read_to_string(&file).unwrap();
+        content.push_str(&claude_line("u2", "world"));
+        fs::write(&file, content).unwrap();
+        let updated = index.summary(&AiTool::ClaudeCode, &file).unwrap();
+        assert_eq!(updated.message_count, 2);
+        assert_ne!(updated.last_message_preview, "cached");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_save_and_reload() {
+        let dir = temp_project("persist");
+        let file = dir.join("s1.jsonl");
+        fs::write(&file, claude_line("u1", "hello")).unwrap();
+        fs::write(dir.join("gone.jsonl"), claude_line("u9", "bye")).unwrap();
+        let index_path = dir.join("index").join("metadata_index.json");
+
+        let index = MetadataIndex::open(index_path.clone());
+        assert_eq!(index.project_summaries(&AiTool::ClaudeCode, &dir).unwrap().len(), 2);
+        fs::remove_file(dir.join("gone.jsonl")).unwrap();
+        index.save().unwrap();
+
+        let reloaded = MetadataIndex::open(index_path);
+        assert_eq!(reloaded.len(), 1);
+        assert!(!reloaded.dirty.load(Ordering::Relaxed));
+        assert_eq!(reloaded.summary(&AiTool::ClaudeCode, &file).unwrap().message_count, 1);
+        assert!(!reloaded.dirty.load(Ordering::Relaxed));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -6,6 +6,15 @@ pub mod timestamp_restore;
 pub mod grouping;
 pub mod discovery;
 pub mod file_utils;
+pub mod path_guard;
+pub mod path_registry;
+pub mod project_identity;
+pub mod metadata_index;
+pub mod message_index;
+pub mod export;
+pub mod archive;
+pub mod handoff;
+pub mod forks;
 
 // Core types (from types.rs)
 pub use types::{
@@ -20,6 +29,7 @@ pub use types::{
     ClaudeJsonlEntry,
     InnerContentBlock,
     HistorySnapshot,
+    viewer_messages,
 };
 
 // Parsing functions
@@ -31,6 +41,10 @@ pub use parser::{
     list_project_summaries,
     list_project_summaries_for_tool,
     load_conversation,
+    find_conversation_file,
+    load_conversation_file,
+    parse_json_session_file,
+    extract_opencode_working_directory,
 };
 
 // Discovery functions
@@ -40,7 +54,7 @@ pub use discovery::discover_projects;
 pub use path_utils::{encode_project_path, decode_project_path, get_claude_projects_dir};
 
 // Grouping (for deduplication)
-pub use grouping::{ConversationSummary, group_conversations};
+pub use grouping::{ConversationSummary, group_conversations, group_conversations_with_forks};
 
 // Timestamp restoration
 pub use timestamp_restore::{
@@ -6,11 +6,20 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
+use rayon::prelude::*;
 
+use crate::config::app_config::DEFAULT_ROOT_LABEL;
 use crate::shared::logging;
 
-use super::path_utils::{get_claude_projects_dir, get_projects_dir, decode_project_path, encode_project_path, smart_decode_project_path, decode_gemini_hash};
-use super::grouping::{group_conversations, ConversationSummary};
+use super::path_utils::{
+    decode_project_path, encode_project_path, smart_decode_project_path, decode_gemini_hash,
+    get_history_roots, find_project_dirs, find_session_file,
+};
+use super::file_utils::extract_session_id;
+use super::project_identity::resolve_project_path;
+use super::metadata_index::metadata_index;
+use super::forks::fork_of;
+use super::grouping::{group_conversations_with_forks, ConversationSummary};
 
 // Re-export types for backward compatibility
 pub use super::types::{
@@ -67,69 +76,8 @@ pub fn parse_jsonl_file(file_path: &Path) -> Result<ConversationHistory, String>
             }
         }
 
-        // Handle user and assistant messages
-        // Note: entry.type can be "user" but assistant messages don't have entry.type
-        // So we check message.role instead
-        let entry_type = entry.entry_type.as_deref();
-        tracing::debug!("Processing entry type={:?}, has_message={}", entry_type, entry.message.is_some());
-
-        if let Some(inner) = entry.message {
-            let role = inner.role.as_deref();
-            tracing::debug!("Entry has message with role={:?}", role);
-
-            // Accept user/assistant messages (check role, not entry type)
-            if role == Some("user") || role == Some("assistant") {
-                // Extract text content - handle both String (user) and Array (assistant) formats
-                let text_content: Vec<ContentBlock> = match &inner.content {
-                    // User messages: content is a simple string
-                    MessageContent::Text(text) => {
-                        vec![ContentBlock::Text { text: text.clone() }]
-                    },
-                    // Assistant messages: content is an array of blocks
-                    MessageContent::Blocks(blocks) => {
-                        blocks.iter()
-                            .filter_map(|block| {
-                                match block {
-                                    InnerContentBlock::Text { text } => Some(ContentBlock::Text { text: text.clone() }),
-                                    InnerContentBlock::ToolUse { name, input } => Some(ContentBlock::ToolUse {
-                                        tool_type: "tool_use".to_string(),
-                                        name: name.clone(),
-                                        input: input.clone(),
-                                    }),
-                                    InnerContentBlock::ToolResult { content } => {
-                                        let content_str = match content {
-                                            serde_json::Value::String(s) => s.clone(),
-                                            _ => content.to_string(),
-                                        };
-                                        Some(ContentBlock::ToolResult {
-                                            result_type: "tool_result".to_string(),
-                                            content: content_str,
-                                        })
-                                    },
-                                    _ => None, // Skip thinking and other blocks
-                                }
-                            })
-                            .collect()
-                    }
-                };
-
-                // Include all messages with any content
-                if !text_content.is_empty() {
-                    let role_str = role.unwrap_or("unknown").to_string();
-                    tracing::debug!(
-                        "Adding message: role={}, content_blocks={}",
-                        role_str,
-                        text_content.len()
-                    );
-                    messages.push(Message {
-                        role: role_str,
-                        content: text_content,
-                        timestamp: entry.timestamp.clone(),
-                    });
-                } else {
-                    tracing::debug!("Skipping empty message for entry_type={:?}", entry_type);
-                }
-            }
+        if let Some(message) = jsonl_entry_message(&entry) {
+            messages.push(message);
         }
     }
 
@@ -169,6 +117,73 @@ pub fn parse_jsonl_file(file_path: &Path) -> Result<ConversationHistory, String>
     })
 }
 
+/// Message carried by one Claude JSONL entry (None for non-message or empty entries)
+/// Note: entry.type can be "user" but assistant messages don't have entry.type
+/// So we check message.role instead
+pub(crate) fn jsonl_entry_message(entry: &ClaudeJsonlEntry) -> Option<Message> {
+    let entry_type = entry.entry_type.as_deref();
+    let inner = entry.message.as_ref()?;
+    let role = inner.role.as_deref();
+    tracing::debug!("Entry has message with role={:?}", role);
+
+    // Accept user/assistant messages (check role, not entry type)
+    if role != Some("user") && role != Some("assistant") {
+        return None;
+    }
+
+    // Extract text content - handle both String (user) and Array (assistant) formats
+    let text_content: Vec<ContentBlock> = match &inner.content {
+        // User messages: content is a simple string
+        MessageContent::Text(text) => {
+            vec![ContentBlock::Text { text: text.clone() }]
+        },
+        // Assistant messages: content is an array of blocks
+        MessageContent::Blocks(blocks) => {
+            blocks.iter()
+                .filter_map(|block| {
+                    match block {
+                        InnerContentBlock::Text { text } => Some(ContentBlock::Text { text: text.clone() }),
+                        InnerContentBlock::ToolUse { name, input } => Some(ContentBlock::ToolUse {
+                            tool_type: "tool_use".to_string(),
+                            name: name.clone(),
+                            input: input.clone(),
+                        }),
+                        InnerContentBlock::ToolResult { content } => {
+                            let content_str = match content {
+                                serde_json::Value::String(s) => s.clone(),
+                                _ => content.to_string(),
+                            };
+                            Some(ContentBlock::ToolResult {
+                                result_type: "tool_result".to_string(),
+                                content: content_str,
+                            })
+                        },
+                        _ => None, // Skip thinking and other blocks
+                    }
+                })
+                .collect()
+        }
+    };
+
+    // Include all messages with any content
+    if text_content.is_empty() {
+        tracing::debug!("Skipping empty message for entry_type={:?}", entry_type);
+        return None;
+    }
+
+    let role_str = role.unwrap_or("unknown").to_string();
+    tracing::debug!(
+        "Adding message: role={}, content_blocks={}",
+        role_str,
+        text_content.len()
+    );
+    Some(Message {
+        role: role_str,
+        content: text_content,
+        timestamp: entry.timestamp.clone(),
+    })
+}
+
 /// Read configuration for a specific AI tool
 fn read_tool_config(ai_tool: &crate::domain::models::AiTool) -> Result<Option<serde_json::Value>, String> {
     let home = std::env::var("HOME")
@@ -242,7 +257,7 @@ pub fn list_projects() -> Result<Vec<ProjectInfo>, String> {
 
 /// Extract the working directory from the most recent OpenCode session file
 /// OpenCode stores `directory` field in each ses_*.json file
-fn extract_opencode_working_directory(session_dir: &Path) -> Option<String> {
+pub fn extract_opencode_working_directory(session_dir: &Path) -> Option<String> {
     // Read all session files and find the most recent one
     let entries = fs::read_dir(session_dir).ok()?;
 
@@ -284,60 +299,31 @@ fn extract_opencode_working_directory(session_dir: &Path) -> Option<String> {
 /// - OpenCode: ses_*.json files directly in project dir
 /// - Gemini: session-*.json files in chats/ subdirectory
 fn count_sessions_for_tool(project_dir: &Path, ai_tool: &crate::domain::models::AiTool) -> usize {
-    match ai_tool {
-        crate::domain::models::AiTool::ClaudeCode => {
-            // Claude: count *.jsonl files
-            fs::read_dir(project_dir)
-                .ok()
-                .map(|entries| {
-                    entries
-                        .flatten()
-                        .filter(|e| {
-                            e.path()
-                                .extension()
-                                .and_then(|ext| ext.to_str())
-                                == Some("jsonl")
-                        })
-                        .count()
-                })
-                .unwrap_or(0)
-        }
-        crate::domain::models::AiTool::OpenCode => {
-            // OpenCode: count ses_*.json files
-            fs::read_dir(project_dir)
-                .ok()
-                .map(|entries| {
-                    entries
-                        .flatten()
-                        .filter(|e| {
-                            let name = e.file_name().to_string_lossy().to_string();
-                            name.starts_with("ses_") && name.ends_with(".json")
-                        })
-                        .count()
-                })
-                .unwrap_or(0)
-        }
-        crate::domain::models::AiTool::Gemini => {
-            // Gemini: count session-*.json files in chats/ subdirectory
-            let chats_dir = project_dir.join("chats");
-            if chats_dir.exists() {
-                fs::read_dir(&chats_dir)
-                    .ok()
-                    .map(|entries| {
-                        entries
-                            .flatten()
-                            .filter(|e| {
-                                let name = e.file_name().to_string_lossy().to_string();
-                                name.starts_with("session-") && name.ends_with(".json")
-                            })
-                            .count()
-                    })
-                    .unwrap_or(0)
-            } else {
-                0
-            }
-        }
-    }
+    list_session_ids_for_tool(project_dir, ai_tool).len()
+}
+
+/// Session IDs found in a project directory
+/// - Claude: *.jsonl files
+/// - OpenCode: ses_*.json files
+/// - Gemini: session-*.json files in chats/ subdirectory
+pub(crate) fn list_session_ids_for_tool(project_dir: &Path, ai_tool: &crate::domain::models::AiTool) -> Vec<String> {
+    let (dir, prefix, suffix) = match ai_tool {
+        crate::domain::models::AiTool::ClaudeCode => (project_dir.to_path_buf(), "", ".jsonl"),
+        crate::domain::models::AiTool::OpenCode => (project_dir.to_path_buf(), "ses_", ".json"),
+        crate::domain::models::AiTool::Gemini => (project_dir.join("chats"), "session-", ".json"),
+    };
+
+    fs::read_dir(&dir)
+        .ok()
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| name.starts_with(prefix) && name.ends_with(suffix))
+                .map(|name| extract_session_id(&name, prefix, suffix))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Get the last updated timestamp for a project directory by checking session file modification times
@@ -385,30 +371,13 @@ fn get_project_last_updated(project_dir: &Path, ai_tool: &crate::domain::models:
             }
         }
         crate::domain::models::AiTool::Gemini => {
-            // Read lastUpdated from JSON files (more accurate than file mtime)
-            let chats_dir = project_dir.join("chats");
-            if let Ok(entries) = fs::read_dir(&chats_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-                    if name.starts_with("session-") && name.ends_with(".json") {
-                        if let Ok(content) = fs::read_to_string(&path) {
-                            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                                if let Some(last_updated) = json.get("lastUpdated").and_then(|v| v.as_str()) {
-                                    if let Ok(dt) = DateTime::parse_from_rfc3339(last_updated) {
-                                        let sys_time = std::time::UNIX_EPOCH + std::time::Duration::from_secs(dt.timestamp() as u64);
-                                        latest_time = Some(match latest_time {
-                                            None => sys_time,
-                                            Some(prev) if sys_time > prev => sys_time,
-                                            Some(prev) => prev,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+            // Indexed session timestamps (lastUpdated, more accurate than file mtime)
+            let summaries = metadata_index().project_summaries(ai_tool, project_dir).unwrap_or_default();
+            latest_time = summaries
+                .iter()
+                .filter_map(|summary| DateTime::parse_from_rfc3339(&summary.last_time).ok())
+                .map(|dt| std::time::UNIX_EPOCH + std::time::Duration::from_secs(dt.timestamp() as u64))
+                .max();
         }
     }
 
@@ -425,11 +394,57 @@ fn get_project_last_updated(project_dir: &Path, ai_tool: &crate::domain::models:
     }
 }
 
-/// Scan projects for a specific AI tool
+/// Scan projects for a specific AI tool across all of its history roots
+/// A project found in several roots is listed once: root labels are collected
+/// and sessions present in more than one root are counted once.
 fn scan_projects_for_tool(ai_tool: &crate::domain::models::AiTool) -> Result<Vec<ProjectInfo>, String> {
+    let mut merged: HashMap<String, ProjectInfo> = HashMap::new();
+
+    for root in get_history_roots(ai_tool) {
+        let projects = match scan_root_for_tool(ai_tool, &root.projects_dir()) {
+            Ok(projects) => projects,
+            Err(e) => {
+                tracing::warn!("Failed to scan {:?} root '{}': {}", ai_tool, root.label, e);
+                continue;
+            }
+        };
+
+        for mut project in projects {
+            match merged.get_mut(&project.encoded_name) {
+                Some(existing) => {
+                    existing.last_updated = existing.last_updated.max(project.last_updated);
+                    existing.roots.push(root.label.clone());
+                }
+                None => {
+                    project.roots = vec![root.label.clone()];
+                    merged.insert(project.encoded_name.clone(), project);
+                }
+            }
+        }
+    }
+
+    for project in merged.values_mut().filter(|p| p.roots.len() > 1) {
+        let session_ids: HashSet<String> = find_project_dirs(ai_tool, &project.encoded_name)
+            .iter()
+            .flat_map(|(_, dir)| list_session_ids_for_tool(dir, ai_tool))
+            .collect();
+        project.session_count = session_ids.len();
+    }
+
+    // Only label projects when extra roots are involved
+    for project in merged.values_mut() {
+        if project.roots.iter().all(|label| label == DEFAULT_ROOT_LABEL) {
+            project.roots.clear();
+        }
+    }
+
+    Ok(merged.into_values().collect())
+}
+
+/// Scan projects of a single history root
+fn scan_root_for_tool(ai_tool: &crate::domain::models::AiTool, projects_dir: &Path) -> Result<Vec<ProjectInfo>, String> {
     let tool_name = format!("{:?}", ai_tool);
     logging::log_project_discovery_start(&tool_name);
-    let projects_dir = get_projects_dir(ai_tool)?;
     tracing::info!("Projects directory for {:?}: {}", ai_tool, projects_dir.display());
 
     if !projects_dir.exists() {
@@ -473,6 +488,7 @@ fn scan_projects_for_tool(ai_tool: &crate::domain::models::AiTool) -> Result<Vec
                             session_count,
                             ai_tool: ai_tool.clone(),
                             last_updated,
+                            roots: Vec::new(),
                         },
                     );
                 } else {
@@ -486,13 +502,13 @@ fn scan_projects_for_tool(ai_tool: &crate::domain::models::AiTool) -> Result<Vec
     // OpenCode stores all sessions in storage/session/global/ with ses_*.json files
     if matches!(ai_tool, crate::domain::models::AiTool::OpenCode) {
         // Count ses_*.json files directly in the projects_dir (which is storage/session/global/)
-        let session_count = count_sessions_for_tool(&projects_dir, ai_tool);
+        let session_count = count_sessions_for_tool(projects_dir, ai_tool);
 
         if session_count > 0 {
             tracing::info!("Found {} OpenCode sessions in global directory", session_count);
 
             // Extract working directory from the most recent session file
-            let working_dir = extract_opencode_working_directory(&projects_dir)
+            let working_dir = extract_opencode_working_directory(projects_dir)
                 .unwrap_or_else(|| "OpenCode".to_string());
 
             // Extract project name from working directory (last segment)
@@ -502,7 +518,7 @@ fn scan_projects_for_tool(ai_tool: &crate::domain::models::AiTool) -> Result<Vec
                 .unwrap_or("OpenCode")
                 .to_string();
 
-            let last_updated = get_project_last_updated(&projects_dir, ai_tool);
+            let last_updated = get_project_last_updated(projects_dir, ai_tool);
             projects.insert(
                 "global".to_string(),
                 ProjectInfo {
@@ -512,6 +528,7 @@ fn scan_projects_for_tool(ai_tool: &crate::domain::models::AiTool) -> Result<Vec
                     session_count,
                     ai_tool: ai_tool.clone(),
                     last_updated,
+                    roots: Vec::new(),
                 },
             );
         }
@@ -522,7 +539,7 @@ fn scan_projects_for_tool(ai_tool: &crate::domain::models::AiTool) -> Result<Vec
     // Fallback: scan filesystem for additional projects
     tracing::info!("Scanning filesystem for additional projects for {:?}", ai_tool);
 
-    let entries = match fs::read_dir(&projects_dir) {
+    let entries = match fs::read_dir(projects_dir) {
         Ok(entries) => entries,
         Err(e) => {
             tracing::warn!("Failed to read projects directory for {:?}: {}", ai_tool, e);
@@ -530,102 +547,128 @@ fn scan_projects_for_tool(ai_tool: &crate::domain::models::AiTool) -> Result<Vec
         }
     };
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        tracing::info!("Processing entry: {}", path.display());
-        if !path.is_dir() {
-            tracing::info!("Skipping non-directory: {}", path.display());
-            continue;
-        }
+    // Project directories are independent: inspect them in parallel
+    let dirs: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    let scanned: Vec<ProjectInfo> = dirs
+        .par_iter()
+        .filter_map(|path| scan_project_dir(ai_tool, &tool_name, path, &projects))
+        .collect();
+    for project in scanned {
+        projects.insert(project.encoded_name.clone(), project);
+    }
 
-        let encoded_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("")
-            .to_string();
-        tracing::info!("Processing directory: {} (encoded: {})", path.display(), encoded_name);
+    let result: Vec<ProjectInfo> = projects.into_values().collect();
+    logging::log_project_discovery_result(&tool_name, result.len(), projects_dir);
+    Ok(result)
+}
 
-        tracing::debug!("Found directory for {:?}: {}", ai_tool, encoded_name);
+/// Inspect one project directory found by the filesystem scan
+/// Returns None for non-project directories, empty projects and projects already known from the config.
+fn scan_project_dir(
+    ai_tool: &crate::domain::models::AiTool,
+    tool_name: &str,
+    path: &Path,
+    known: &HashMap<String, ProjectInfo>,
+) -> Option<ProjectInfo> {
+    tracing::info!("Processing entry: {}", path.display());
+    if !path.is_dir() {
+        tracing::info!("Skipping non-directory: {}", path.display());
+        return None;
+    }
 
-        // For Gemini, skip non-hash directories (like 'bin')
-        // Gemini uses hash directories with 32+ characters
-        if matches!(ai_tool, crate::domain::models::AiTool::Gemini)
-            && (encoded_name.len() < 10 || !encoded_name.chars().all(|c| c.is_ascii_hexdigit())) {
-                logging::log_gemini_dir_filter(&encoded_name, "not a valid hash directory", true);
-                continue;
-            }
+    let encoded_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string();
+    tracing::info!("Processing directory: {} (encoded: {})", path.display(), encoded_name);
 
-        // Use tool-specific decode for project paths
-        let (project_path, project_name) = match ai_tool {
-            crate::domain::models::AiTool::ClaudeCode => {
-                // Claude uses dash-separated paths
-                let (path, name) = smart_decode_project_path(&encoded_name);
-                tracing::debug!("Smart decoded {} -> path: {}, name: {}", encoded_name, path, name);
-                (path, name)
-            }
-            crate::domain::models::AiTool::Gemini => {
-                // Gemini uses SHA256 hash of the path
-                let (path, name) = decode_gemini_hash(&encoded_name);
-                tracing::debug!("Gemini hash {} -> path: {}, name: {}", encoded_name, path, name);
-                (path, name)
-            }
-            _ => {
-                // For other tools (OpenCode), use standard decode
-                match decode_project_path(&encoded_name) {
-                    Ok(p) => {
-                        let name = PathBuf::from(&p)
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("unknown")
-                            .to_string();
-                        tracing::debug!("Decoded {} to {}", encoded_name, p);
-                        (p, name)
-                    },
-                    Err(e) => {
-                        tracing::warn!("Failed to decode project directory {}: {}", encoded_name, e);
-                        continue;
-                    },
+    tracing::debug!("Found directory for {:?}: {}", ai_tool, encoded_name);
+
+    // For Gemini, skip non-hash directories (like 'bin')
+    // Gemini uses hash directories with 32+ characters
+    if matches!(ai_tool, crate::domain::models::AiTool::Gemini)
+        && (encoded_name.len() < 10 || !encoded_name.chars().all(|c| c.is_ascii_hexdigit())) {
+            logging::log_gemini_dir_filter(&encoded_name, "not a valid hash directory", true);
+            return None;
+        }
+
+    // Use tool-specific decode for project paths
+    let (project_path, project_name) = match ai_tool {
+        crate::domain::models::AiTool::ClaudeCode => {
+            // Real path from the sessions' cwd, else guess from the dash-separated name
+            match resolve_project_path(ai_tool, &encoded_name) {
+                Some(path) => {
+                    let name = PathBuf::from(&path)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or(&encoded_name)
+                        .to_string();
+                    (path, name)
+                }
+                None => {
+                    let (path, name) = smart_decode_project_path(&encoded_name);
+                    tracing::debug!("Smart decoded {} -> path: {}, name: {}", encoded_name, path, name);
+                    (path, name)
                 }
             }
-        };
-
-        // Check if we already have this project from config
-        if projects.contains_key(&encoded_name) {
-            tracing::debug!("Project {} already known from config", encoded_name);
-            continue;
         }
+        crate::domain::models::AiTool::Gemini => {
+            // Gemini uses SHA256 hash of the path
+            let (path, name) = decode_gemini_hash(&encoded_name);
+            tracing::debug!("Gemini hash {} -> path: {}, name: {}", encoded_name, path, name);
+            (path, name)
+        }
+        _ => {
+            // For other tools (OpenCode), use standard decode
+            match decode_project_path(&encoded_name) {
+                Ok(p) => {
+                    let name = PathBuf::from(&p)
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("unknown")
+                        .to_string();
+                    tracing::debug!("Decoded {} to {}", encoded_name, p);
+                    (p, name)
+                },
+                Err(e) => {
+                    tracing::warn!("Failed to decode project directory {}: {}", encoded_name, e);
+                    return None;
+                },
+            }
+        }
+    };
 
-        // Count sessions using tool-specific logic
-        let session_count = count_sessions_for_tool(&path, ai_tool);
+    // Check if we already have this project from config
+    if known.contains_key(&encoded_name) {
+        tracing::debug!("Project {} already known from config", encoded_name);
+        return None;
+    }
 
-        // Skip projects with 0 sessions (empty directories)
-        if session_count == 0 {
-            logging::log_empty_project_skip(&tool_name, &project_name);
-            continue;
-        }
+    // Count sessions using tool-specific logic
+    let session_count = count_sessions_for_tool(path, ai_tool);
 
-        // Log session count
-        logging::log_session_count(&tool_name, &project_name, session_count);
-
-        tracing::debug!("Found {} sessions for project {}", session_count, project_name);
-
-        let last_updated = get_project_last_updated(&path, ai_tool);
-        projects.insert(
-            encoded_name.clone(),
-            ProjectInfo {
-                name: project_name,
-                path: project_path,
-                encoded_name,
-                session_count,
-                ai_tool: ai_tool.clone(),
-                last_updated,
-            },
-        );
+    // Skip projects with 0 sessions (empty directories)
+    if session_count == 0 {
+        logging::log_empty_project_skip(tool_name, &project_name);
+        return None;
     }
 
-    let result: Vec<ProjectInfo> = projects.into_values().collect();
-    logging::log_project_discovery_result(&tool_name, result.len(), &projects_dir);
-    Ok(result)
+    // Log session count
+    logging::log_session_count(tool_name, &project_name, session_count);
+
+    tracing::debug!("Found {} sessions for project {}", session_count, project_name);
+
+    let last_updated = get_project_last_updated(path, ai_tool);
+    Some(ProjectInfo {
+        name: project_name,
+        path: project_path,
+        encoded_name,
+        session_count,
+        ai_tool: ai_tool.clone(),
+        last_updated,
+        roots: Vec::new(),
+    })
 }
 
 /// List all projects from ~/.claude/projects/ (legacy function for compatibility)
@@ -753,6 +796,7 @@ pub fn parse_history_file_metadata(file_path: &Path) -> Result<ConversationFile,
 /// List conversation summaries for a project (with deduplication)
 /// This is the recommended function for listing - it removes duplicates from continued sessions
 /// Supports all AI tools: Claude (.jsonl), OpenCode (ses_*.json), Gemini (chats/session-*.json)
+/// All history roots of the first provider that has the project are scanned together.
 pub fn list_project_summaries(encoded_name: &str) -> Result<Vec<ConversationSummary>, String> {
     // Try all AI tool directories to find the project
     let ai_tools = vec![
@@ -762,41 +806,12 @@ pub fn list_project_summaries(encoded_name: &str) -> Result<Vec<ConversationSumm
     ];
 
     for ai_tool in &ai_tools {
-        let projects_dir = match get_projects_dir(ai_tool) {
-            Ok(dir) => dir,
-            Err(_) => continue,
-        };
-
-        // Special case for OpenCode: "global" is the projects_dir itself, not a subdirectory
-        let project_dir = if matches!(ai_tool, crate::domain::models::AiTool::OpenCode) && encoded_name == "global" {
-            projects_dir.clone()
-        } else {
-            projects_dir.join(encoded_name)
-        };
-
-        if !project_dir.exists() {
-            continue;
-        }
-
-        tracing::info!("Found project {} in {:?} directory", encoded_name, ai_tool);
-
-        // Use specialized parsers for each AI tool
-        let conversation_files = match ai_tool {
-            crate::domain::models::AiTool::ClaudeCode => {
-                super::parsers::parse_claude_summaries(&project_dir)?
-            }
-            crate::domain::models::AiTool::OpenCode => {
-                super::parsers::parse_opencode_summaries(&project_dir)?
-            }
-            crate::domain::models::AiTool::Gemini => {
-                super::parsers::parse_gemini_summaries(&project_dir)?
-            }
-        };
+        let conversation_files = collect_project_files(ai_tool, encoded_name)?;
 
         if !conversation_files.is_empty() {
             tracing::debug!("Before grouping: {} conversation files", conversation_files.len());
             // Apply grouping algorithm to remove duplicates (mainly for Claude)
-            let summaries = group_conversations(conversation_files);
+            let summaries = group_conversations_with_forks(conversation_files, |id| fork_of(id).is_some());
             tracing::debug!("After grouping: {} summaries", summaries.len());
             return Ok(summaries);
         }
@@ -806,6 +821,40 @@ pub fn list_project_summaries(encoded_name: &str) -> Result<Vec<ConversationSumm
     Ok(Vec::new())
 }
 
+/// Parse the session metadata of a project in every root of a provider
+/// Sessions present in several roots are kept once (see `dedup_session_files`).
+fn collect_project_files(ai_tool: &crate::domain::models::AiTool, encoded_name: &str) -> Result<Vec<ConversationFile>, String> {
+    let mut conversation_files = Vec::new();
+
+    for (root, project_dir) in find_project_dirs(ai_tool, encoded_name) {
+        tracing::info!("Found project {} in {:?} root '{}'", encoded_name, ai_tool, root.label);
+
+        // Summaries come from the metadata index: only changed files are parsed
+        let files = metadata_index().project_summaries(ai_tool, &project_dir)?;
+        conversation_files.extend(files);
+    }
+
+    Ok(dedup_session_files(conversation_files))
+}
+
+/// Keep one file per session ID: the one with the most messages, then the most recent
+/// (the same session copied to several roots)
+pub fn dedup_session_files(files: Vec<ConversationFile>) -> Vec<ConversationFile> {
+    let mut by_session: HashMap<String, ConversationFile> = HashMap::new();
+
+    for file in files {
+        match by_session.get(&file.session_id) {
+            Some(existing)
+                if (existing.message_count, &existing.last_time) >= (file.message_count, &file.last_time) => {}
+            _ => {
+                by_session.insert(file.session_id.clone(), file);
+            }
+        }
+    }
+
+    by_session.into_values().collect()
+}
+
 /// List project summaries for a specific AI tool
 /// tool_slug: Optional filter ("claude", "opencode", "gemini") - if None, searches all tools
 pub fn list_project_summaries_for_tool(encoded_name: &str, tool_slug: Option<&str>) -> Result<Vec<ConversationSummary>, String> {
@@ -831,45 +880,14 @@ pub fn list_project_summaries_for_tool(encoded_name: &str, tool_slug: Option<&st
     let mut all_conversation_files = Vec::new();
 
     for ai_tool in &ai_tools {
-        let projects_dir = match get_projects_dir(ai_tool) {
-            Ok(dir) => dir,
-            Err(_) => continue,
-        };
-
-        // Special case for OpenCode: "global" is the projects_dir itself, not a subdirectory
-        let project_dir = if matches!(ai_tool, crate::domain::models::AiTool::OpenCode) && encoded_name == "global" {
-            projects_dir.clone()
-        } else {
-            projects_dir.join(encoded_name)
-        };
-
-        if !project_dir.exists() {
-            tracing::debug!("Project dir not found: {:?} (tool: {:?})", project_dir, ai_tool);
-            continue;
-        }
-
-        tracing::info!("Found project {} in {:?} directory (tool filter: {:?})", encoded_name, ai_tool, tool_slug);
-
-        // Use specialized parsers for each AI tool
-        let conversation_files = match ai_tool {
-            crate::domain::models::AiTool::ClaudeCode => {
-                super::parsers::parse_claude_summaries(&project_dir)?
-            }
-            crate::domain::models::AiTool::OpenCode => {
-                super::parsers::parse_opencode_summaries(&project_dir)?
-            }
-            crate::domain::models::AiTool::Gemini => {
-                super::parsers::parse_gemini_summaries(&project_dir)?
-            }
-        };
-
+        let conversation_files = collect_project_files(ai_tool, encoded_name)?;
         tracing::info!("Tool {:?} returned {} conversation files for {}", ai_tool, conversation_files.len(), encoded_name);
         all_conversation_files.extend(conversation_files);
     }
 
     if !all_conversation_files.is_empty() {
         tracing::debug!("Before grouping: {} total conversation files", all_conversation_files.len());
-        let summaries = group_conversations(all_conversation_files);
+        let summaries = group_conversations_with_forks(all_conversation_files, |id| fork_of(id).is_some());
         tracing::debug!("After grouping: {} summaries", summaries.len());
         return Ok(summaries);
     }
@@ -881,40 +899,21 @@ pub fn list_project_summaries_for_tool(encoded_name: &str, tool_slug: Option<&st
 /// List all conversation histories for a specific project (full parsing)
 /// Note: For listing purposes, prefer `list_project_summaries` which is faster and deduplicates
 pub fn list_project_histories(encoded_name: &str) -> Result<Vec<ConversationHistory>, String> {
-    let projects_dir = get_claude_projects_dir()?;
-    let project_dir = projects_dir.join(encoded_name);
-
-    if !project_dir.exists() {
+    let ai_tool = crate::domain::models::AiTool::ClaudeCode;
+    if find_project_dirs(&ai_tool, encoded_name).is_empty() {
         return Ok(Vec::new());
     }
 
     // First get the deduplicated summaries to know which sessions to load
     let summaries = list_project_summaries(encoded_name)?;
-    let valid_session_ids: HashSet<String> = summaries.iter()
-        .map(|s| s.session_id.clone())
-        .collect();
 
     let mut histories = Vec::new();
 
-    let entries = fs::read_dir(&project_dir)
-        .map_err(|e| format!("Failed to read project directory: {}", e))?;
-
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+    for summary in summaries {
+        // Resolve across roots (most recent copy of the session)
+        let Some((_, path)) = find_session_file(&ai_tool, encoded_name, &summary.session_id) else {
             continue;
-        }
-
-        // Only load sessions that passed the deduplication filter
-        let session_id = path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("");
-
-        if !valid_session_ids.contains(session_id) {
-            tracing::debug!("Skipping duplicate session: {}", session_id);
-            continue;
-        }
+        };
 
         match parse_jsonl_file(&path) {
             Ok(history) => histories.push(history),
@@ -930,69 +929,47 @@ pub fn list_project_histories(encoded_name: &str) -> Result<Vec<ConversationHist
 
 /// Load a specific conversation by session ID with timestamp restoration
 /// Supports all AI tools: Claude (.jsonl), OpenCode (ses_*.json), Gemini (chats/session-*.json)
+/// Every history root is searched; the most recent copy of the session wins.
 pub fn load_conversation(encoded_name: &str, session_id: &str) -> Result<ConversationHistory, String> {
     tracing::debug!("load_conversation called: encoded_name='{}', session_id='{}'", encoded_name, session_id);
 
-    // Try all AI tool directories to find the session
-    let ai_tools = vec![
+    let (ai_tool, file_path) = find_conversation_file(encoded_name, session_id)
+        .ok_or_else(|| format!("Session not found: {}", session_id))?;
+    load_conversation_file(&ai_tool, &file_path, session_id)
+}
+
+/// Locate a session file, trying every AI tool directory
+pub fn find_conversation_file(encoded_name: &str, session_id: &str) -> Option<(crate::domain::models::AiTool, PathBuf)> {
+    let ai_tools = [
         crate::domain::models::AiTool::ClaudeCode,
         crate::domain::models::AiTool::OpenCode,
         crate::domain::models::AiTool::Gemini,
     ];
 
-    for ai_tool in &ai_tools {
-        let projects_dir = match get_projects_dir(ai_tool) {
-            Ok(dir) => dir,
-            Err(_) => continue,
-        };
-
-        // Special case for OpenCode: "global" is the projects_dir itself, not a subdirectory
-        let project_dir = if matches!(ai_tool, crate::domain::models::AiTool::OpenCode) && encoded_name == "global" {
-            projects_dir.clone()
-        } else {
-            projects_dir.join(encoded_name)
-        };
-
-        tracing::debug!("Trying {:?}: project_dir={}, exists={}", ai_tool, project_dir.display(), project_dir.exists());
-
-        if !project_dir.exists() {
-            continue;
-        }
-
-        // Different file patterns per AI tool
-        let file_path = match ai_tool {
-            crate::domain::models::AiTool::ClaudeCode => {
-                project_dir.join(format!("{}.jsonl", session_id))
-            }
-            crate::domain::models::AiTool::OpenCode => {
-                project_dir.join(format!("ses_{}.json", session_id))
-            }
-            crate::domain::models::AiTool::Gemini => {
-                project_dir.join("chats").join(format!("session-{}.json", session_id))
-            }
-        };
-
-        tracing::debug!("Checking file: {}, exists={}", file_path.display(), file_path.exists());
-
-        if file_path.exists() {
-            tracing::info!("Found session {} in {:?} directory at {}", session_id, ai_tool, file_path.display());
+    ai_tools.into_iter().find_map(|ai_tool| {
+        let (root, file_path) = find_session_file(&ai_tool, encoded_name, session_id)?;
+        tracing::info!("Found session {} in {:?} root '{}' at {}", session_id, ai_tool, root.label, file_path.display());
+        Some((ai_tool, file_path))
+    })
+}
 
-            // Claude uses JSONL, OpenCode/Gemini use JSON
-            return match ai_tool {
-                crate::domain::models::AiTool::ClaudeCode => parse_jsonl_file(&file_path),
-                crate::domain::models::AiTool::OpenCode | crate::domain::models::AiTool::Gemini => {
-                    // For OpenCode/Gemini, parse JSON and convert to ConversationHistory
-                    parse_json_session_file(&file_path, session_id, ai_tool)
-                }
-            };
+/// Parse a session file found by `find_conversation_file`
+pub fn load_conversation_file(
+    ai_tool: &crate::domain::models::AiTool,
+    file_path: &Path,
+    session_id: &str,
+) -> Result<ConversationHistory, String> {
+    // Claude uses JSONL, OpenCode/Gemini use JSON
+    match ai_tool {
+        crate::domain::models::AiTool::ClaudeCode => parse_jsonl_file(file_path),
+        crate::domain::models::AiTool::OpenCode | crate::domain::models::AiTool::Gemini => {
+            parse_json_session_file(file_path, session_id, ai_tool)
         }
     }
-
-    Err(format!("Session not found: {}", session_id))
 }
 
 /// Parse OpenCode or Gemini JSON session file
-fn parse_json_session_file(file_path: &Path, session_id: &str, ai_tool: &crate::domain::models::AiTool) -> Result<ConversationHistory, String> {
+pub fn parse_json_session_file(file_path: &Path, session_id: &str, ai_tool: &crate::domain::models::AiTool) -> Result<ConversationHistory, String> {
     let content = fs::read_to_string(file_path)
         .map_err(|e| format!("Failed to read file: {}", e))?;
 
@@ -1005,7 +982,9 @@ fn parse_json_session_file(file_path: &Path, session_id: &str, ai_tool: &crate::
     // OpenCode stores messages in a SEPARATE directory: ~/.local/share/opencode/storage/message/ses_{id}/msg_*.json
     // The session file only contains metadata (title, time, etc.)
     if matches!(ai_tool, crate::domain::models::AiTool::OpenCode) {
-        messages = super::parsers::opencode::load_opencode_messages(session_id)?;
+        let storage = super::parsers::opencode::storage_dir_of(file_path)
+            .ok_or_else(|| format!("Not an OpenCode session file: {}", file_path.display()))?;
+        messages = super::parsers::opencode::load_opencode_messages(storage, session_id)?;
     } else {
         // Gemini format: { "messages": [...] } or root array
         // Note: Gemini uses "type" field ("user"/"gemini") instead of "role"
@@ -1202,4 +1181,29 @@ mod tests {
 
         println!("===========================================\n");
     }
+
+    #[test]
+    fn test_dedup_session_files_across_roots() {
+        let file = |session_id: &str, path: &str, count: usize, last: &str| ConversationFile {
+            session_id: session_id.to_string(),
+            file_path: path.to_string(),
+            message_ids: std::collections::HashSet::new(),
+            start_time: "2025-01-01T00:00:00Z".to_string(),
+            last_time: last.to_string(),
+            message_count: count,
+            last_message_preview: String::new(),
+        };
+
+        let mut files = dedup_session_files(vec![
+            file("a", "/local/a.jsonl", 3, "2025-01-02T00:00:00Z"),
+            file("a", "/backup/a.jsonl", 5, "2025-01-01T00:00:00Z"),
+            file("b", "/local/b.jsonl", 2, "2025-01-01T00:00:00Z"),
+            file("b", "/backup/b.jsonl", 2, "2025-01-03T00:00:00Z"),
+        ]);
+        files.sort_by(|x, y| x.session_id.cmp(&y.session_id));
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].file_path, "/backup/a.jsonl");
+        assert_eq!(files[1].file_path, "/backup/b.jsonl");
+    }
 }
@@ -29,59 +29,65 @@ pub fn parse_gemini_summaries(project_dir: &Path) -> Result<Vec<ConversationFile
             continue;
         }
 
-        let session_id = crate::history::file_utils::extract_session_id(name, "session-", ".json");
-
-        // Use file modification time as fallback instead of Utc::now()
-        let file_mtime_fallback = crate::history::file_utils::get_file_mtime_fallback(&path);
-
-        // Parse Gemini session file to get message count and timestamps
-        let (message_count, start_time, last_time, preview) = fs::read_to_string(&path)
-            .ok()
-            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
-            .map(|json| {
-                let count = json.get("messages")
-                    .and_then(|m| m.as_array())
-                    .map(|arr| arr.len())
-                    .unwrap_or(0);
-                let start = json.get("startTime")
-                    .and_then(|t| t.as_str())
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| file_mtime_fallback.clone());
-                let internal_last = json.get("lastUpdated")
-                    .and_then(|t| t.as_str())
-                    .filter(|s| !s.is_empty())
-                    .map(|s| s.to_string());
-                // Use MAX of internal timestamp and file mtime for accurate "time ago"
-                let last = crate::history::file_utils::get_max_timestamp(internal_last, &file_mtime_fallback);
-                // Get last message preview
-                let prev = json.get("messages")
-                    .and_then(|m| m.as_array())
-                    .and_then(|arr| arr.last())
-                    .and_then(|msg| msg.get("content"))
-                    .and_then(|c| c.as_str())
-                    .map(|s| s.chars().take(100).collect::<String>())
-                    .unwrap_or_else(|| "Gemini session".to_string());
-                (count, start, last, prev)
-            })
-            .unwrap_or_else(|| {
-                (0, file_mtime_fallback.clone(), file_mtime_fallback.clone(), "Gemini session".to_string())
-            });
-
-        conversation_files.push(ConversationFile {
-            session_id,
-            file_path: path.to_string_lossy().to_string(),
-            message_ids: HashSet::new(),
-            start_time,
-            last_time,
-            message_count,
-            last_message_preview: preview,
-        });
+        conversation_files.push(parse_gemini_session_metadata(&path));
     }
 
     Ok(conversation_files)
 }
 
+/// Summary of a single Gemini session-*.json file
+pub fn parse_gemini_session_metadata(path: &Path) -> ConversationFile {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let session_id = crate::history::file_utils::extract_session_id(name, "session-", ".json");
+
+    // Use file modification time as fallback instead of Utc::now()
+    let file_mtime_fallback = crate::history::file_utils::get_file_mtime_fallback(path);
+
+    // Parse Gemini session file to get message count and timestamps
+    let (message_count, start_time, last_time, preview) = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .map(|json| {
+            let count = json.get("messages")
+                .and_then(|m| m.as_array())
+                .map(|arr| arr.len())
+                .unwrap_or(0);
+            let start = json.get("startTime")
+                .and_then(|t| t.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| file_mtime_fallback.clone());
+            let internal_last = json.get("lastUpdated")
+                .and_then(|t| t.as_str())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string());
+            // Use MAX of internal timestamp and file mtime for accurate "time ago"
+            let last = crate::history::file_utils::get_max_timestamp(internal_last, &file_mtime_fallback);
+            // Get last message preview
+            let prev = json.get("messages")
+                .and_then(|m| m.as_array())
+                .and_then(|arr| arr.last())
+                .and_then(|msg| msg.get("content"))
+                .and_then(|c| c.as_str())
+                .map(|s| s.chars().take(100).collect::<String>())
+                .unwrap_or_else(|| "Gemini session".to_string());
+            (count, start, last, prev)
+        })
+        .unwrap_or_else(|| {
+            (0, file_mtime_fallback.clone(), file_mtime_fallback.clone(), "Gemini session".to_string())
+        });
+
+    ConversationFile {
+        session_id,
+        file_path: path.to_string_lossy().to_string(),
+        message_ids: HashSet::new(),
+        start_time,
+        last_time,
+        message_count,
+        last_message_preview: preview,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
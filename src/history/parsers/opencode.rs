@@ -16,9 +16,11 @@ pub fn parse_opencode_summaries(project_dir: &Path) -> Result<Vec<ConversationFi
     let entries = fs::read_dir(project_dir)
         .map_err(|e| format!("Failed to read project directory: {}", e))?;
 
-    // Get the base storage directory for message counting
-    let opencode_base = dirs::home_dir()
-        .map(|h| h.join(".local/share/opencode/storage/message"));
+    // Messages of the same root: <storage>/session/global -> <storage>/message
+    let opencode_base = project_dir
+        .parent()
+        .and_then(|session| session.parent())
+        .map(|storage| storage.join("message"));
 
     let mut conversation_files = Vec::new();
 
@@ -29,87 +31,90 @@ pub fn parse_opencode_summaries(project_dir: &Path) -> Result<Vec<ConversationFi
             continue;
         }
 
-        // OpenCode uses JSON format - create summary from file metadata
-        let session_id = crate::history::file_utils::extract_session_id(name, "ses_", ".json");
-        let full_session_id = format!("ses_{}", session_id);
-
-        // Count messages from storage/message/ses_*/ directory
-        let message_count = opencode_base.as_ref()
-            .and_then(|base| {
-                let msg_dir = base.join(&full_session_id);
-                if msg_dir.exists() {
-                    fs::read_dir(&msg_dir).ok().map(|entries| {
-                        entries.filter_map(|e| e.ok())
-                            .filter(|e| {
-                                let n = e.file_name().to_string_lossy().to_string();
-                                n.starts_with("msg_") && n.ends_with(".json")
-                            })
-                            .count()
-                    })
-                } else {
-                    None
-                }
-            })
-            .unwrap_or(0);
-
-        // Read timestamps from JSON file (time.created, time.updated are Unix milliseconds)
-        // Use file modification time as fallback
-        let file_mtime_fallback = crate::history::file_utils::get_file_mtime_fallback(&path);
-
-        let (start_time, last_time, title) = fs::read_to_string(&path)
-            .ok()
-            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
-            .map(|json| {
-                let created = json.get("time")
-                    .and_then(|t| t.get("created"))
-                    .and_then(|c| c.as_i64())
-                    .and_then(chrono::DateTime::from_timestamp_millis)
-                    .map(|dt| dt.to_rfc3339())
-                    .unwrap_or_else(|| file_mtime_fallback.clone());
-
-                let internal_updated = json.get("time")
-                    .and_then(|t| t.get("updated"))
-                    .and_then(|u| u.as_i64())
-                    .and_then(chrono::DateTime::from_timestamp_millis)
-                    .map(|dt| dt.to_rfc3339());
-
-                // Use MAX of internal timestamp and file mtime for accurate "time ago"
-                let updated = crate::history::file_utils::get_max_timestamp(internal_updated, &file_mtime_fallback);
-
-                let title = json.get("title")
-                    .and_then(|t| t.as_str())
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| format!("OpenCode session ({} messages)", message_count));
-
-                (created, updated, title)
-            })
-            .unwrap_or_else(|| (file_mtime_fallback.clone(), file_mtime_fallback, format!("OpenCode session ({} messages)", message_count)));
-
-        conversation_files.push(ConversationFile {
-            session_id,
-            file_path: path.to_string_lossy().to_string(),
-            message_ids: HashSet::new(),
-            start_time,
-            last_time,
-            message_count,
-            last_message_preview: title,
-        });
+        conversation_files.push(parse_opencode_session_metadata(&path, opencode_base.as_deref()));
     }
 
     Ok(conversation_files)
 }
 
-/// Load OpenCode messages from the separate message and parts directories
-/// Structure:
-/// - Messages metadata: ~/.local/share/opencode/storage/message/ses_{id}/msg_*.json
-/// - Message content: ~/.local/share/opencode/storage/part/msg_{message_id}/prt_*.json
-pub fn load_opencode_messages(session_id: &str) -> Result<Vec<Message>, String> {
-    let home_dir = dirs::home_dir()
-        .ok_or_else(|| "Could not find home directory".to_string())?;
+/// Summary of a single OpenCode ses_*.json file
+/// `message_base` is the storage/message directory holding the session's messages.
+pub fn parse_opencode_session_metadata(path: &Path, message_base: Option<&Path>) -> ConversationFile {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    // OpenCode uses JSON format - create summary from file metadata
+    let session_id = crate::history::file_utils::extract_session_id(name, "ses_", ".json");
+    let full_session_id = format!("ses_{}", session_id);
+
+    // Count messages from storage/message/ses_*/ directory
+    let message_count = message_base
+        .and_then(|base| {
+            let msg_dir = base.join(&full_session_id);
+            if msg_dir.exists() {
+                fs::read_dir(&msg_dir).ok().map(|entries| {
+                    entries.filter_map(|e| e.ok())
+                        .filter(|e| {
+                            let n = e.file_name().to_string_lossy().to_string();
+                            n.starts_with("msg_") && n.ends_with(".json")
+                        })
+                        .count()
+                })
+            } else {
+                None
+            }
+        })
+        .unwrap_or(0);
+
+    // Read timestamps from JSON file (time.created, time.updated are Unix milliseconds)
+    // Use file modification time as fallback
+    let file_mtime_fallback = crate::history::file_utils::get_file_mtime_fallback(path);
+
+    let (start_time, last_time, title) = fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .map(|json| {
+            let created = json.get("time")
+                .and_then(|t| t.get("created"))
+                .and_then(|c| c.as_i64())
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|dt| dt.to_rfc3339())
+                .unwrap_or_else(|| file_mtime_fallback.clone());
+
+            let internal_updated = json.get("time")
+                .and_then(|t| t.get("updated"))
+                .and_then(|u| u.as_i64())
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|dt| dt.to_rfc3339());
+
+            // Use MAX of internal timestamp and file mtime for accurate "time ago"
+            let updated = crate::history::file_utils::get_max_timestamp(internal_updated, &file_mtime_fallback);
+
+            let title = json.get("title")
+                .and_then(|t| t.as_str())
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| format!("OpenCode session ({} messages)", message_count));
 
-    let storage_base = home_dir.join(".local/share/opencode/storage");
+            (created, updated, title)
+        })
+        .unwrap_or_else(|| (file_mtime_fallback.clone(), file_mtime_fallback, format!("OpenCode session ({} messages)", message_count)));
+
+    ConversationFile {
+        session_id,
+        file_path: path.to_string_lossy().to_string(),
+        message_ids: HashSet::new(),
+        start_time,
+        last_time,
+        message_count,
+        last_message_preview: title,
+    }
+}
 
-    // OpenCode message directory: ~/.local/share/opencode/storage/message/ses_{id}/
+/// Load OpenCode messages from the separate message and parts directories
+/// `storage_base` is the `storage` directory of the root holding the session file.
+/// Structure:
+/// - Messages metadata: <storage>/message/ses_{id}/msg_*.json
+/// - Message content: <storage>/part/msg_{message_id}/prt_*.json
+pub fn load_opencode_messages(storage_base: &Path, session_id: &str) -> Result<Vec<Message>, String> {
+    // OpenCode message directory: <storage>/message/ses_{id}/
     // Note: session_id comes WITHOUT the "ses_" prefix, so we add it
     let message_dir = storage_base
         .join("message")
@@ -217,6 +222,11 @@ pub fn load_opencode_messages(session_id: &str) -> Result<Vec<Message>, String>
     Ok(messages)
 }
 
+/// `storage` directory of a session file (`<storage>/session/global/ses_*.json`)
+pub fn storage_dir_of(session_file: &Path) -> Option<&Path> {
+    session_file.parent()?.parent()?.parent()
+}
+
 /// Load message content from parts directory.
 ///
 /// Parts are stored in: `~/.local/share/opencode/storage/part/msg_{message_id}/prt_*.json`
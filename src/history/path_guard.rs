@@ -0,0 +1,251 @@
+//! Path guard for destructive file operations
+//!
+//! Every path built from client input (encoded project names, session IDs)
+//! must go through this module before a file is moved or deleted:
+//! - each segment must be a single plain file name (no `..`, `.`, separators,
+//!   absolute paths or NUL bytes)
+//! - the target is canonicalized, so symlinks pointing outside are caught
+//! - the canonical target must lie inside the canonical provider root
+//! - roots marked `read_only` in the config refuse any modification
+//!
+//! Denied attempts are logged with `tracing::warn!`.
+
+use std::path::{Component, Path, PathBuf};
+use thiserror::Error;
+
+use crate::config::HistoryRoot;
+use crate::domain::models::AiTool;
+use super::path_utils::get_history_roots;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PathGuardError {
+    #[error("Invalid path segment: {0:?}")]
+    InvalidSegment(String),
+
+    #[error("Path escapes its root: {0}")]
+    OutsideRoot(String),
+
+    #[error("Path not found: {0}")]
+    NotFound(String),
+
+    #[error("Root directory unavailable: {0}")]
+    RootUnavailable(String),
+
+    #[error("History root '{0}' is read-only")]
+    ReadOnlyRoot(String),
+}
+
+impl PathGuardError {
+    /// Whether the request was refused (as opposed to the target simply missing)
+    pub fn is_denied(&self) -> bool {
+        matches!(self, Self::InvalidSegment(_) | Self::OutsideRoot(_) | Self::ReadOnlyRoot(_))
+    }
+}
+
+fn deny(error: PathGuardError) -> PathGuardError {
+    tracing::warn!("Path guard denied access: {}", error);
+    error
+}
+
+/// Check that a client-supplied value is a single plain path segment
+pub fn validate_segment(segment: &str) -> Result<(), PathGuardError> {
+    let invalid = segment.is_empty()
+        || segment.contains(['/', '\\', '\0'])
+        || !matches!(
+            Path::new(segment).components().collect::<Vec<_>>().as_slice(),
+            [Component::Normal(_)]
+        );
+
+    if invalid {
+        return Err(deny(PathGuardError::InvalidSegment(segment.to_string())));
+    }
+    Ok(())
+}
+
+/// Resolve `root/segments...` to an existing canonical path inside `root`
+pub fn resolve_existing(root: &Path, segments: &[&str]) -> Result<PathBuf, PathGuardError> {
+    for segment in segments {
+        validate_segment(segment)?;
+    }
+
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| PathGuardError::RootUnavailable(format!("{}: {}", root.display(), e)))?;
+
+    let target = segments.iter().fold(root.to_path_buf(), |path, segment| path.join(segment));
+    if !target.exists() && target.symlink_metadata().is_err() {
+        return Err(PathGuardError::NotFound(target.display().to_string()));
+    }
+
+    let canonical_target = target
+        .canonicalize()
+        .map_err(|_| deny(PathGuardError::OutsideRoot(target.display().to_string())))?;
+
+    if canonical_target == canonical_root || !canonical_target.starts_with(&canonical_root) {
+        return Err(deny(PathGuardError::OutsideRoot(format!(
+            "{} -> {}",
+            target.display(),
+            canonical_target.display()
+        ))));
+    }
+
+    Ok(canonical_target)
+}
+
+/// Resolve `segments` inside every history root of a provider (see [`get_history_roots`])
+/// Roots where the target does not exist (or that are missing) are skipped.
+pub fn resolve_in_history_roots(ai_tool: &AiTool, segments: &[&str]) -> Result<Vec<(HistoryRoot, PathBuf)>, PathGuardError> {
+    let mut found = Vec::new();
+    for root in get_history_roots(ai_tool) {
+        match resolve_existing(&root.projects_dir(), segments) {
+            Ok(path) => found.push((root, path)),
+            Err(PathGuardError::NotFound(_) | PathGuardError::RootUnavailable(_)) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(found)
+}
+
+/// Refuse modifications inside a read-only history root
+pub fn ensure_writable(root: &HistoryRoot) -> Result<(), PathGuardError> {
+    if root.read_only {
+        return Err(deny(PathGuardError::ReadOnlyRoot(root.label.clone())));
+    }
+    Ok(())
+}
+
+/// Check that an absolute path (which may not exist yet) stays inside `root`
+/// Used before writing back to a location, e.g. when restoring from the trash.
+pub fn ensure_within(root: &Path, path: &Path) -> Result<PathBuf, PathGuardError> {
+    if !path.is_absolute() || path.components().any(|c| matches!(c, Component::ParentDir | Component::CurDir)) {
+        return Err(deny(PathGuardError::InvalidSegment(path.display().to_string())));
+    }
+
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| PathGuardError::RootUnavailable(format!("{}: {}", root.display(), e)))?;
+
+    // Canonicalize the deepest existing ancestor, then re-append the missing tail
+    let mut existing = path.to_path_buf();
+    let mut tail = Vec::new();
+    while !existing.exists() {
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                tail.push(name.to_os_string());
+                existing = parent.to_path_buf();
+            }
+            _ => return Err(deny(PathGuardError::OutsideRoot(path.display().to_string()))),
+        }
+    }
+
+    let mut resolved = existing
+        .canonicalize()
+        .map_err(|_| deny(PathGuardError::OutsideRoot(path.display().to_string())))?;
+    for name in tail.into_iter().rev() {
+        resolved.push(name);
+    }
+
+    if resolved == canonical_root || !resolved.starts_with(&canonical_root) {
+        return Err(deny(PathGuardError::OutsideRoot(format!("{} -> {}", path.display(), resolved.display()))));
+    }
+
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("path-guard-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_rejects_traversal_segments() {
+        let payloads = [
+            "", ".", "..", "../..", "../etc", "a/../../b", "/etc/passwd",
+            "..\\..\\windows", "project/../../..", "foo\0bar", "./project",
+        ];
+        for payload in payloads {
+            assert!(
+                matches!(validate_segment(payload), Err(PathGuardError::InvalidSegment(_))),
+                "payload should be rejected: {:?}",
+                payload
+            );
+        }
+    }
+
+    #[test]
+    fn test_accepts_plain_names() {
+        for name in ["-Users-me-project", "abc-123.jsonl", "ses_42.json", "...jsonl", "%2e%2e"] {
+            assert!(validate_segment(name).is_ok(), "should accept {:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_resolve_existing_inside_root() {
+        let root = temp_root("inside");
+        fs::create_dir_all(root.join("project")).unwrap();
+        fs::write(root.join("project").join("abc.jsonl"), "").unwrap();
+
+        let resolved = resolve_existing(&root, &["project", "abc.jsonl"]).unwrap();
+        assert!(resolved.starts_with(root.canonicalize().unwrap()));
+
+        assert!(matches!(
+            resolve_existing(&root, &["project", "missing.jsonl"]),
+            Err(PathGuardError::NotFound(_))
+        ));
+        assert!(matches!(
+            resolve_existing(&root, &["..", "etc"]),
+            Err(PathGuardError::InvalidSegment(_))
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_root_itself_is_not_a_target() {
+        let root = temp_root("self");
+        assert!(resolve_existing(&root, &[]).unwrap_err().is_denied());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rejects_symlink_escape() {
+        let root = temp_root("symlink");
+        let outside = temp_root("outside");
+        std::os::unix::fs::symlink(&outside, root.join("evil")).unwrap();
+
+        let err = resolve_existing(&root, &["evil"]).unwrap_err();
+        assert!(matches!(err, PathGuardError::OutsideRoot(_)));
+
+        fs::remove_dir_all(&root).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+
+    #[test]
+    fn test_read_only_root_is_denied() {
+        let root = HistoryRoot {
+            ai_tool: AiTool::ClaudeCode,
+            label: "laptop".to_string(),
+            path: PathBuf::from("/backup"),
+            read_only: true,
+        };
+        assert!(ensure_writable(&root).unwrap_err().is_denied());
+        assert!(ensure_writable(&HistoryRoot { read_only: false, ..root }).is_ok());
+    }
+
+    #[test]
+    fn test_ensure_within() {
+        let root = temp_root("within");
+        assert!(ensure_within(&root, &root.join("new-project").join("a.jsonl")).is_ok());
+        assert!(ensure_within(&root, &root.join("..").join("x")).unwrap_err().is_denied());
+        assert!(ensure_within(&root, Path::new("relative/path")).unwrap_err().is_denied());
+        assert!(ensure_within(&root, &std::env::temp_dir().join("elsewhere")).unwrap_err().is_denied());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,399 @@
+//! Persistent registry of known project paths
+//!
+//! Gemini names its project directories after the SHA-256 of the project path,
+//! which cannot be reversed. Instead we collect every project path we can learn
+//! about and resolve hashes by lookup:
+//! - `cwd` fields of Claude sessions
+//! - `directory` fields of OpenCode sessions
+//! - project keys of `~/.claude.json`
+//! - paths added by the user when mapping an unresolved hash to a directory
+//!
+//! The registry is stored in `<data_dir>/path_registry.json` and refreshed
+//! in the background when the sync service starts. Afterwards the sync
+//! watcher adds the paths of new Claude and OpenCode sessions, and a hash that
+//! does not resolve triggers a full refresh (at most once per minute).
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::AiTool;
+pub use crate::domain::models::is_gemini_hash;
+use super::path_utils::{compute_sha256, get_history_roots};
+
+/// How many lines of a Claude session are read to find its `cwd`
+const CWD_SCAN_LINES: usize = 50;
+
+/// Minimum delay between two refreshes triggered by unresolved hashes
+const MISS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// When the registry was last refreshed from every source
+static LAST_REFRESH: Lazy<Mutex<Option<Instant>>> = Lazy::new(|| Mutex::new(None));
+
+static REGISTRY: Lazy<RwLock<PathRegistry>> = Lazy::new(|| {
+    let path = registry_file();
+    let registry = PathRegistry::load(&path).unwrap_or_else(|e| {
+        tracing::warn!("Failed to load path registry: {}", e);
+        PathRegistry::default()
+    });
+    RwLock::new(registry)
+});
+
+/// Where a known path was learned from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PathSource {
+    ClaudeSession,
+    OpenCodeSession,
+    ClaudeConfig,
+    User,
+}
+
+/// Known project paths, indexed by their SHA-256
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PathRegistry {
+    #[serde(default)]
+    paths: BTreeMap<String, PathSource>,
+    /// Manual hash → path mappings (the path may not hash to the key, e.g. a moved project)
+    #[serde(default)]
+    mappings: BTreeMap<String, String>,
+    #[serde(skip)]
+    by_hash: HashMap<String, String>,
+}
+
+impl PathRegistry {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut registry: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        registry.by_hash = registry
+            .paths
+            .keys()
+            .map(|p| (compute_sha256(p), p.clone()))
+            .collect();
+        Ok(registry)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize path registry: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Add a project path; returns true if it was not known yet
+    /// A user-added path keeps its source when seen again elsewhere.
+    pub fn add(&mut self, path: &str, source: PathSource) -> bool {
+        let Some(path) = normalize_path(path) else {
+            return false;
+        };
+        match self.paths.get(&path) {
+            Some(_) if source != PathSource::User => false,
+            Some(existing) if *existing == source => false,
+            _ => {
+                self.by_hash.insert(compute_sha256(&path), path.clone());
+                self.paths.insert(path, source).is_none()
+            }
+        }
+    }
+
+    /// Map a Gemini hash to a directory chosen by the user
+    pub fn map_hash(&mut self, hash: &str, path: &str) -> Result<(), String> {
+        if !is_gemini_hash(hash) {
+            return Err(format!("Invalid Gemini project hash: {}", hash));
+        }
+        let path = normalize_path(path).ok_or_else(|| format!("Path must be absolute: {}", path))?;
+
+        if compute_sha256(&path) != hash {
+            tracing::info!("Manual mapping {} -> {} does not match the path hash", hash, path);
+        }
+        self.add(&path, PathSource::User);
+        self.mappings.insert(hash.to_string(), path);
+        Ok(())
+    }
+
+    /// Resolve a SHA-256 project hash (manual mappings first)
+    pub fn resolve_hash(&self, hash: &str) -> Option<&str> {
+        self.mappings
+            .get(hash)
+            .or_else(|| self.by_hash.get(hash))
+            .map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+}
+
+/// Location of the registry file
+pub fn registry_file() -> PathBuf {
+    crate::config::config().paths.data_dir.join("path_registry.json")
+}
+
+/// Resolve a Gemini project hash to its directory, if known
+/// An unknown hash refreshes the registry first, unless it was refreshed recently.
+pub fn resolve_gemini_hash(hash: &str) -> Option<String> {
+    if let Some(path) = lookup_hash(hash) {
+        return Some(path);
+    }
+    if !is_gemini_hash(hash) || !refresh_due() {
+        return None;
+    }
+    if let Err(e) = refresh_registry() {
+        tracing::warn!("Failed to refresh path registry: {}", e);
+    }
+    lookup_hash(hash)
+}
+
+fn lookup_hash(hash: &str) -> Option<String> {
+    let registry = REGISTRY.read().ok()?;
+    registry.resolve_hash(hash).map(str::to_string)
+}
+
+/// Whether a refresh triggered by an unresolved hash may run now
+fn refresh_due() -> bool {
+    LAST_REFRESH
+        .lock()
+        .map(|last| last.is_none_or(|at| at.elapsed() >= MISS_REFRESH_INTERVAL))
+        .unwrap_or(false)
+}
+
+/// Map a Gemini project hash to a directory and persist it
+pub fn map_gemini_hash(hash: &str, path: &str) -> Result<(), String> {
+    update(|registry| registry.map_hash(hash, path))
+}
+
+/// Collect paths from every source and persist the registry
+/// Returns the number of newly learned paths.
+pub fn refresh_registry() -> Result<usize, String> {
+    let mut found: Vec<(String, PathSource)> = Vec::new();
+    found.extend(claude_session_paths().into_iter().map(|p| (p, PathSource::ClaudeSession)));
+    found.extend(opencode_session_paths().into_iter().map(|p| (p, PathSource::OpenCodeSession)));
+    found.extend(claude_config_paths().into_iter().map(|p| (p, PathSource::ClaudeConfig)));
+
+    if let Ok(mut last) = LAST_REFRESH.lock() {
+        *last = Some(Instant::now());
+    }
+
+    let mut added = 0;
+    update(|registry| {
+        added = found.iter().filter(|(path, source)| registry.add(path, *source)).count();
+        Ok(())
+    })?;
+
+    tracing::info!("Path registry refreshed: {} new paths", added);
+    Ok(added)
+}
+
+/// Learn the project paths of changed Claude and OpenCode session files (sync watcher)
+/// The registry file is only rewritten when a path is new.
+pub fn learn_session_paths(files: &[(AiTool, PathBuf)]) -> Result<usize, String> {
+    let found: Vec<(String, PathSource)> = files
+        .iter()
+        .filter_map(|(ai_tool, file)| match ai_tool {
+            AiTool::ClaudeCode => session_cwd(file).map(|p| (p, PathSource::ClaudeSession)),
+            AiTool::OpenCode => opencode_session_directory(file).map(|p| (p, PathSource::OpenCodeSession)),
+            AiTool::Gemini => None,
+        })
+        .collect();
+
+    let known = |registry: &PathRegistry, path: &str| {
+        normalize_path(path).is_none_or(|path| registry.paths.contains_key(&path))
+    };
+    {
+        let registry = REGISTRY.read().map_err(|_| "Path registry lock poisoned".to_string())?;
+        if found.iter().all(|(path, _)| known(&registry, path)) {
+            return Ok(0);
+        }
+    }
+
+    let mut added = 0;
+    update(|registry| {
+        added = found.iter().filter(|(path, source)| registry.add(path, *source)).count();
+        Ok(())
+    })?;
+    tracing::info!("Path registry: {} new paths from changed sessions", added);
+    Ok(added)
+}
+
+fn update(apply: impl FnOnce(&mut PathRegistry) -> Result<(), String>) -> Result<(), String> {
+    let mut registry = REGISTRY
+        .write()
+        .map_err(|_| "Path registry lock poisoned".to_string())?;
+    apply(&mut registry)?;
+    registry.save(&registry_file())
+}
+
+/// Absolute path without trailing separators (Gemini hashes the path as given)
+fn normalize_path(path: &str) -> Option<String> {
+    let path = path.trim();
+    if !path.starts_with('/') {
+        return None;
+    }
+    let trimmed = path.trim_end_matches('/');
+    Some(if trimmed.is_empty() { "/".to_string() } else { trimmed.to_string() })
+}
+
+/// `cwd` of every Claude session, in every Claude root
+fn claude_session_paths() -> HashSet<String> {
+    let mut paths = HashSet::new();
+    for root in get_history_roots(&AiTool::ClaudeCode) {
+        let Ok(projects) = fs::read_dir(root.projects_dir()) else {
+            continue;
+        };
+        for project in projects.flatten() {
+            let Ok(files) = fs::read_dir(project.path()) else {
+                continue;
+            };
+            let sessions = files
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"));
+            paths.extend(sessions.filter_map(|path| session_cwd(&path)));
+        }
+    }
+    paths
+}
+
+/// First `cwd` field found at the start of a Claude session file
+pub(crate) fn session_cwd(path: &Path) -> Option<String> {
+    let file = fs::File::open(path).ok()?;
+    BufReader::new(file)
+        .lines()
+        .take(CWD_SCAN_LINES)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+        .find_map(|json| json.get("cwd").and_then(|v| v.as_str()).map(str::to_string))
+}
+
+/// `directory` of every OpenCode session, in every OpenCode root
+fn opencode_session_paths() -> HashSet<String> {
+    let mut paths = HashSet::new();
+    for root in get_history_roots(&AiTool::OpenCode) {
+        let sessions_dir = root.path.join("storage").join("session");
+        let Ok(projects) = fs::read_dir(&sessions_dir) else {
+            continue;
+        };
+        for project in projects.flatten() {
+            let Ok(files) = fs::read_dir(project.path()) else {
+                continue;
+            };
+            for file in files.flatten() {
+                let name = file.file_name().to_string_lossy().to_string();
+                if !name.starts_with("ses_") || !name.ends_with(".json") {
+                    continue;
+                }
+                paths.extend(opencode_session_directory(&file.path()));
+            }
+        }
+    }
+    paths
+}
+
+/// `directory` field of an OpenCode session file
+fn opencode_session_directory(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    let json = serde_json::from_str::<serde_json::Value>(&content).ok()?;
+    json.get("directory").and_then(|v| v.as_str()).map(str::to_string)
+}
+
+/// Project keys of `~/.claude.json`
+fn claude_config_paths() -> Vec<String> {
+    let Ok(home) = std::env::var("HOME") else {
+        return Vec::new();
+    };
+    fs::read_to_string(PathBuf::from(home).join(".claude.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .map(|config| config_project_keys(&config))
+        .unwrap_or_default()
+}
+
+fn config_project_keys(config: &serde_json::Value) -> Vec<String> {
+    config
+        .get("projects")
+        .and_then(|projects| projects.as_object())
+        .map(|projects| projects.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_by_hash() {
+        let mut registry = PathRegistry::default();
+        assert!(registry.add("/Users/me/code/app/", PathSource::ClaudeSession));
+        assert!(!registry.add("/Users/me/code/app", PathSource::OpenCodeSession));
+        assert!(!registry.add("relative/path", PathSource::User));
+
+        let hash = compute_sha256("/Users/me/code/app");
+        assert_eq!(registry.resolve_hash(&hash), Some("/Users/me/code/app"));
+        assert_eq!(registry.resolve_hash(&compute_sha256("/other")), None);
+    }
+
+    #[test]
+    fn test_manual_mapping() {
+        let mut registry = PathRegistry::default();
+        let hash = compute_sha256("/old/location/app");
+
+        assert!(registry.map_hash("not-a-hash", "/new/app").is_err());
+        assert!(registry.map_hash(&hash, "new/app").is_err());
+
+        registry.map_hash(&hash, "/new/location/app").unwrap();
+        assert_eq!(registry.resolve_hash(&hash), Some("/new/location/app"));
+        assert_eq!(registry.paths.get("/new/location/app"), Some(&PathSource::User));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("path-registry-{}", uuid::Uuid::new_v4()));
+        let file = dir.join("path_registry.json");
+
+        let mut registry = PathRegistry::default();
+        registry.add("/srv/project", PathSource::ClaudeConfig);
+        registry.save(&file).unwrap();
+
+        let loaded = PathRegistry::load(&file).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.resolve_hash(&compute_sha256("/srv/project")), Some("/srv/project"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sources() {
+        let config = serde_json::json!({ "projects": { "/a": {}, "/b/c": {} } });
+        let mut keys = config_project_keys(&config);
+        keys.sort();
+        assert_eq!(keys, vec!["/a", "/b/c"]);
+
+        let file = std::env::temp_dir().join(format!("registry-cwd-{}.jsonl", uuid::Uuid::new_v4()));
+        fs::write(&file, "{\"type\":\"summary\"}\n{\"type\":\"user\",\"cwd\":\"/work/app\"}\n").unwrap();
+        assert_eq!(session_cwd(&file).as_deref(), Some("/work/app"));
+        fs::remove_file(&file).ok();
+
+        let file = std::env::temp_dir().join(format!("ses_registry-{}.json", uuid::Uuid::new_v4()));
+        fs::write(&file, "{\"id\":\"ses_1\",\"directory\":\"/work/api\"}").unwrap();
+        assert_eq!(opencode_session_directory(&file).as_deref(), Some("/work/api"));
+        fs::remove_file(&file).ok();
+    }
+}
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use crate::config::HistoryRoot;
+
 /// Encode a filesystem path to Claude's dash-separated format
 /// Example: "/Users/toto/project" -> "-Users-toto-project"
 /// This matches the format used by Claude CLI in ~/.claude/projects/
@@ -100,9 +102,19 @@ use sha2::{Sha256, Digest};
 
 /// Decode Gemini project hash to original path
 /// Gemini uses SHA256(path) as directory name
-/// We try common paths to find a match
+/// Looks the hash up in the path registry first, then tries common paths
 /// Returns (decoded_path, project_name) or fallback to hash
 pub fn decode_gemini_hash(hash: &str) -> (String, String) {
+    // Known paths (sessions of other providers, ~/.claude.json, manual mappings)
+    if let Some(path) = super::path_registry::resolve_gemini_hash(hash) {
+        let name = std::path::Path::new(&path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Gemini")
+            .to_string();
+        return (path, name);
+    }
+
     let home = std::env::var("HOME").unwrap_or_default();
 
     // Common base directories to search
@@ -157,36 +169,79 @@ pub fn decode_gemini_hash(hash: &str) -> (String, String) {
 }
 
 /// Compute SHA256 hash of a string (for Gemini path matching)
-fn compute_sha256(input: &str) -> String {
+pub(crate) fn compute_sha256(input: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(input.as_bytes());
     format!("{:x}", hasher.finalize())
 }
 
 /// Get the base storage directory path for a specific AI tool
-/// Returns the root directory where projects/sessions are stored:
+/// Returns the root directory where projects/sessions are stored (see `[providers]` in the config):
 /// - Claude: ~/.claude/projects/
-/// - OpenCode: ~/.local/share/opencode/storage/session/
+/// - OpenCode: ~/.local/share/opencode/storage/session/global/
 /// - Gemini: ~/.gemini/tmp/ (contains {hash}/chats/ subdirs)
 pub fn get_projects_dir(ai_tool: &AiTool) -> Result<PathBuf, String> {
-    let home = std::env::var("HOME")
-        .map_err(|_| "HOME environment variable not set".to_string())?;
+    let config = crate::config::config();
 
     let path = match ai_tool {
-        AiTool::ClaudeCode => PathBuf::from(&home).join(".claude").join("projects"),
-        AiTool::OpenCode => PathBuf::from(&home)
-            .join(".local")
-            .join("share")
-            .join("opencode")
-            .join("storage")
-            .join("session")
-            .join("global"),
-        AiTool::Gemini => PathBuf::from(&home).join(".gemini").join("tmp"),
+        AiTool::ClaudeCode => config.providers.claude_dir.clone(),
+        AiTool::OpenCode => config.opencode_sessions_dir(),
+        AiTool::Gemini => config.providers.gemini_dir.clone(),
     };
 
     Ok(path)
 }
 
+/// Every history root of a provider (default root first, see `[[providers.roots]]`)
+pub fn get_history_roots(ai_tool: &AiTool) -> Vec<HistoryRoot> {
+    crate::config::config().history_roots(ai_tool)
+}
+
+/// Directory of a project inside a root
+/// OpenCode keeps every session in the "global" directory itself.
+pub fn project_dir_in_root(root: &HistoryRoot, encoded_name: &str) -> PathBuf {
+    let projects_dir = root.projects_dir();
+    if matches!(root.ai_tool, AiTool::OpenCode) && encoded_name == "global" {
+        projects_dir
+    } else {
+        projects_dir.join(encoded_name)
+    }
+}
+
+/// Existing directories of a project across all roots of a provider
+pub fn find_project_dirs(ai_tool: &AiTool, encoded_name: &str) -> Vec<(HistoryRoot, PathBuf)> {
+    get_history_roots(ai_tool)
+        .into_iter()
+        .map(|root| {
+            let dir = project_dir_in_root(&root, encoded_name);
+            (root, dir)
+        })
+        .filter(|(_, dir)| dir.is_dir())
+        .collect()
+}
+
+/// Session file path relative to its project directory
+pub fn session_file_in_project(ai_tool: &AiTool, project_dir: &std::path::Path, session_id: &str) -> PathBuf {
+    match ai_tool {
+        AiTool::ClaudeCode => project_dir.join(format!("{}.jsonl", session_id)),
+        AiTool::OpenCode => project_dir.join(format!("ses_{}.json", session_id)),
+        AiTool::Gemini => project_dir.join("chats").join(format!("session-{}.json", session_id)),
+    }
+}
+
+/// Find a session file across all roots of a provider
+/// When the same session exists in several roots, the most recently modified copy wins.
+pub fn find_session_file(ai_tool: &AiTool, encoded_name: &str, session_id: &str) -> Option<(HistoryRoot, PathBuf)> {
+    find_project_dirs(ai_tool, encoded_name)
+        .into_iter()
+        .map(|(root, dir)| {
+            let file = session_file_in_project(ai_tool, &dir, session_id);
+            (root, file)
+        })
+        .filter(|(_, file)| file.is_file())
+        .max_by_key(|(_, file)| std::fs::metadata(file).and_then(|m| m.modified()).ok())
+}
+
 /// Get the Claude projects directory path (legacy function)
 /// Returns ~/.claude/projects/
 pub fn get_claude_projects_dir() -> Result<PathBuf, String> {
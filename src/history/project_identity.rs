@@ -0,0 +1,132 @@
+//! Lossless project identity
+//!
+//! Encoded project names (`-Users-me-my-app`) cannot be decoded reliably: every
+//! `/`, `.` and `_` became a dash. The real path is taken from what the CLIs
+//! recorded instead, and cached per project:
+//! - Claude: the `cwd` field of the project's session files
+//! - OpenCode: the `directory` field of its sessions
+//! - Gemini: the path registry (see `path_registry`)
+//!
+//! A cached path is dropped when it no longer exists (project moved or
+//! deleted) and when the sync watcher sees the project's sessions change.
+
+use std::fs;
+use std::path::PathBuf;
+
+use dashmap::DashMap;
+use once_cell::sync::Lazy;
+
+use crate::domain::models::AiTool;
+use super::parser::extract_opencode_working_directory;
+use super::path_registry::{resolve_gemini_hash, session_cwd};
+use super::path_utils::{encode_project_path, find_project_dirs};
+
+/// How many of the most recent Claude sessions are read to find a `cwd`
+const MAX_SESSIONS_SCANNED: usize = 20;
+
+/// Resolved paths by (tool, encoded name); unresolved projects are not cached
+static PROJECT_PATHS: Lazy<DashMap<(AiTool, String), String>> = Lazy::new(DashMap::new);
+
+const AI_TOOLS: [AiTool; 3] = [AiTool::ClaudeCode, AiTool::OpenCode, AiTool::Gemini];
+
+/// Real filesystem path of a project, if it can be determined
+pub fn resolve_project_path(ai_tool: &AiTool, encoded_name: &str) -> Option<String> {
+    let key = (ai_tool.clone(), encoded_name.to_string());
+    if let Some(path) = PROJECT_PATHS.get(&key).map(|path| path.clone()) {
+        if std::path::Path::new(&path).exists() {
+            return Some(path);
+        }
+        PROJECT_PATHS.remove(&key);
+    }
+
+    let path = match ai_tool {
+        AiTool::ClaudeCode => claude_project_cwd(encoded_name),
+        AiTool::OpenCode => find_project_dirs(ai_tool, encoded_name)
+            .iter()
+            .find_map(|(_, dir)| extract_opencode_working_directory(dir)),
+        AiTool::Gemini => resolve_gemini_hash(encoded_name),
+    }?;
+
+    tracing::debug!("Resolved {:?} project {} -> {}", ai_tool, encoded_name, path);
+    PROJECT_PATHS.insert(key, path.clone());
+    Some(path)
+}
+
+/// Forget the cached path of a project (its sessions changed)
+pub fn invalidate_project_path(ai_tool: &AiTool, encoded_name: &str) {
+    PROJECT_PATHS.remove(&(ai_tool.clone(), encoded_name.to_string()));
+}
+
+/// Resolve a project without knowing its provider (first match wins)
+pub fn resolve_any_project_path(encoded_name: &str) -> Option<(AiTool, String)> {
+    AI_TOOLS
+        .iter()
+        .find_map(|tool| resolve_project_path(tool, encoded_name).map(|path| (tool.clone(), path)))
+}
+
+/// `cwd` of the most recent Claude sessions of a project
+/// A `cwd` that encodes back to the project name is preferred: sessions may
+/// record a subdirectory after a `cd`.
+fn claude_project_cwd(encoded_name: &str) -> Option<String> {
+    let mut sessions: Vec<(std::time::SystemTime, PathBuf)> = find_project_dirs(&AiTool::ClaudeCode, encoded_name)
+        .into_iter()
+        .filter_map(|(_, dir)| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .collect();
+    sessions.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    let cwds: Vec<String> = sessions
+        .iter()
+        .take(MAX_SESSIONS_SCANNED)
+        .filter_map(|(_, path)| session_cwd(path))
+        .collect();
+
+    pick_project_cwd(encoded_name, cwds)
+}
+
+fn pick_project_cwd(encoded_name: &str, cwds: Vec<String>) -> Option<String> {
+    cwds.iter()
+        .find(|cwd| encode_project_path(cwd) == encoded_name)
+        .cloned()
+        .or_else(|| cwds.into_iter().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_project_cwd_prefers_matching_encoding() {
+        let cwds = vec!["/Users/me/my-app/src".to_string(), "/Users/me/my-app".to_string()];
+        assert_eq!(
+            pick_project_cwd("-Users-me-my-app", cwds).as_deref(),
+            Some("/Users/me/my-app")
+        );
+
+        // The recorded cwd keeps the underscore the encoding turned into a dash
+        let cwds = vec!["/Users/me/my_app".to_string()];
+        assert_eq!(pick_project_cwd("-Users-me-my-app", cwds).as_deref(), Some("/Users/me/my_app"));
+
+        assert_eq!(pick_project_cwd("-Users-me-my-app", Vec::new()), None);
+    }
+
+    #[test]
+    fn test_cached_path_dropped_when_gone() {
+        let key = (AiTool::Gemini, "not-a-gemini-hash".to_string());
+        PROJECT_PATHS.insert(key.clone(), "/nonexistent/moved-project".to_string());
+        assert_eq!(resolve_project_path(&key.0, &key.1), None);
+        assert!(!PROJECT_PATHS.contains_key(&key));
+
+        let dir = std::env::temp_dir().to_string_lossy().to_string();
+        PROJECT_PATHS.insert(key.clone(), dir.clone());
+        assert_eq!(resolve_project_path(&key.0, &key.1), Some(dir));
+        invalidate_project_path(&key.0, &key.1);
+        assert!(!PROJECT_PATHS.contains_key(&key));
+    }
+}
@@ -61,7 +61,7 @@ pub type ClaudeJsonlEntry = RawHistoryLine;
 
 /// Intermediate structure for grouping algorithm
 /// Contains parsed file metadata and message IDs for deduplication
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConversationFile {
     pub session_id: String,
     pub file_path: String,
@@ -135,6 +135,115 @@ pub struct Message {
     pub timestamp: Option<String>,
 }
 
+/// Domain message for the viewer: text blocks joined, missing timestamps set to now
+/// Tool calls and results are dropped; see [`viewer_messages`] to keep them.
+impl From<Message> for crate::domain::models::Message {
+    fn from(m: Message) -> Self {
+        let timestamp = parse_timestamp(m.timestamp.as_deref()).unwrap_or_else(Utc::now);
+
+        let content = m
+            .content
+            .iter()
+            .filter_map(|c| match c {
+                ContentBlock::Text { text } => Some(text.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        text_message(&m.role, content, timestamp)
+    }
+}
+
+/// Domain messages with tool activity (`hub show`, `hub search`, `hub tui`)
+/// Text blocks are joined per message and every tool call becomes a
+/// `Message::Tool`, completed by the next tool result (results carry no call
+/// ID). A message without timestamp takes the previous one's, or `fallback`.
+pub fn viewer_messages(messages: &[Message], fallback: DateTime<Utc>) -> Vec<crate::domain::models::Message> {
+    use crate::domain::models::Message as DomainMessage;
+
+    let mut viewer = Vec::new();
+    let mut pending_calls = std::collections::VecDeque::new();
+    let mut last_timestamp = fallback;
+
+    for message in messages {
+        let timestamp = parse_timestamp(message.timestamp.as_deref()).unwrap_or(last_timestamp);
+        last_timestamp = timestamp;
+
+        let mut texts: Vec<&str> = Vec::new();
+        let flush = |texts: &mut Vec<&str>, viewer: &mut Vec<DomainMessage>| {
+            if !texts.is_empty() {
+                viewer.push(text_message(&message.role, texts.join("\n"), timestamp));
+                texts.clear();
+            }
+        };
+
+        for block in &message.content {
+            match block {
+                ContentBlock::Text { text } => texts.push(text),
+                ContentBlock::ToolUse { name, input, .. } => {
+                    flush(&mut texts, &mut viewer);
+                    pending_calls.push_back(viewer.len());
+                    viewer.push(DomainMessage::Tool {
+                        name: name.clone(),
+                        input: input.clone(),
+                        output: None,
+                        timestamp,
+                        tool_call_id: None,
+                        metadata: None,
+                    });
+                }
+                ContentBlock::ToolResult { content, .. } => {
+                    flush(&mut texts, &mut viewer);
+                    match pending_calls.pop_front().and_then(|index| viewer.get_mut(index)) {
+                        Some(DomainMessage::Tool { output, .. }) => *output = Some(content.clone()),
+                        _ => viewer.push(DomainMessage::System {
+                            content: content.clone(),
+                            timestamp,
+                            level: None,
+                            metadata: None,
+                        }),
+                    }
+                }
+            }
+        }
+        flush(&mut texts, &mut viewer);
+    }
+    viewer
+}
+
+fn parse_timestamp(timestamp: Option<&str>) -> Option<DateTime<Utc>> {
+    timestamp
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn text_message(role: &str, content: String, timestamp: DateTime<Utc>) -> crate::domain::models::Message {
+    use crate::domain::models::Message as DomainMessage;
+
+    match role {
+        "user" | "human" => DomainMessage::User {
+            content,
+            timestamp,
+            images: vec![],
+            metadata: None,
+        },
+        "assistant" => DomainMessage::Assistant {
+            content,
+            timestamp,
+            model: None,
+            images: vec![],
+            metadata: None,
+        },
+        _ => DomainMessage::System {
+            content,
+            timestamp,
+            level: None,
+            metadata: None,
+        },
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ContentBlock {
@@ -171,4 +280,8 @@ pub struct ProjectInfo {
     pub session_count: usize,
     pub ai_tool: crate::domain::models::AiTool,
     pub last_updated: DateTime<Utc>,
+    /// Labels of the history roots holding this project (e.g. "local", "laptop")
+    /// Left empty when the project only lives in the default root.
+    #[serde(default)]
+    pub roots: Vec<String>,
 }
@@ -12,8 +12,13 @@ use crate::domain::models::{
 };
 use std::path::PathBuf;
 
+/// Configured upload directory (default: `<cwd>/uploads`)
+fn upload_dir() -> PathBuf {
+    crate::config::config().paths.upload_dir.clone()
+}
+
 /// Error response for upload failures
-#[derive(serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, utoipa::ToSchema)]
 pub struct UploadError {
     pub error: String,
     pub code: String,
@@ -45,6 +50,22 @@ pub async fn debug_upload(headers: HeaderMap, body: axum::body::Bytes) -> String
 }
 
 /// Handle image upload with validation
+#[utoipa::path(
+    post,
+    path = "/upload",
+    tag = "uploads",
+    request_body(
+        content = String,
+        content_type = "multipart/form-data",
+        description = "One image file field (jpeg, png, webp or gif, 10MB max)",
+    ),
+    responses(
+        (status = 200, description = "Stored image", body = ImageUpload),
+        (status = 400, description = "Invalid multipart body or unsupported image", body = UploadError),
+        (status = 413, description = "Image too large", body = UploadError),
+        (status = 500, description = "Image could not be stored", body = UploadError),
+    )
+)]
 pub async fn upload_image(headers: HeaderMap, mut multipart: Multipart) -> Result<Json<ImageUpload>, (StatusCode, Json<UploadError>)> {
     // Log all headers for debugging
     tracing::info!("📤 Upload request received - logging headers:");
@@ -52,11 +73,8 @@ pub async fn upload_image(headers: HeaderMap, mut multipart: Multipart) -> Resul
         tracing::info!("📤 Header: {} = {:?}", name, value.to_str().unwrap_or("<binary>"));
     }
 
-    // Create uploads directory if it doesn't exist
-    // Use absolute path so .exists() check works regardless of CWD
-    let upload_dir = std::env::current_dir()
-        .map(|cwd| cwd.join("uploads"))
-        .unwrap_or_else(|_| PathBuf::from("uploads"));
+    // Create uploads directory if it doesn't exist (absolute, from `paths.upload_dir`)
+    let upload_dir = upload_dir();
     std::fs::create_dir_all(&upload_dir).map_err(|e| {
         tracing::error!("Failed to create uploads directory: {}", e);
         (StatusCode::INTERNAL_SERVER_ERROR, Json(UploadError {
@@ -219,8 +237,19 @@ fn get_extension_for_type(content_type: &str) -> &'static str {
 }
 
 /// Get uploaded image with proper Content-Type
+#[utoipa::path(
+    get,
+    path = "/images/{image_id}",
+    tag = "uploads",
+    params(("image_id" = String, Path, description = "Image id returned by /upload")),
+    responses(
+        (status = 200, description = "Image bytes", content_type = "image/*"),
+        (status = 400, description = "Malformed image id"),
+        (status = 404, description = "Image not found"),
+    )
+)]
 pub async fn get_image(Path(image_id): Path<String>) -> Result<Response, StatusCode> {
-    let upload_dir = PathBuf::from("uploads");
+    let upload_dir = upload_dir();
 
     // Validate image_id format (UUID)
     if image_id.len() != 36 || !image_id.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
@@ -265,7 +294,7 @@ pub async fn get_image(Path(image_id): Path<String>) -> Result<Response, StatusC
 pub async fn analyze_image(
     Json(request): Json<ImageAnalysisRequest>,
 ) -> Result<Json<ImageAnalysisResponse>, StatusCode> {
-    let upload_dir = PathBuf::from("uploads");
+    let upload_dir = upload_dir();
 
     // Find image file
     let entries = std::fs::read_dir(&upload_dir).map_err(|_| StatusCode::NOT_FOUND)?;
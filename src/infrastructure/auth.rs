@@ -0,0 +1,293 @@
+//! Authentication for the web server
+//!
+//! Two ways to authenticate, both optional but at least one is always active:
+//! - a static API token (`auth.token` in the config, `CLAUDE_VIEWER_AUTH_TOKEN`,
+//!   or `token` in the auth file),
+//!   sent as `Authorization: Bearer <token>` or used on the login page
+//! - a local password, stored as an Argon2 hash in `~/.claude-viewer/auth.json`
+//!
+//! A successful login creates an in-memory session referenced by an
+//! `HttpOnly; SameSite=Strict` cookie. Each session carries a CSRF token.
+//!
+//! If nothing is configured, a random token is generated at startup and logged,
+//! so the server is never reachable without credentials.
+//! Set `auth.disabled` / `CLAUDE_VIEWER_AUTH_DISABLED=1` to opt out (trusted local use only).
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use argon2::password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use dashmap::DashMap;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+
+/// Environment variable holding the static API token (read by the config loader)
+pub const TOKEN_ENV: &str = "CLAUDE_VIEWER_AUTH_TOKEN";
+
+/// Environment variable setting the login password (hashed and stored on startup)
+pub const PASSWORD_ENV: &str = "CLAUDE_VIEWER_PASSWORD";
+
+/// Environment variable disabling authentication entirely (read by the config loader)
+pub const DISABLED_ENV: &str = "CLAUDE_VIEWER_AUTH_DISABLED";
+
+/// Name of the session cookie
+pub const SESSION_COOKIE: &str = "hub_session";
+
+/// Header carrying the CSRF token of the current session
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// How long a login session stays valid
+pub const SESSION_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+static AUTH_CONFIG: OnceCell<AuthConfig> = OnceCell::new();
+
+static SESSIONS: Lazy<DashMap<String, AuthSession>> = Lazy::new(DashMap::new);
+
+/// Contents of `~/.claude-viewer/auth.json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuthFile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl AuthFile {
+    pub fn default_path() -> Result<PathBuf, String> {
+        Ok(crate::config::config().paths.auth_file.clone())
+    }
+
+    pub fn load() -> Result<Self, String> {
+        let path = Self::default_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {}: {}", path.display(), e))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = Self::default_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize auth file: {}", e))?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+        }
+        Ok(())
+    }
+}
+
+/// Resolved authentication settings
+#[derive(Debug, Clone, Default)]
+pub struct AuthConfig {
+    pub token: Option<String>,
+    pub password_hash: Option<String>,
+    pub disabled: bool,
+}
+
+impl AuthConfig {
+    /// Build the config from the app config, the auth file and `CLAUDE_VIEWER_PASSWORD`
+    pub fn from_env() -> Result<Self, String> {
+        let settings = &crate::config::config().auth;
+        let disabled = settings.disabled;
+
+        let mut file = AuthFile::load()?;
+
+        if let Some(password) = std::env::var(PASSWORD_ENV).ok().filter(|p| !p.is_empty()) {
+            let already_set = file
+                .password_hash
+                .as_deref()
+                .is_some_and(|hash| verify_password(hash, &password));
+            if !already_set {
+                file.password_hash = Some(hash_password(&password)?);
+                file.save()?;
+                tracing::info!("Login password updated from {}", PASSWORD_ENV);
+            }
+        }
+
+        let token = settings.token.clone().or(file.token);
+
+        let mut config = Self {
+            token,
+            password_hash: file.password_hash,
+            disabled,
+        };
+
+        if config.disabled {
+            tracing::warn!("Authentication disabled via {}: anyone reaching the port has full access", DISABLED_ENV);
+        } else if config.token.is_none() && config.password_hash.is_none() {
+            let token = generate_secret();
+            tracing::warn!(
+                "No credentials configured (set {} or {}). Generated access token for this run: {}",
+                TOKEN_ENV,
+                PASSWORD_ENV,
+                token
+            );
+            config.token = Some(token);
+        }
+
+        Ok(config)
+    }
+
+    /// Check a static API token
+    pub fn check_token(&self, candidate: &str) -> bool {
+        self.token
+            .as_deref()
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), candidate.as_bytes()))
+    }
+
+    /// Check a login secret: the password, or the static token
+    pub fn check_secret(&self, candidate: &str) -> bool {
+        let password_ok = self
+            .password_hash
+            .as_deref()
+            .is_some_and(|hash| verify_password(hash, candidate));
+        password_ok || self.check_token(candidate)
+    }
+}
+
+/// Load the auth config once (call at startup so a generated token is logged early)
+pub fn init_auth() -> &'static AuthConfig {
+    AUTH_CONFIG.get_or_init(|| {
+        AuthConfig::from_env().unwrap_or_else(|e| {
+            // Fail closed: keep auth on with a fresh token
+            tracing::error!("Failed to load auth config: {}", e);
+            let token = generate_secret();
+            tracing::warn!("Generated access token for this run: {}", token);
+            AuthConfig { token: Some(token), ..Default::default() }
+        })
+    })
+}
+
+/// Current auth config
+pub fn auth_config() -> &'static AuthConfig {
+    init_auth()
+}
+
+/// Store a new login password (Argon2 hash) in the auth file
+pub fn set_password(password: &str) -> Result<(), String> {
+    if password.len() < 8 {
+        return Err("Password must be at least 8 characters".to_string());
+    }
+    let mut file = AuthFile::load()?;
+    file.password_hash = Some(hash_password(password)?);
+    file.save()
+}
+
+/// Hash a password with Argon2id and a random salt (PHC string)
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| format!("Failed to hash password: {}", e))
+}
+
+/// Verify a password against a stored PHC hash
+pub fn verify_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash)
+        .map(|parsed| Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+        .unwrap_or(false)
+}
+
+/// Compare secrets without leaking where they differ
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Random 256-bit-ish secret (two v4 UUIDs, hex)
+pub fn generate_secret() -> String {
+    format!("{}{}", uuid::Uuid::new_v4().simple(), uuid::Uuid::new_v4().simple())
+}
+
+/// A logged-in browser session
+#[derive(Debug, Clone)]
+pub struct AuthSession {
+    pub id: String,
+    pub csrf_token: String,
+    pub expires_at: Instant,
+}
+
+/// Create and register a new session
+pub fn create_session() -> AuthSession {
+    let session = AuthSession {
+        id: generate_secret(),
+        csrf_token: generate_secret(),
+        expires_at: Instant::now() + SESSION_TTL,
+    };
+    SESSIONS.insert(session.id.clone(), session.clone());
+    session
+}
+
+/// Look up a live session (expired ones are dropped)
+pub fn get_session(id: &str) -> Option<AuthSession> {
+    let session = SESSIONS.get(id).map(|entry| entry.clone())?;
+    if session.expires_at <= Instant::now() {
+        SESSIONS.remove(id);
+        return None;
+    }
+    Some(session)
+}
+
+/// Remove a session (logout)
+pub fn remove_session(id: &str) {
+    SESSIONS.remove(id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_hash_roundtrip() {
+        let hash = hash_password("correct horse").unwrap();
+        assert!(hash.starts_with("$argon2"));
+        assert!(verify_password(&hash, "correct horse"));
+        assert!(!verify_password(&hash, "wrong horse"));
+        assert!(!verify_password("not-a-hash", "correct horse"));
+    }
+
+    #[test]
+    fn test_check_secret() {
+        let config = AuthConfig {
+            token: Some("tok-123".to_string()),
+            password_hash: Some(hash_password("s3cret-pass").unwrap()),
+            disabled: false,
+        };
+        assert!(config.check_secret("s3cret-pass"));
+        assert!(config.check_secret("tok-123"));
+        assert!(!config.check_secret(""));
+        assert!(config.check_token("tok-123"));
+        assert!(!config.check_token("s3cret-pass"));
+    }
+
+    #[test]
+    fn test_sessions() {
+        let session = create_session();
+        assert_ne!(session.id, session.csrf_token);
+        assert_eq!(get_session(&session.id).unwrap().csrf_token, session.csrf_token);
+        remove_session(&session.id);
+        assert!(get_session(&session.id).is_none());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"ab"));
+    }
+}
@@ -0,0 +1,327 @@
+//! In-memory cache of parsed conversations
+//!
+//! Bounded LRU keyed by (provider, project, session). Every entry remembers the
+//! fingerprint (mtime, size) of its session file when it was parsed; a hit is
+//! only served if the file still has that fingerprint, so a conversation the
+//! CLI appended to is re-read on the next request instead of after a TTL.
+//! The file watcher also drops entries as soon as their file changes.
+//!
+//! Bounds come from the `[cache]` config section: number of conversations and
+//! total number of messages. The least recently used entries go first.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::{AiTool, Conversation};
+use crate::history::metadata_index::{fingerprint, Fingerprint};
+
+static CONVERSATION_CACHE: Lazy<ConversationCache> = Lazy::new(|| {
+    let settings = &crate::config::config().cache;
+    ConversationCache::new(settings.max_entries, settings.max_messages)
+});
+
+/// Global conversation cache
+pub fn conversation_cache() -> &'static ConversationCache {
+    &CONVERSATION_CACHE
+}
+
+/// Cache key: one session of one project of one provider
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub ai_tool: AiTool,
+    pub encoded_name: String,
+    pub session_id: String,
+}
+
+impl CacheKey {
+    pub fn new(ai_tool: &AiTool, encoded_name: &str, session_id: &str) -> Self {
+        Self {
+            ai_tool: ai_tool.clone(),
+            encoded_name: encoded_name.to_string(),
+            session_id: session_id.to_string(),
+        }
+    }
+}
+
+/// Counters and occupancy, as reported by `GET /api/cache/stats`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CacheStats {
+    pub entries: usize,
+    pub messages: usize,
+    pub max_entries: usize,
+    pub max_messages: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to stay within bounds
+    pub evictions: u64,
+    /// Entries dropped because their file changed or was deleted
+    pub invalidations: u64,
+}
+
+struct CacheEntry {
+    conversation: Arc<Conversation>,
+    file_path: PathBuf,
+    fingerprint: Fingerprint,
+    /// Position in `CacheState::recency`
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Use tick → key, oldest first
+    recency: BTreeMap<u64, CacheKey>,
+    tick: u64,
+    messages: usize,
+}
+
+impl CacheState {
+    fn touch(&mut self, key: &CacheKey) {
+        self.tick += 1;
+        let tick = self.tick;
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.last_used);
+            entry.last_used = tick;
+            self.recency.insert(tick, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &CacheKey) -> bool {
+        match self.entries.remove(key) {
+            Some(entry) => {
+                self.recency.remove(&entry.last_used);
+                self.messages -= entry.conversation.messages.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn pop_oldest(&mut self) -> bool {
+        let Some((_, key)) = self.recency.pop_first() else {
+            return false;
+        };
+        if let Some(entry) = self.entries.remove(&key) {
+            self.messages -= entry.conversation.messages.len();
+        }
+        true
+    }
+}
+
+/// Size-bounded LRU of parsed conversations, validated against the files on disk
+pub struct ConversationCache {
+    state: Mutex<CacheState>,
+    max_entries: usize,
+    max_messages: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl ConversationCache {
+    /// Empty cache; `max_entries == 0` disables caching
+    pub fn new(max_entries: usize, max_messages: usize) -> Self {
+        Self {
+            state: Mutex::new(CacheState::default()),
+            max_entries,
+            max_messages,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+        }
+    }
+
+    /// Cached conversation, if its file has not changed since it was parsed
+    pub fn get(&self, key: &CacheKey, file_path: &Path) -> Option<Arc<Conversation>> {
+        let current = fingerprint(&key.ai_tool, file_path);
+        let mut state = self.state.lock().unwrap();
+
+        let fresh = match state.entries.get(key) {
+            None => {
+                drop(state);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            Some(entry) => entry.file_path == file_path && Some(entry.fingerprint) == current,
+        };
+
+        if !fresh {
+            state.remove(key);
+            drop(state);
+            self.invalidations.fetch_add(1, Ordering::Relaxed);
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        }
+
+        state.touch(key);
+        let conversation = state.entries.get(key).map(|entry| Arc::clone(&entry.conversation));
+        drop(state);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        conversation
+    }
+
+    /// Store a conversation parsed from a file that had `fingerprint` before parsing
+    pub fn insert(
+        &self,
+        key: CacheKey,
+        file_path: PathBuf,
+        fingerprint: Fingerprint,
+        conversation: Arc<Conversation>,
+    ) {
+        let size = conversation.messages.len();
+        if self.max_entries == 0 || size > self.max_messages {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        state.remove(&key);
+
+        let mut evicted = 0;
+        while state.entries.len() >= self.max_entries || state.messages + size > self.max_messages {
+            if !state.pop_oldest() {
+                break;
+            }
+            evicted += 1;
+        }
+
+        state.messages += size;
+        state.entries.insert(
+            key.clone(),
+            CacheEntry {
+                conversation,
+                file_path,
+                fingerprint,
+                last_used: 0,
+            },
+        );
+        state.touch(&key);
+        drop(state);
+
+        if evicted > 0 {
+            self.evictions.fetch_add(evicted, Ordering::Relaxed);
+        }
+    }
+
+    /// Drop the entries read from a file (called by the file watcher)
+    pub fn invalidate_file(&self, file_path: &Path) -> usize {
+        self.invalidate_where(|_, entry| entry.file_path == file_path)
+    }
+
+    /// Drop one session, whatever the provider
+    pub fn invalidate_session(&self, encoded_name: &str, session_id: &str) -> usize {
+        self.invalidate_where(|key, _| key.encoded_name == encoded_name && key.session_id == session_id)
+    }
+
+    /// Drop every session of a project
+    pub fn invalidate_project(&self, encoded_name: &str) -> usize {
+        self.invalidate_where(|key, _| key.encoded_name == encoded_name)
+    }
+
+    fn invalidate_where(&self, matches: impl Fn(&CacheKey, &CacheEntry) -> bool) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let keys: Vec<CacheKey> = state
+            .entries
+            .iter()
+            .filter(|(key, entry)| matches(key, entry))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &keys {
+            state.remove(key);
+        }
+        drop(state);
+
+        if !keys.is_empty() {
+            self.invalidations.fetch_add(keys.len() as u64, Ordering::Relaxed);
+        }
+        keys.len()
+    }
+
+    /// Current occupancy and counters
+    pub fn stats(&self) -> CacheStats {
+        let state = self.state.lock().unwrap();
+        CacheStats {
+            entries: state.entries.len(),
+            messages: state.messages,
+            max_entries: self.max_entries,
+            max_messages: self.max_messages,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn conversation(session_id: &str, count: usize) -> Arc<Conversation> {
+        let messages = (0..count)
+            .map(|i| crate::domain::models::Message::User {
+                content: format!("message {}", i),
+                timestamp: chrono::Utc::now(),
+                images: Vec::new(),
+                metadata: None,
+            })
+            .collect();
+        Arc::new(Conversation {
+            session_id: session_id.to_string(),
+            messages,
+        })
+    }
+
+    fn cache_file(cache: &ConversationCache, dir: &Path, id: &str, count: usize) -> (CacheKey, PathBuf) {
+        let file = dir.join(format!("{}.jsonl", id));
+        fs::write(&file, id).unwrap();
+        let key = CacheKey::new(&AiTool::ClaudeCode, "-project", id);
+        let fp = fingerprint(&AiTool::ClaudeCode, &file).unwrap();
+        cache.insert(key.clone(), file.clone(), fp, conversation(id, count));
+        (key, file)
+    }
+
+    #[test]
+    fn test_lru_bounds_and_validation() {
+        let dir = std::env::temp_dir().join(format!("conversation-cache-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let cache = ConversationCache::new(2, 10);
+
+        let (a, file_a) = cache_file(&cache, &dir, "a", 3);
+        let (b, file_b) = cache_file(&cache, &dir, "b", 3);
+        assert!(cache.get(&a, &file_a).is_some());
+
+        // Entry bound: "b" is the least recently used
+        let (c, file_c) = cache_file(&cache, &dir, "c", 3);
+        assert!(cache.get(&b, &file_b).is_none());
+        assert!(cache.get(&a, &file_a).is_some());
+
+        // Message bound: 3 + 3 + 6 > 10, "c" then goes
+        let (d, file_d) = cache_file(&cache, &dir, "d", 6);
+        assert!(cache.get(&c, &file_c).is_none());
+        assert_eq!(cache.stats().messages, 9);
+
+        // A file that grew is not served from the cache
+        fs::write(&file_d, "d appended by the CLI").unwrap();
+        assert!(cache.get(&d, &file_d).is_none());
+
+        assert_eq!(cache.invalidate_file(&file_a), 1);
+        assert!(cache.get(&a, &file_a).is_none());
+
+        let stats = cache.stats();
+        assert_eq!((stats.entries, stats.messages), (0, 0));
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 4);
+        assert_eq!(stats.evictions, 2);
+        assert_eq!(stats.invalidations, 2);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,6 +1,7 @@
 //! SurrealDB connection management
 //!
 //! Provides embedded database connection stored in ~/.claude-viewer/surreal/
+//! (configurable via `paths.database_dir`)
 
 use surrealdb::engine::local::{Db, RocksDb};
 use surrealdb::Surreal;
@@ -16,14 +17,9 @@ pub type Database = Arc<Surreal<Db>>;
 /// Global database instance (singleton)
 static DB: OnceCell<Database> = OnceCell::const_new();
 
-/// Get the database directory path (~/.claude-viewer/surreal/)
+/// Get the database directory path (default: ~/.claude-viewer/surreal/)
 fn get_db_path() -> Result<PathBuf, String> {
-    let home = std::env::var("HOME")
-        .map_err(|_| "HOME environment variable not set".to_string())?;
-
-    let db_path = PathBuf::from(home)
-        .join(".claude-viewer")
-        .join("surreal");
+    let db_path = crate::config::config().paths.database_dir.clone();
 
     // Create directory if it doesn't exist
     std::fs::create_dir_all(&db_path)
@@ -1,17 +1,26 @@
-//! JSONL to SurrealDB importer
+//! History to SurrealDB importer
 //!
-//! Reads Claude history files and imports them into SurrealDB.
+//! Reads Claude (.jsonl), OpenCode (ses_*.json) and Gemini (session-*.json)
+//! history files and imports them into SurrealDB.
 
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use chrono::{DateTime, Utc};
 use surrealdb::engine::local::Db;
 use surrealdb::sql::Thing;
 use surrealdb::Surreal;
 
-use crate::history::parser::{parse_history_file_metadata, RawHistoryLine, InnerMessage, MessageContent};
-use crate::history::path_utils::{get_claude_projects_dir, list_project_folders, decode_project_path};
+use crate::domain::models::AiTool;
+use crate::history::parser::{
+    parse_json_session_file, extract_opencode_working_directory, dedup_session_files,
+    ConversationFile, RawHistoryLine, InnerMessage, MessageContent,
+};
+use crate::history::parsers::{parse_claude_summaries, parse_opencode_summaries, parse_gemini_summaries};
+use crate::history::path_utils::{
+    get_history_roots, find_project_dirs, list_project_folders, decode_project_path, decode_gemini_hash,
+};
 use crate::history::grouping::group_conversations;
+use crate::history::project_identity::resolve_project_path;
 
 use super::repositories::{
     project_repo::{CreateProject, ProjectRepository},
@@ -19,6 +28,9 @@ use super::repositories::{
     message_repo::{CreateMessage, MessageRepository},
 };
 
+/// All providers the importer knows how to read
+const AI_TOOLS: [AiTool; 3] = [AiTool::ClaudeCode, AiTool::OpenCode, AiTool::Gemini];
+
 /// Import result statistics
 #[derive(Debug, Default)]
 pub struct ImportStats {
@@ -28,25 +40,65 @@ pub struct ImportStats {
     pub errors: Vec<String>,
 }
 
-/// Import all Claude history into SurrealDB
+impl ImportStats {
+    /// Accumulate another run's statistics into this one
+    pub fn merge(&mut self, other: ImportStats) {
+        self.projects_imported += other.projects_imported;
+        self.conversations_imported += other.conversations_imported;
+        self.messages_imported += other.messages_imported;
+        self.errors.extend(other.errors);
+    }
+}
+
+/// Database key used for the `ai_tool` field of project and conversation records
+pub fn ai_tool_key(ai_tool: &AiTool) -> &'static str {
+    match ai_tool {
+        AiTool::ClaudeCode => "claude_code",
+        AiTool::OpenCode => "opencode",
+        AiTool::Gemini => "gemini",
+    }
+}
+
+/// Import history of every provider into SurrealDB
 pub async fn import_all_history(db: &Surreal<Db>) -> Result<ImportStats, String> {
     let mut stats = ImportStats::default();
 
-    // Get Claude projects directory
-    let projects_dir = get_claude_projects_dir()?;
+    for ai_tool in &AI_TOOLS {
+        match import_tool_history(db, ai_tool).await {
+            Ok(tool_stats) => stats.merge(tool_stats),
+            Err(e) => stats.errors.push(format!("{:?}: {}", ai_tool, e)),
+        }
+    }
+
+    Ok(stats)
+}
+
+/// Import all projects of a single provider (every history root)
+/// A project present in several roots is imported once from all of its folders.
+pub async fn import_tool_history(db: &Surreal<Db>, ai_tool: &AiTool) -> Result<ImportStats, String> {
+    let mut stats = ImportStats::default();
 
-    // List all project folders
-    let project_folders = list_project_folders(&projects_dir)?;
+    let mut projects: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for root in get_history_roots(ai_tool) {
+        let projects_dir = root.projects_dir();
+        if !projects_dir.exists() {
+            tracing::debug!("No history directory for {:?} root '{}': {}", ai_tool, root.label, projects_dir.display());
+            continue;
+        }
+        for (encoded_name, folder) in list_tool_project_folders(ai_tool, &projects_dir)? {
+            projects.entry(encoded_name).or_default().push(folder);
+        }
+    }
 
-    for folder in project_folders {
-        match import_project(db, &folder).await {
-            Ok((_, conv_count, msg_count)) => {
-                stats.projects_imported += 1;
+    for (encoded_name, folders) in projects {
+        match import_project(db, ai_tool, &encoded_name, &folders, None).await {
+            Ok((project_count, conv_count, msg_count)) => {
+                stats.projects_imported += project_count;
                 stats.conversations_imported += conv_count;
                 stats.messages_imported += msg_count;
             }
             Err(e) => {
-                stats.errors.push(format!("Project {}: {}", folder.display(), e));
+                stats.errors.push(format!("Project {}: {}", encoded_name, e));
             }
         }
     }
@@ -54,44 +106,103 @@ pub async fn import_all_history(db: &Surreal<Db>) -> Result<ImportStats, String>
     Ok(stats)
 }
 
-/// Import a single project
+/// List (encoded_name, directory) pairs for a provider
+/// - Claude: one dash-encoded folder per project
+/// - OpenCode: all sessions live in the "global" directory itself
+/// - Gemini: one SHA256 hash folder per project (other folders like `bin` are skipped)
+pub fn list_tool_project_folders(
+    ai_tool: &AiTool,
+    projects_dir: &PathBuf,
+) -> Result<Vec<(String, PathBuf)>, String> {
+    if matches!(ai_tool, AiTool::OpenCode) {
+        return Ok(vec![("global".to_string(), projects_dir.clone())]);
+    }
+
+    let folders = list_project_folders(projects_dir)?
+        .into_iter()
+        .filter_map(|folder| {
+            let name = folder.file_name()?.to_str()?.to_string();
+            Some((name, folder))
+        })
+        .filter(|(name, _)| {
+            !matches!(ai_tool, AiTool::Gemini)
+                || (name.len() >= 10 && name.chars().all(|c| c.is_ascii_hexdigit()))
+        })
+        .collect();
+
+    Ok(folders)
+}
+
+/// Resolve the display name and real path of a project folder
+fn resolve_project_identity(ai_tool: &AiTool, encoded_name: &str, project_path: &Path) -> (String, String) {
+    match ai_tool {
+        AiTool::ClaudeCode => {
+            // Real path from the sessions' cwd; the dash decode is only a fallback
+            let decoded = resolve_project_path(ai_tool, encoded_name)
+                .or_else(|| decode_project_path(encoded_name).ok())
+                .unwrap_or_else(|| encoded_name.to_string());
+            (decoded.clone(), decoded)
+        }
+        AiTool::OpenCode => {
+            let path = extract_opencode_working_directory(project_path)
+                .unwrap_or_else(|| "OpenCode".to_string());
+            let name = Path::new(&path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("OpenCode")
+                .to_string();
+            (name, path)
+        }
+        AiTool::Gemini => {
+            let (path, name) = decode_gemini_hash(encoded_name);
+            (name, path)
+        }
+    }
+}
+
+/// Import a single project from its folders (one per history root holding it)
+///
+/// When `only_sessions` is set, sessions are still grouped across the whole
+/// project (so deduplication stays correct) but only the listed ones are imported.
 async fn import_project(
     db: &Surreal<Db>,
-    project_path: &PathBuf,
+    ai_tool: &AiTool,
+    encoded_name: &str,
+    project_paths: &[PathBuf],
+    only_sessions: Option<&HashSet<String>>,
 ) -> Result<(usize, usize, usize), String> {
-    let folder_name = project_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or("Invalid project folder name")?;
+    let Some(project_path) = project_paths.first() else {
+        return Ok((0, 0, 0));
+    };
 
-    // Decode project name
-    let decoded_name = decode_project_path(folder_name)
-        .unwrap_or_else(|_| folder_name.to_string());
+    // Parse session metadata with the provider-specific parser
+    let mut conversation_files = Vec::new();
+    for path in project_paths {
+        conversation_files.extend(match ai_tool {
+            AiTool::ClaudeCode => parse_claude_summaries(path)?,
+            AiTool::OpenCode => parse_opencode_summaries(path)?,
+            AiTool::Gemini => parse_gemini_summaries(path)?,
+        });
+    }
 
-    // List session files
-    let session_files = list_session_files(project_path)?;
+    // Same session copied to several roots: keep the most complete copy
+    let conversation_files = dedup_session_files(conversation_files);
 
-    if session_files.is_empty() {
+    if conversation_files.is_empty() {
         return Ok((0, 0, 0));
     }
 
-    // Parse all session files to get metadata for grouping
-    let mut conversation_files = Vec::new();
-    for file_path in &session_files {
-        if let Ok(metadata) = parse_history_file_metadata(file_path) {
-            conversation_files.push(metadata);
-        }
-    }
-
     // Group conversations (deduplicate by message.id sets)
     let grouped = group_conversations(conversation_files.clone());
 
+    let (name, path) = resolve_project_identity(ai_tool, encoded_name, project_path);
+
     // Create or update project in DB
     let project = ProjectRepository::upsert(db, CreateProject {
-        name: decoded_name.clone(),
-        path: decoded_name.clone(),
-        encoded_name: folder_name.to_string(),
-        ai_tool: "claude_code".to_string(),
+        name,
+        path,
+        encoded_name: encoded_name.to_string(),
+        ai_tool: ai_tool_key(ai_tool).to_string(),
         session_count: grouped.len() as i32,
     }).await?;
 
@@ -101,26 +212,39 @@ async fn import_project(
     let mut total_conversations = 0;
     let mut total_messages = 0;
 
-    // Create a map from session_id to file_path for quick lookup
-    let file_map: std::collections::HashMap<String, String> = conversation_files
+    // Create a map from session_id to its metadata for quick lookup
+    let file_map: std::collections::HashMap<String, &ConversationFile> = conversation_files
         .iter()
-        .map(|cf| (cf.session_id.clone(), cf.file_path.clone()))
+        .map(|cf| (cf.session_id.clone(), cf))
         .collect();
 
     for summary in grouped {
-        // Find the file path for this session
-        let file_path = match file_map.get(&summary.session_id) {
-            Some(path) => PathBuf::from(path),
+        if only_sessions.is_some_and(|only| !only.contains(&summary.session_id)) {
+            continue;
+        }
+
+        let conversation_file = match file_map.get(&summary.session_id) {
+            Some(cf) => *cf,
             None => continue,
         };
 
-        match import_conversation(db, &project_id, &summary.session_id, &file_path).await {
+        let result = match ai_tool {
+            AiTool::ClaudeCode => {
+                let file_path = PathBuf::from(&conversation_file.file_path);
+                import_conversation(db, &project_id, &summary.session_id, &file_path).await
+            }
+            AiTool::OpenCode | AiTool::Gemini => {
+                import_json_conversation(db, &project_id, ai_tool, conversation_file).await
+            }
+        };
+
+        match result {
             Ok(msg_count) => {
                 total_conversations += 1;
                 total_messages += msg_count;
             }
             Err(e) => {
-                tracing::warn!("Failed to import conversation {}: {}", summary.session_id, e);
+                tracing::warn!("Failed to import {:?} conversation {}: {}", ai_tool, summary.session_id, e);
             }
         }
     }
@@ -128,7 +252,7 @@ async fn import_project(
     Ok((1, total_conversations, total_messages))
 }
 
-/// Import a single conversation
+/// Import a single Claude conversation (.jsonl)
 async fn import_conversation(
     db: &Surreal<Db>,
     project_id: &Thing,
@@ -181,6 +305,7 @@ async fn import_conversation(
     let conversation = ConversationRepository::upsert(db, CreateConversation {
         project_id: project_id.clone(),
         session_id: session_id.to_string(),
+        ai_tool: ai_tool_key(&AiTool::ClaudeCode).to_string(),
         source_files: vec![file_path.to_string_lossy().to_string()],
         message_count: raw_messages.len() as i32,
         start_time: start_dt,
@@ -240,6 +365,79 @@ async fn import_conversation(
     Ok(imported_count)
 }
 
+/// Import a single OpenCode or Gemini conversation (.json)
+///
+/// These formats have no stable per-message IDs, so messages are keyed by
+/// their position in the session. Re-importing a session that grew only
+/// inserts the appended messages.
+async fn import_json_conversation(
+    db: &Surreal<Db>,
+    project_id: &Thing,
+    ai_tool: &AiTool,
+    conversation_file: &ConversationFile,
+) -> Result<usize, String> {
+    let file_path = Path::new(&conversation_file.file_path);
+    let session_id = &conversation_file.session_id;
+
+    let history = parse_json_session_file(file_path, session_id, ai_tool)?;
+
+    if history.messages.is_empty() {
+        return Ok(0);
+    }
+
+    let start_dt = parse_timestamp(&Some(conversation_file.start_time.clone())).unwrap_or_else(Utc::now);
+    let end_dt = parse_timestamp(&Some(conversation_file.last_time.clone())).unwrap_or(start_dt);
+
+    let conversation = ConversationRepository::upsert(db, CreateConversation {
+        project_id: project_id.clone(),
+        session_id: session_id.clone(),
+        ai_tool: ai_tool_key(ai_tool).to_string(),
+        source_files: vec![conversation_file.file_path.clone()],
+        message_count: history.messages.len() as i32,
+        start_time: start_dt,
+        end_time: end_dt,
+        last_preview: conversation_file.last_message_preview.chars().take(100).collect(),
+    }).await?;
+
+    let conversation_id = conversation.id.ok_or("Conversation created without ID")?;
+
+    let existing_msg_ids = get_existing_message_ids(db, &conversation_id).await?;
+
+    let mut new_messages = Vec::new();
+    for (index, message) in history.messages.into_iter().enumerate() {
+        let msg_id = json_message_id(session_id, index);
+        if existing_msg_ids.contains(&msg_id) {
+            continue;
+        }
+
+        let timestamp = parse_timestamp(&message.timestamp).unwrap_or(end_dt);
+
+        new_messages.push(CreateMessage {
+            conversation_id: conversation_id.clone(),
+            message_id: Some(msg_id),
+            role: message.role,
+            content: serde_json::to_value(&message.content).unwrap_or(serde_json::Value::Null),
+            timestamp,
+            uuid: None,
+            parent_uuid: None,
+            is_sidechain: false,
+        });
+    }
+
+    let imported_count = new_messages.len();
+
+    if !new_messages.is_empty() {
+        MessageRepository::create_batch(db, new_messages).await?;
+    }
+
+    Ok(imported_count)
+}
+
+/// Synthetic message ID for formats without per-message IDs
+fn json_message_id(session_id: &str, index: usize) -> String {
+    format!("{}:{}", session_id, index)
+}
+
 /// Parse ISO 8601 timestamp string to DateTime
 fn parse_timestamp(ts: &Option<String>) -> Option<DateTime<Utc>> {
     ts.as_ref().and_then(|s| {
@@ -249,22 +447,6 @@ fn parse_timestamp(ts: &Option<String>) -> Option<DateTime<Utc>> {
     })
 }
 
-/// List JSONL session files in a project folder
-fn list_session_files(project_path: &PathBuf) -> Result<Vec<PathBuf>, String> {
-    let entries = std::fs::read_dir(project_path)
-        .map_err(|e| format!("Failed to read project directory: {}", e))?;
-
-    let mut files = Vec::new();
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if path.extension().and_then(|e| e.to_str()) == Some("jsonl") {
-            files.push(path);
-        }
-    }
-
-    Ok(files)
-}
-
 /// Get existing message IDs for a conversation
 async fn get_existing_message_ids(
     db: &Surreal<Db>,
@@ -298,23 +480,54 @@ fn extract_preview_text(message: &Option<InnerMessage>) -> Option<String> {
     }
 }
 
-/// Sync a single project (incremental update)
+/// Resolve the on-disk directories of a project for a given provider (all roots)
+fn resolve_project_dirs(ai_tool: &AiTool, encoded_name: &str) -> Result<Vec<PathBuf>, String> {
+    let dirs: Vec<PathBuf> = find_project_dirs(ai_tool, encoded_name)
+        .into_iter()
+        .map(|(_, dir)| dir)
+        .collect();
+
+    if dirs.is_empty() {
+        return Err(format!("Project folder not found: {}", encoded_name));
+    }
+
+    Ok(dirs)
+}
+
+/// Sync a single project of a given provider (incremental update)
 pub async fn sync_project(
     db: &Surreal<Db>,
+    ai_tool: &AiTool,
     encoded_name: &str,
 ) -> Result<ImportStats, String> {
-    let projects_dir = get_claude_projects_dir()?;
-    let project_path = projects_dir.join(encoded_name);
+    let project_paths = resolve_project_dirs(ai_tool, encoded_name)?;
+    Ok(run_project_import(db, ai_tool, encoded_name, &project_paths, None).await)
+}
 
-    if !project_path.exists() {
-        return Err(format!("Project folder not found: {}", encoded_name));
-    }
+/// Sync only some sessions of a project (incremental update)
+/// Messages already in the database are skipped, so only appended ones are inserted.
+pub async fn sync_sessions(
+    db: &Surreal<Db>,
+    ai_tool: &AiTool,
+    encoded_name: &str,
+    session_ids: &HashSet<String>,
+) -> Result<ImportStats, String> {
+    let project_paths = resolve_project_dirs(ai_tool, encoded_name)?;
+    Ok(run_project_import(db, ai_tool, encoded_name, &project_paths, Some(session_ids)).await)
+}
 
+async fn run_project_import(
+    db: &Surreal<Db>,
+    ai_tool: &AiTool,
+    encoded_name: &str,
+    project_paths: &[PathBuf],
+    only_sessions: Option<&HashSet<String>>,
+) -> ImportStats {
     let mut stats = ImportStats::default();
 
-    match import_project(db, &project_path).await {
-        Ok((_, conv_count, msg_count)) => {
-            stats.projects_imported = 1;
+    match import_project(db, ai_tool, encoded_name, project_paths, only_sessions).await {
+        Ok((project_count, conv_count, msg_count)) => {
+            stats.projects_imported = project_count;
             stats.conversations_imported = conv_count;
             stats.messages_imported = msg_count;
         }
@@ -323,5 +536,39 @@ pub async fn sync_project(
         }
     }
 
-    Ok(stats)
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ai_tool_key() {
+        assert_eq!(ai_tool_key(&AiTool::ClaudeCode), "claude_code");
+        assert_eq!(ai_tool_key(&AiTool::OpenCode), "opencode");
+        assert_eq!(ai_tool_key(&AiTool::Gemini), "gemini");
+    }
+
+    #[test]
+    fn test_json_message_id_is_positional() {
+        assert_eq!(json_message_id("abc", 0), "abc:0");
+        assert_ne!(json_message_id("abc", 1), json_message_id("abd", 1));
+    }
+
+    #[test]
+    fn test_gemini_folders_skip_non_hash_dirs() {
+        let dir = std::env::temp_dir().join(format!("importer-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(dir.join("bin")).unwrap();
+        std::fs::create_dir_all(dir.join("0123456789abcdef0123")).unwrap();
+
+        let folders = list_tool_project_folders(&AiTool::Gemini, &dir).unwrap();
+        let names: Vec<_> = folders.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["0123456789abcdef0123"]);
+
+        let opencode = list_tool_project_folders(&AiTool::OpenCode, &dir).unwrap();
+        assert_eq!(opencode, vec![("global".to_string(), dir.clone())]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -4,12 +4,14 @@
 //! - Embedded SurrealDB connection (~/.claude-viewer/surreal/)
 //! - Schema definitions for projects, conversations, and messages
 //! - Repository layer for CRUD operations
-//! - Import/sync utilities from Claude's JSONL files
+//! - Import/sync utilities from Claude, OpenCode and Gemini history files
+//! - Background sync service keeping the database in step with history files
 
 pub mod connection;
 pub mod schema;
 pub mod repositories;
 pub mod importer;
+pub mod sync;
 
 pub use connection::{Database, init_database, get_database, try_get_database};
 pub use schema::run_migrations;
@@ -17,5 +19,7 @@ pub use repositories::{
     ProjectRepository,
     ConversationRepository,
     MessageRepository,
+    SyncStateRepository,
 };
-pub use importer::{import_all_history, sync_project, ImportStats};
+pub use importer::{import_all_history, import_tool_history, sync_project, sync_sessions, ImportStats};
+pub use sync::{start_sync_service, sync_status, SyncStatus, SyncPhase};
@@ -12,6 +12,8 @@ pub struct ConversationRecord {
     pub id: Option<Thing>,
     pub project_id: Thing,
     pub session_id: String,
+    /// Provider that produced the session ("claude_code", "opencode", "gemini")
+    pub ai_tool: String,
     pub title: Option<String>,
     pub source_files: Vec<String>,
     pub message_count: i32,
@@ -31,6 +33,7 @@ pub struct ConversationRecord {
 pub struct CreateConversation {
     pub project_id: Thing,
     pub session_id: String,
+    pub ai_tool: String,
     pub source_files: Vec<String>,
     pub message_count: i32,
     pub start_time: DateTime<Utc>,
@@ -43,6 +46,7 @@ pub struct CreateConversation {
 pub struct ConversationSummary {
     pub id: Thing,
     pub session_id: String,
+    pub ai_tool: String,
     pub title: Option<String>,
     pub message_count: i32,
     pub start_time: DateTime<Utc>,
@@ -96,6 +100,7 @@ impl ConversationRepository {
                 id: None,
                 project_id: conversation.project_id,
                 session_id: conversation.session_id,
+                ai_tool: conversation.ai_tool,
                 title: None,
                 source_files: conversation.source_files,
                 message_count: conversation.message_count,
@@ -123,7 +128,7 @@ impl ConversationRepository {
         let project_id_owned = project_id.clone();
         let conversations: Vec<ConversationSummary> = db
             .query(r#"
-                SELECT id, session_id, title, message_count, start_time, end_time,
+                SELECT id, session_id, ai_tool, title, message_count, start_time, end_time,
                        last_preview, is_favorite, tags
                 FROM conversation
                 WHERE project_id = $project_id AND is_deleted = false
@@ -174,24 +179,44 @@ impl ConversationRepository {
     }
 
     /// Hard delete a conversation (and its messages)
-    pub async fn hard_delete(db: &Surreal<Db>, session_id: &str) -> Result<Vec<String>, String> {
+    /// With `ai_tool` (database key), only that provider's conversation is deleted.
+    pub async fn hard_delete(
+        db: &Surreal<Db>,
+        session_id: &str,
+        ai_tool: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        let filter = match ai_tool {
+            Some(_) => "session_id = $session_id AND ai_tool = $ai_tool",
+            None => "session_id = $session_id",
+        };
+        let session_id_owned = session_id.to_string();
+        let ai_tool_owned = ai_tool.map(str::to_string);
+
         // Get source files before deletion
-        let conversation = Self::find_by_session_id(db, session_id).await?;
+        let mut result = db
+            .query(format!("SELECT * FROM conversation WHERE {} LIMIT 1", filter))
+            .bind(("session_id", session_id_owned.clone()))
+            .bind(("ai_tool", ai_tool_owned.clone()))
+            .await
+            .map_err(|e| format!("Failed to query conversation: {}", e))?;
+        let conversation: Option<ConversationRecord> = result
+            .take(0)
+            .map_err(|e| format!("Failed to get conversation: {}", e))?;
         let source_files = conversation
             .map(|c| c.source_files)
             .unwrap_or_default();
 
-        let session_id_owned = session_id.to_string();
-
         // Delete messages first
-        db.query("DELETE FROM message WHERE conversation_id IN (SELECT id FROM conversation WHERE session_id = $session_id)")
+        db.query(format!("DELETE FROM message WHERE conversation_id IN (SELECT id FROM conversation WHERE {})", filter))
             .bind(("session_id", session_id_owned.clone()))
+            .bind(("ai_tool", ai_tool_owned.clone()))
             .await
             .map_err(|e| format!("Failed to delete messages: {}", e))?;
 
         // Delete conversation
-        db.query("DELETE FROM conversation WHERE session_id = $session_id")
+        db.query(format!("DELETE FROM conversation WHERE {}", filter))
             .bind(("session_id", session_id_owned))
+            .bind(("ai_tool", ai_tool_owned))
             .await
             .map_err(|e| format!("Failed to delete conversation: {}", e))?;
 
@@ -241,7 +266,7 @@ impl ConversationRepository {
     pub async fn find_favorites(db: &Surreal<Db>) -> Result<Vec<ConversationSummary>, String> {
         let conversations: Vec<ConversationSummary> = db
             .query(r#"
-                SELECT id, session_id, title, message_count, start_time, end_time,
+                SELECT id, session_id, ai_tool, title, message_count, start_time, end_time,
                        last_preview, is_favorite, tags
                 FROM conversation
                 WHERE is_favorite = true AND is_deleted = false
@@ -4,11 +4,14 @@
 //! - Projects
 //! - Conversations
 //! - Messages
+//! - Sync state (file fingerprints for background sync)
 
 pub mod project_repo;
 pub mod conversation_repo;
 pub mod message_repo;
+pub mod sync_state_repo;
 
 pub use project_repo::ProjectRepository;
 pub use conversation_repo::ConversationRepository;
 pub use message_repo::MessageRepository;
+pub use sync_state_repo::SyncStateRepository;
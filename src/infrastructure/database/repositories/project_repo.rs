@@ -33,17 +33,16 @@ pub struct CreateProject {
 pub struct ProjectRepository;
 
 impl ProjectRepository {
-    /// Create or update a project (upsert by encoded_name)
+    /// Create or update a project (upsert by ai_tool + encoded_name)
     pub async fn upsert(db: &Surreal<Db>, project: CreateProject) -> Result<ProjectRecord, String> {
         let result: Option<ProjectRecord> = db
             .query(r#"
                 UPDATE project SET
                     name = $name,
                     path = $path,
-                    ai_tool = $ai_tool,
                     session_count = $session_count,
                     updated_at = time::now()
-                WHERE encoded_name = $encoded_name
+                WHERE encoded_name = $encoded_name AND ai_tool = $ai_tool
                 RETURN AFTER
             "#)
             .bind(("name", project.name.clone()))
@@ -0,0 +1,118 @@
+//! Sync state repository for database operations
+//!
+//! Stores the last synced fingerprint (mtime + size) of each history file so
+//! the background sync can skip unchanged files on startup.
+
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::local::Db;
+use surrealdb::sql::Thing;
+use surrealdb::Surreal;
+use chrono::{DateTime, Utc};
+
+/// Sync state record in database
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncStateRecord {
+    pub id: Option<Thing>,
+    pub path: String,
+    pub ai_tool: String,
+    pub encoded_name: String,
+    pub session_id: String,
+    /// Modification time in Unix milliseconds
+    pub mtime: i64,
+    pub size: i64,
+    pub synced_at: DateTime<Utc>,
+}
+
+/// Input for recording a synced file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSyncState {
+    pub path: String,
+    pub ai_tool: String,
+    pub encoded_name: String,
+    pub session_id: String,
+    pub mtime: i64,
+    pub size: i64,
+}
+
+/// Sync state repository
+pub struct SyncStateRepository;
+
+impl SyncStateRepository {
+    /// Create or update the fingerprint of a file (upsert by path)
+    pub async fn upsert(db: &Surreal<Db>, state: CreateSyncState) -> Result<SyncStateRecord, String> {
+        let result: Option<SyncStateRecord> = db
+            .query(r#"
+                UPDATE sync_state SET
+                    mtime = $mtime,
+                    size = $size,
+                    synced_at = time::now()
+                WHERE path = $path
+                RETURN AFTER
+            "#)
+            .bind(("path", state.path.clone()))
+            .bind(("mtime", state.mtime))
+            .bind(("size", state.size))
+            .await
+            .map_err(|e| format!("Failed to upsert sync state: {}", e))?
+            .take(0)
+            .map_err(|e| format!("Failed to get upsert result: {}", e))?;
+
+        if let Some(record) = result {
+            return Ok(record);
+        }
+
+        let created: Option<SyncStateRecord> = db
+            .create("sync_state")
+            .content(SyncStateRecord {
+                id: None,
+                path: state.path,
+                ai_tool: state.ai_tool,
+                encoded_name: state.encoded_name,
+                session_id: state.session_id,
+                mtime: state.mtime,
+                size: state.size,
+                synced_at: Utc::now(),
+            })
+            .await
+            .map_err(|e| format!("Failed to create sync state: {}", e))?;
+
+        created.ok_or_else(|| "Failed to create sync state".to_string())
+    }
+
+    /// Get all recorded file fingerprints
+    pub async fn find_all(db: &Surreal<Db>) -> Result<Vec<SyncStateRecord>, String> {
+        let states: Vec<SyncStateRecord> = db
+            .select("sync_state")
+            .await
+            .map_err(|e| format!("Failed to fetch sync state: {}", e))?;
+
+        Ok(states)
+    }
+
+    /// Files recorded for a session (one per history root holding a copy)
+    pub async fn find_by_session_id(db: &Surreal<Db>, session_id: &str) -> Result<Vec<SyncStateRecord>, String> {
+        let session_id_owned = session_id.to_string();
+        let mut result = db
+            .query("SELECT * FROM sync_state WHERE session_id = $session_id")
+            .bind(("session_id", session_id_owned))
+            .await
+            .map_err(|e| format!("Failed to fetch sync state: {}", e))?;
+
+        let states: Vec<SyncStateRecord> = result
+            .take(0)
+            .map_err(|e| format!("Failed to parse sync state: {}", e))?;
+
+        Ok(states)
+    }
+
+    /// Forget a file (after it was deleted on disk)
+    pub async fn delete_by_path(db: &Surreal<Db>, path: &str) -> Result<(), String> {
+        let path_owned = path.to_string();
+        db.query("DELETE FROM sync_state WHERE path = $path")
+            .bind(("path", path_owned))
+            .await
+            .map_err(|e| format!("Failed to delete sync state: {}", e))?;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 //! Database schema definitions and migrations
 //!
-//! Defines tables for: project, conversation, message
+//! Defines tables for: project, conversation, message, sync_state
 //! Uses SurrealQL for schema definitions
 
 use surrealdb::engine::local::Db;
@@ -19,6 +19,9 @@ pub async fn run_migrations(db: &Surreal<Db>) -> Result<(), String> {
     // Create message table
     create_message_table(db).await?;
 
+    // Create sync state table
+    create_sync_state_table(db).await?;
+
     tracing::info!("Database migrations completed");
     Ok(())
 }
@@ -35,7 +38,9 @@ async fn create_project_table(db: &Surreal<Db>) -> Result<(), String> {
         DEFINE FIELD IF NOT EXISTS created_at ON project TYPE datetime DEFAULT time::now();
         DEFINE FIELD IF NOT EXISTS updated_at ON project TYPE datetime DEFAULT time::now();
 
-        DEFINE INDEX IF NOT EXISTS idx_project_encoded ON project FIELDS encoded_name UNIQUE;
+        -- Encoded names are only unique per provider ("global" or a hash can collide)
+        REMOVE INDEX IF EXISTS idx_project_encoded ON project;
+        DEFINE INDEX IF NOT EXISTS idx_project_tool_encoded ON project FIELDS ai_tool, encoded_name UNIQUE;
         DEFINE INDEX IF NOT EXISTS idx_project_path ON project FIELDS path;
     "#)
     .await
@@ -50,6 +55,7 @@ async fn create_conversation_table(db: &Surreal<Db>) -> Result<(), String> {
 
         DEFINE FIELD IF NOT EXISTS project_id ON conversation TYPE record<project>;
         DEFINE FIELD IF NOT EXISTS session_id ON conversation TYPE string;
+        DEFINE FIELD IF NOT EXISTS ai_tool ON conversation TYPE string DEFAULT 'claude_code';
         DEFINE FIELD IF NOT EXISTS title ON conversation TYPE option<string>;
         DEFINE FIELD IF NOT EXISTS source_files ON conversation TYPE array DEFAULT [];
         DEFINE FIELD IF NOT EXISTS message_count ON conversation TYPE int DEFAULT 0;
@@ -67,6 +73,10 @@ async fn create_conversation_table(db: &Surreal<Db>) -> Result<(), String> {
         DEFINE INDEX IF NOT EXISTS idx_conv_project ON conversation FIELDS project_id;
         DEFINE INDEX IF NOT EXISTS idx_conv_favorite ON conversation FIELDS is_favorite;
         DEFINE INDEX IF NOT EXISTS idx_conv_deleted ON conversation FIELDS is_deleted;
+        DEFINE INDEX IF NOT EXISTS idx_conv_ai_tool ON conversation FIELDS ai_tool;
+
+        -- Conversations imported before multi-provider support were Claude-only
+        UPDATE conversation SET ai_tool = 'claude_code' WHERE ai_tool = NONE;
     "#)
     .await
     .map_err(|e| format!("Failed to create conversation table: {}", e))?;
@@ -98,3 +108,23 @@ async fn create_message_table(db: &Surreal<Db>) -> Result<(), String> {
 
     Ok(())
 }
+
+async fn create_sync_state_table(db: &Surreal<Db>) -> Result<(), String> {
+    db.query(r#"
+        DEFINE TABLE IF NOT EXISTS sync_state SCHEMAFULL;
+
+        DEFINE FIELD IF NOT EXISTS path ON sync_state TYPE string;
+        DEFINE FIELD IF NOT EXISTS ai_tool ON sync_state TYPE string;
+        DEFINE FIELD IF NOT EXISTS encoded_name ON sync_state TYPE string;
+        DEFINE FIELD IF NOT EXISTS session_id ON sync_state TYPE string;
+        DEFINE FIELD IF NOT EXISTS mtime ON sync_state TYPE int;
+        DEFINE FIELD IF NOT EXISTS size ON sync_state TYPE int;
+        DEFINE FIELD IF NOT EXISTS synced_at ON sync_state TYPE datetime DEFAULT time::now();
+
+        DEFINE INDEX IF NOT EXISTS idx_sync_path ON sync_state FIELDS path UNIQUE;
+    "#)
+    .await
+    .map_err(|e| format!("Failed to create sync_state table: {}", e))?;
+
+    Ok(())
+}
@@ -0,0 +1,562 @@
+//! Background sync from history files into SurrealDB
+//!
+//! Started once with the server:
+//! 0. The project path registry is refreshed so Gemini hashes can be decoded.
+//! 1. Startup reconciliation: compares each session file's mtime + size with
+//!    the fingerprint stored in `sync_state` and only re-imports what changed.
+//! 2. Watch mode: listens to every history root of every provider with `notify`
+//!    and upserts changed sessions (appended messages only) or removes deleted ones.
+//!    Project paths of changed Claude and OpenCode sessions go to the registry.
+//!
+//! Status is kept in memory and exposed via `/api/sync/status`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use surrealdb::engine::local::Db;
+use surrealdb::Surreal;
+
+use crate::domain::models::AiTool;
+use crate::history::file_utils::extract_session_id;
+use crate::history::forks::{is_fork_copy, remove_stale_copies};
+use crate::history::path_registry::{learn_session_paths, refresh_registry};
+use crate::history::path_utils::get_history_roots;
+use crate::history::project_identity::invalidate_project_path;
+use crate::infrastructure::conversation_cache::conversation_cache;
+
+use super::connection::init_database;
+use super::importer::{ai_tool_key, list_tool_project_folders, sync_sessions};
+use super::repositories::{
+    conversation_repo::ConversationRepository,
+    sync_state_repo::{CreateSyncState, SyncStateRepository},
+};
+
+/// Delay used to batch bursts of file events (CLIs write many lines per turn)
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// All providers watched by the sync service
+const AI_TOOLS: [AiTool; 3] = [AiTool::ClaudeCode, AiTool::OpenCode, AiTool::Gemini];
+
+/// Current phase of the sync service
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    NotStarted,
+    Reconciling,
+    Watching,
+    Failed,
+}
+
+/// Sync status exposed at GET /api/sync/status
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SyncStatus {
+    pub phase: SyncPhase,
+    pub started_at: Option<DateTime<Utc>>,
+    pub last_sync_at: Option<DateTime<Utc>>,
+    pub watched_roots: Vec<String>,
+    pub tracked_files: usize,
+    pub conversations_synced: usize,
+    pub messages_synced: usize,
+    pub sessions_removed: usize,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+}
+
+impl Default for SyncStatus {
+    fn default() -> Self {
+        Self {
+            phase: SyncPhase::NotStarted,
+            started_at: None,
+            last_sync_at: None,
+            watched_roots: Vec::new(),
+            tracked_files: 0,
+            conversations_synced: 0,
+            messages_synced: 0,
+            sessions_removed: 0,
+            last_error: None,
+            last_error_at: None,
+        }
+    }
+}
+
+static STATUS: Lazy<RwLock<SyncStatus>> = Lazy::new(|| RwLock::new(SyncStatus::default()));
+static STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Snapshot of the current sync status
+pub fn sync_status() -> SyncStatus {
+    STATUS.read().map(|s| s.clone()).unwrap_or_default()
+}
+
+fn update_status(f: impl FnOnce(&mut SyncStatus)) {
+    if let Ok(mut status) = STATUS.write() {
+        f(&mut status);
+    }
+}
+
+fn record_error(error: String) {
+    tracing::warn!("Sync error: {}", error);
+    update_status(|s| {
+        s.last_error = Some(error);
+        s.last_error_at = Some(Utc::now());
+    });
+}
+
+/// Start the background sync service on the current Tokio runtime.
+/// Calling it more than once is a no-op.
+pub fn start_sync_service() {
+    if STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    update_status(|s| s.started_at = Some(Utc::now()));
+
+    tokio::spawn(async {
+        if let Err(e) = run_sync_service().await {
+            record_error(e);
+            update_status(|s| s.phase = SyncPhase::Failed);
+        }
+    });
+}
+
+async fn run_sync_service() -> Result<(), String> {
+    // Truncated fork copies left by a crash are not sessions
+    if let Err(e) = tokio::task::spawn_blocking(remove_stale_copies).await {
+        tracing::warn!("Fork copy cleanup panicked: {}", e);
+    }
+
+    // Learn project paths first so Gemini hashes resolve during the import
+    match tokio::task::spawn_blocking(refresh_registry).await {
+        Ok(Err(e)) => tracing::warn!("Failed to refresh path registry: {}", e),
+        Err(e) => tracing::warn!("Path registry refresh panicked: {}", e),
+        Ok(Ok(_)) => {}
+    }
+
+    let db = init_database().await?;
+
+    update_status(|s| s.phase = SyncPhase::Reconciling);
+    reconcile(&db).await?;
+    tracing::info!("Startup sync reconciliation finished");
+
+    update_status(|s| s.phase = SyncPhase::Watching);
+    watch(&db).await
+}
+
+/// A provider session file on disk
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionFileRef {
+    pub ai_tool: AiTool,
+    pub encoded_name: String,
+    pub session_id: String,
+    pub file_path: PathBuf,
+}
+
+/// Map a changed path under a provider root to the session file it belongs to
+/// - Claude: `<projects>/<encoded>/<id>.jsonl`
+/// - OpenCode: `<storage>/session/global/ses_<id>.json` or `<storage>/message/ses_<id>/msg_*.json`
+/// - Gemini: `<tmp>/<hash>/chats/session-<id>.json`
+pub fn classify_session_path(ai_tool: &AiTool, projects_dir: &Path, path: &Path) -> Option<SessionFileRef> {
+    match ai_tool {
+        AiTool::ClaudeCode => {
+            let rel = path.strip_prefix(projects_dir).ok()?;
+            let parts: Vec<&str> = rel.iter().filter_map(|p| p.to_str()).collect();
+            match parts.as_slice() {
+                [encoded, file] if file.ends_with(".jsonl") => Some(SessionFileRef {
+                    ai_tool: ai_tool.clone(),
+                    encoded_name: encoded.to_string(),
+                    session_id: file.trim_end_matches(".jsonl").to_string(),
+                    file_path: path.to_path_buf(),
+                }),
+                _ => None,
+            }
+        }
+        AiTool::OpenCode => {
+            let storage = opencode_storage_dir(projects_dir);
+            let rel = path.strip_prefix(&storage).ok()?;
+            let parts: Vec<&str> = rel.iter().filter_map(|p| p.to_str()).collect();
+            let session_file = match parts.as_slice() {
+                ["session", "global", file] if file.starts_with("ses_") && file.ends_with(".json") => {
+                    file.to_string()
+                }
+                ["message", session_dir, file] if session_dir.starts_with("ses_") && file.starts_with("msg_") => {
+                    format!("{}.json", session_dir)
+                }
+                _ => return None,
+            };
+            Some(SessionFileRef {
+                ai_tool: ai_tool.clone(),
+                encoded_name: "global".to_string(),
+                session_id: extract_session_id(&session_file, "ses_", ".json"),
+                file_path: projects_dir.join(session_file),
+            })
+        }
+        AiTool::Gemini => {
+            let rel = path.strip_prefix(projects_dir).ok()?;
+            let parts: Vec<&str> = rel.iter().filter_map(|p| p.to_str()).collect();
+            match parts.as_slice() {
+                [hash, "chats", file] if file.starts_with("session-") && file.ends_with(".json") => Some(SessionFileRef {
+                    ai_tool: ai_tool.clone(),
+                    encoded_name: hash.to_string(),
+                    session_id: extract_session_id(file, "session-", ".json"),
+                    file_path: path.to_path_buf(),
+                }),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// OpenCode storage root (`storage/`), two levels above `storage/session/global`
+fn opencode_storage_dir(projects_dir: &Path) -> PathBuf {
+    projects_dir
+        .parent()
+        .and_then(|p| p.parent())
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| projects_dir.to_path_buf())
+}
+
+/// Directory to watch recursively for a provider
+fn watch_root(ai_tool: &AiTool, projects_dir: &Path) -> PathBuf {
+    match ai_tool {
+        AiTool::OpenCode => opencode_storage_dir(projects_dir),
+        _ => projects_dir.to_path_buf(),
+    }
+}
+
+/// List every session file of a provider
+fn list_session_files(ai_tool: &AiTool, projects_dir: &PathBuf) -> Vec<SessionFileRef> {
+    let folders = match list_tool_project_folders(ai_tool, projects_dir) {
+        Ok(folders) => folders,
+        Err(e) => {
+            tracing::warn!("Failed to list {:?} projects: {}", ai_tool, e);
+            return Vec::new();
+        }
+    };
+
+    let mut files = Vec::new();
+    for (_, folder) in folders {
+        let session_dir = match ai_tool {
+            AiTool::Gemini => folder.join("chats"),
+            _ => folder,
+        };
+        let Ok(entries) = std::fs::read_dir(&session_dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            if let Some(session) = classify_session_path(ai_tool, projects_dir, &entry.path())
+                .filter(|session| !is_fork_copy(&session.file_path))
+            {
+                files.push(session);
+            }
+        }
+    }
+    files
+}
+
+/// File fingerprint as (mtime in Unix ms, size in bytes).
+/// OpenCode stores messages outside the session file, so its message directory
+/// is folded in (directory mtime + number of message files).
+fn fingerprint(session: &SessionFileRef) -> Option<(i64, i64)> {
+    let metadata = std::fs::metadata(&session.file_path).ok()?;
+    let mut mtime = metadata.modified().ok().map(|t| DateTime::<Utc>::from(t).timestamp_millis())?;
+    let mut size = metadata.len() as i64;
+
+    if matches!(session.ai_tool, AiTool::OpenCode) {
+        let storage = session.file_path
+            .parent()
+            .map(opencode_storage_dir)
+            .unwrap_or_default();
+        let message_dir = storage.join("message").join(format!("ses_{}", session.session_id));
+        if let Ok(dir_meta) = std::fs::metadata(&message_dir) {
+            if let Ok(dir_mtime) = dir_meta.modified() {
+                mtime = mtime.max(DateTime::<Utc>::from(dir_mtime).timestamp_millis());
+            }
+            size += std::fs::read_dir(&message_dir).map(|e| e.count() as i64).unwrap_or(0);
+        }
+    }
+
+    Some((mtime, size))
+}
+
+/// Startup reconciliation: sync new/changed files and drop sessions whose file is gone
+async fn reconcile(db: &Surreal<Db>) -> Result<(), String> {
+    let mut known: HashMap<String, ((i64, i64), String)> = SyncStateRepository::find_all(db)
+        .await?
+        .into_iter()
+        .map(|s| (s.path, ((s.mtime, s.size), s.ai_tool)))
+        .collect();
+
+    let mut changed = Vec::new();
+    let mut tracked = 0;
+
+    for ai_tool in &AI_TOOLS {
+        for root in get_history_roots(ai_tool) {
+            let projects_dir = root.projects_dir();
+            if !projects_dir.exists() {
+                continue;
+            }
+
+            for session in list_session_files(ai_tool, &projects_dir) {
+                tracked += 1;
+                let key = session.file_path.to_string_lossy().to_string();
+                let previous = known.remove(&key).map(|(fingerprint, _)| fingerprint);
+                if previous.is_none() || previous != fingerprint(&session) {
+                    changed.push(session);
+                }
+            }
+        }
+    }
+
+    tracing::info!("Sync reconciliation: {} tracked files, {} changed, {} removed", tracked, changed.len(), known.len());
+    update_status(|s| s.tracked_files = tracked);
+
+    apply_changes(db, changed).await;
+
+    // Files that were synced before but no longer exist
+    for (path, (_, key)) in known {
+        match AI_TOOLS.iter().find(|ai_tool| ai_tool_key(ai_tool) == key) {
+            Some(ai_tool) => remove_session_file(db, ai_tool, &path).await,
+            None => record_error(format!("Unknown provider '{}' for {}", key, path)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Watch every provider root and sync changes as they happen
+async fn watch(db: &Surreal<Db>) -> Result<(), String> {
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<notify::Event>(1024);
+
+    let mut watcher = RecommendedWatcher::new(
+        move |res: Result<notify::Event, notify::Error>| match res {
+            Ok(event) => {
+                let _ = tx.blocking_send(event);
+            }
+            Err(e) => record_error(format!("Watcher error: {}", e)),
+        },
+        Config::default(),
+    )
+    .map_err(|e| format!("Failed to create file watcher: {}", e))?;
+
+    let mut roots = Vec::new();
+    for ai_tool in &AI_TOOLS {
+        for history_root in get_history_roots(ai_tool) {
+            let projects_dir = history_root.projects_dir();
+            let root = watch_root(ai_tool, &projects_dir);
+            if !root.exists() {
+                continue;
+            }
+            match watcher.watch(&root, RecursiveMode::Recursive) {
+                Ok(()) => roots.push((ai_tool.clone(), projects_dir, root)),
+                Err(e) => record_error(format!("Failed to watch {}: {}", root.display(), e)),
+            }
+        }
+    }
+
+    update_status(|s| {
+        s.watched_roots = roots.iter().map(|(_, _, root)| root.to_string_lossy().to_string()).collect();
+    });
+    tracing::info!("Sync watcher started on {} roots", roots.len());
+
+    while let Some(event) = rx.recv().await {
+        let mut events = vec![event];
+
+        // Batch bursts of events
+        tokio::time::sleep(DEBOUNCE).await;
+        while let Ok(event) = rx.try_recv() {
+            events.push(event);
+        }
+
+        let mut sessions: HashSet<SessionFileRef> = HashSet::new();
+        for event in events {
+            if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                continue;
+            }
+            for path in &event.paths {
+                for (ai_tool, projects_dir, root) in &roots {
+                    if !path.starts_with(root) {
+                        continue;
+                    }
+                    if let Some(session) = classify_session_path(ai_tool, projects_dir, path)
+                        .filter(|session| !is_fork_copy(&session.file_path))
+                    {
+                        sessions.insert(session);
+                    }
+                }
+            }
+        }
+
+        // Parsed conversations of changed files are stale, and so may be their project's path
+        let cache = conversation_cache();
+        for session in &sessions {
+            cache.invalidate_file(&session.file_path);
+            invalidate_project_path(&session.ai_tool, &session.encoded_name);
+        }
+
+        let (present, removed): (Vec<_>, Vec<_>) = sessions.into_iter().partition(|s| s.file_path.exists());
+
+        // Paths of new projects, so their Gemini hashes resolve too
+        let files: Vec<(AiTool, PathBuf)> = present
+            .iter()
+            .filter(|s| !matches!(s.ai_tool, AiTool::Gemini))
+            .map(|s| (s.ai_tool.clone(), s.file_path.clone()))
+            .collect();
+        if !files.is_empty() {
+            match tokio::task::spawn_blocking(move || learn_session_paths(&files)).await {
+                Ok(Err(e)) => record_error(e),
+                Err(e) => tracing::warn!("Path registry update panicked: {}", e),
+                Ok(Ok(_)) => {}
+            }
+        }
+
+        apply_changes(db, present).await;
+        for session in removed {
+            remove_session_file(db, &session.ai_tool, &session.file_path.to_string_lossy()).await;
+        }
+    }
+
+    Ok(())
+}
+
+/// Import changed sessions (grouped per project) and record their fingerprints
+async fn apply_changes(db: &Surreal<Db>, sessions: Vec<SessionFileRef>) {
+    if sessions.is_empty() {
+        return;
+    }
+
+    let mut by_project: HashMap<(String, String), (AiTool, Vec<SessionFileRef>)> = HashMap::new();
+    for session in sessions {
+        by_project
+            .entry((ai_tool_key(&session.ai_tool).to_string(), session.encoded_name.clone()))
+            .or_insert_with(|| (session.ai_tool.clone(), Vec::new()))
+            .1
+            .push(session);
+    }
+
+    for ((_, encoded_name), (ai_tool, sessions)) in by_project {
+        let session_ids: HashSet<String> = sessions.iter().map(|s| s.session_id.clone()).collect();
+
+        match sync_sessions(db, &ai_tool, &encoded_name, &session_ids).await {
+            Ok(stats) => {
+                for error in stats.errors {
+                    record_error(format!("{:?} {}: {}", ai_tool, encoded_name, error));
+                }
+                update_status(|s| {
+                    s.conversations_synced += stats.conversations_imported;
+                    s.messages_synced += stats.messages_imported;
+                    s.last_sync_at = Some(Utc::now());
+                });
+            }
+            Err(e) => {
+                record_error(format!("{:?} {}: {}", ai_tool, encoded_name, e));
+                continue;
+            }
+        }
+
+        for session in sessions {
+            let Some((mtime, size)) = fingerprint(&session) else {
+                continue;
+            };
+            let result = SyncStateRepository::upsert(db, CreateSyncState {
+                path: session.file_path.to_string_lossy().to_string(),
+                ai_tool: ai_tool_key(&ai_tool).to_string(),
+                encoded_name: encoded_name.clone(),
+                session_id: session.session_id,
+                mtime,
+                size,
+            })
+            .await;
+            if let Err(e) = result {
+                record_error(e);
+            }
+        }
+    }
+}
+
+/// Reflect a deleted session file of `ai_tool` in the database
+async fn remove_session_file(db: &Surreal<Db>, ai_tool: &AiTool, path: &str) {
+    let file_name = Path::new(path).file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let session_id = match ai_tool {
+        AiTool::ClaudeCode => file_name.trim_end_matches(".jsonl").to_string(),
+        AiTool::OpenCode => extract_session_id(file_name, "ses_", ".json"),
+        AiTool::Gemini => extract_session_id(file_name, "session-", ".json"),
+    };
+    let key = ai_tool_key(ai_tool);
+
+    // Another history root still holds a copy: keep the conversation, forget this file only
+    let other_copy = SyncStateRepository::find_by_session_id(db, &session_id)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .any(|state| state.ai_tool == key && state.path != path && Path::new(&state.path).exists());
+    if other_copy {
+        tracing::info!("Session file {} removed, {} still present in another root", path, session_id);
+        if let Err(e) = SyncStateRepository::delete_by_path(db, path).await {
+            record_error(e);
+        }
+        return;
+    }
+
+    tracing::info!("Session file removed, dropping {} from database", session_id);
+
+    if let Err(e) = ConversationRepository::hard_delete(db, &session_id, Some(key)).await {
+        record_error(e);
+        return;
+    }
+    if let Err(e) = SyncStateRepository::delete_by_path(db, path).await {
+        record_error(e);
+        return;
+    }
+
+    update_status(|s| {
+        s.sessions_removed += 1;
+        s.last_sync_at = Some(Utc::now());
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_claude_session() {
+        let root = PathBuf::from("/home/u/.claude/projects");
+        let path = root.join("-home-u-app").join("abc-123.jsonl");
+        let session = classify_session_path(&AiTool::ClaudeCode, &root, &path).unwrap();
+        assert_eq!(session.encoded_name, "-home-u-app");
+        assert_eq!(session.session_id, "abc-123");
+
+        // Nested or non-jsonl files are ignored
+        assert!(classify_session_path(&AiTool::ClaudeCode, &root, &root.join("-home-u-app/x.txt")).is_none());
+        assert!(classify_session_path(&AiTool::ClaudeCode, &root, &root.join("a/b/c.jsonl")).is_none());
+    }
+
+    #[test]
+    fn test_classify_opencode_message_maps_to_session() {
+        let root = PathBuf::from("/home/u/.local/share/opencode/storage/session/global");
+        let msg = PathBuf::from("/home/u/.local/share/opencode/storage/message/ses_42/msg_1.json");
+        let session = classify_session_path(&AiTool::OpenCode, &root, &msg).unwrap();
+        assert_eq!(session.encoded_name, "global");
+        assert_eq!(session.session_id, "42");
+        assert_eq!(session.file_path, root.join("ses_42.json"));
+
+        let direct = classify_session_path(&AiTool::OpenCode, &root, &root.join("ses_42.json")).unwrap();
+        assert_eq!(direct, session);
+    }
+
+    #[test]
+    fn test_classify_gemini_session() {
+        let root = PathBuf::from("/home/u/.gemini/tmp");
+        let path = root.join("abcdef0123").join("chats").join("session-2025-01-01.json");
+        let session = classify_session_path(&AiTool::Gemini, &root, &path).unwrap();
+        assert_eq!(session.encoded_name, "abcdef0123");
+        assert_eq!(session.session_id, "2025-01-01");
+        assert!(classify_session_path(&AiTool::Gemini, &root, &root.join("abcdef0123/logs.json")).is_none());
+    }
+}
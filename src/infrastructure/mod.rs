@@ -3,3 +3,11 @@ pub mod api;
 pub mod storage;
 
 pub mod database;
+
+pub mod trash;
+
+pub mod shares;
+
+pub mod auth;
+
+pub mod conversation_cache;
@@ -0,0 +1,372 @@
+//! Recycle bin for deleted sessions and projects
+//!
+//! Instead of removing history files, deletions move them into
+//! `~/.claude-viewer/trash/<id>/` together with a `meta.json` describing
+//! where every file came from, so they can be restored later.
+//!
+//! Layout:
+//! - `<id>/meta.json`: [`TrashItem`] metadata
+//! - `<id>/files/<n>`: moved files or directories
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::AiTool;
+
+/// How often the background purger runs
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+static PURGER_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// What was deleted
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    Session,
+    Project,
+}
+
+/// A moved file or directory and its original location
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub original_path: String,
+    pub stored_name: String,
+}
+
+/// Metadata of a trashed item (stored as meta.json)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashItem {
+    pub id: String,
+    pub kind: TrashKind,
+    pub ai_tool: AiTool,
+    pub encoded_name: String,
+    pub session_id: Option<String>,
+    pub label: String,
+    pub deleted_at: DateTime<Utc>,
+    pub entries: Vec<TrashEntry>,
+}
+
+/// Retention period from the config (None = never purge)
+pub fn retention_days() -> Option<i64> {
+    let days = crate::config::config().trash.retention_days;
+    (days > 0).then_some(days)
+}
+
+/// Recycle bin rooted at a directory
+pub struct Trash {
+    root: PathBuf,
+}
+
+impl Trash {
+    /// Open the recycle bin at a custom location (created if missing)
+    pub fn new(root: impl Into<PathBuf>) -> Result<Self, String> {
+        let root = root.into();
+        fs::create_dir_all(&root)
+            .map_err(|e| format!("Failed to create trash directory: {}", e))?;
+        Ok(Self { root })
+    }
+
+    /// Open the configured recycle bin (default: ~/.claude-viewer/trash/)
+    pub fn open_default() -> Result<Self, String> {
+        Self::new(crate::config::config().paths.trash_dir.clone())
+    }
+
+    /// Move files/directories into the trash as a single item
+    pub fn move_to_trash(
+        &self,
+        kind: TrashKind,
+        ai_tool: AiTool,
+        encoded_name: &str,
+        session_id: Option<&str>,
+        label: &str,
+        paths: &[PathBuf],
+    ) -> Result<TrashItem, String> {
+        let paths: Vec<&PathBuf> = paths.iter().filter(|p| p.exists()).collect();
+        if paths.is_empty() {
+            return Err("Nothing to move to trash".to_string());
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let item_dir = self.root.join(&id);
+        let files_dir = item_dir.join("files");
+        fs::create_dir_all(&files_dir)
+            .map_err(|e| format!("Failed to create trash item directory: {}", e))?;
+
+        let mut entries = Vec::new();
+        for (index, path) in paths.into_iter().enumerate() {
+            let stored_name = index.to_string();
+            if let Err(e) = move_path(path, &files_dir.join(&stored_name)) {
+                // Put back what was already moved so the deletion stays atomic
+                for entry in &entries {
+                    let _ = restore_entry(&files_dir, entry);
+                }
+                let _ = fs::remove_dir_all(&item_dir);
+                return Err(format!("Failed to move {} to trash: {}", path.display(), e));
+            }
+            entries.push(TrashEntry {
+                original_path: path.to_string_lossy().to_string(),
+                stored_name,
+            });
+        }
+
+        let item = TrashItem {
+            id,
+            kind,
+            ai_tool,
+            encoded_name: encoded_name.to_string(),
+            session_id: session_id.map(|s| s.to_string()),
+            label: label.to_string(),
+            deleted_at: Utc::now(),
+            entries,
+        };
+
+        let meta = serde_json::to_string_pretty(&item)
+            .map_err(|e| format!("Failed to serialize trash metadata: {}", e))?;
+        fs::write(item_dir.join("meta.json"), meta)
+            .map_err(|e| format!("Failed to write trash metadata: {}", e))?;
+
+        tracing::info!("Moved {:?} {} to trash ({})", item.kind, item.label, item.id);
+        Ok(item)
+    }
+
+    /// List trashed items, most recently deleted first
+    pub fn list(&self) -> Result<Vec<TrashItem>, String> {
+        let entries = fs::read_dir(&self.root)
+            .map_err(|e| format!("Failed to read trash directory: {}", e))?;
+
+        let mut items: Vec<TrashItem> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let meta = fs::read_to_string(entry.path().join("meta.json")).ok()?;
+                serde_json::from_str(&meta).ok()
+            })
+            .collect();
+
+        items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+        Ok(items)
+    }
+
+    /// Get a single trashed item
+    pub fn get(&self, id: &str) -> Result<TrashItem, String> {
+        let item_dir = self.item_dir(id)?;
+        let meta = fs::read_to_string(item_dir.join("meta.json"))
+            .map_err(|_| format!("Trash item not found: {}", id))?;
+
+        serde_json::from_str(&meta)
+            .map_err(|e| format!("Invalid trash metadata for {}: {}", id, e))
+    }
+
+    /// Move an item back to its original location
+    /// Fails without touching anything if one of the original paths exists again.
+    pub fn restore(&self, id: &str) -> Result<TrashItem, String> {
+        let item = self.get(id)?;
+        let item_dir = self.item_dir(id)?;
+        let files_dir = item_dir.join("files");
+
+        if let Some(conflict) = item.entries.iter().find(|e| Path::new(&e.original_path).exists()) {
+            return Err(format!("Cannot restore, path already exists: {}", conflict.original_path));
+        }
+
+        for entry in &item.entries {
+            restore_entry(&files_dir, entry)?;
+        }
+
+        fs::remove_dir_all(&item_dir)
+            .map_err(|e| format!("Failed to clean up trash item: {}", e))?;
+
+        tracing::info!("Restored {:?} {} from trash ({})", item.kind, item.label, item.id);
+        Ok(item)
+    }
+
+    /// Permanently delete an item
+    pub fn purge(&self, id: &str) -> Result<(), String> {
+        let item_dir = self.item_dir(id)?;
+        if !item_dir.exists() {
+            return Err(format!("Trash item not found: {}", id));
+        }
+
+        fs::remove_dir_all(&item_dir)
+            .map_err(|e| format!("Failed to purge trash item: {}", e))?;
+
+        tracing::info!("Purged trash item {}", id);
+        Ok(())
+    }
+
+    /// Permanently delete every item
+    pub fn purge_all(&self) -> Result<usize, String> {
+        let items = self.list()?;
+        for item in &items {
+            self.purge(&item.id)?;
+        }
+        Ok(items.len())
+    }
+
+    /// Permanently delete items older than `retention_days`
+    pub fn purge_expired(&self, retention_days: i64, now: DateTime<Utc>) -> Result<usize, String> {
+        let cutoff = now - chrono::Duration::days(retention_days);
+        let mut purged = 0;
+
+        for item in self.list()? {
+            if item.deleted_at < cutoff {
+                self.purge(&item.id)?;
+                purged += 1;
+            }
+        }
+
+        Ok(purged)
+    }
+
+    /// Resolve an item directory, rejecting anything that is not a plain ID
+    fn item_dir(&self, id: &str) -> Result<PathBuf, String> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(format!("Invalid trash item id: {}", id));
+        }
+        Ok(self.root.join(id))
+    }
+}
+
+/// Start the background purger on the current Tokio runtime
+/// Runs once at startup, then every hour, using [`retention_days`].
+/// Calling it more than once is a no-op.
+pub fn start_trash_purger() {
+    if PURGER_STARTED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let Some(days) = retention_days() else {
+                continue;
+            };
+
+            match Trash::open_default().and_then(|trash| trash.purge_expired(days, Utc::now())) {
+                Ok(0) => {}
+                Ok(count) => tracing::info!("Purged {} expired trash items (retention: {} days)", count, days),
+                Err(e) => tracing::warn!("Failed to purge expired trash items: {}", e),
+            }
+        }
+    });
+}
+
+fn restore_entry(files_dir: &Path, entry: &TrashEntry) -> Result<(), String> {
+    let original = Path::new(&entry.original_path);
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to recreate {}: {}", parent.display(), e))?;
+    }
+
+    move_path(&files_dir.join(&entry.stored_name), original)
+        .map_err(|e| format!("Failed to restore {}: {}", entry.original_path, e))
+}
+
+/// Rename, falling back to copy + delete across filesystems
+fn move_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+
+    copy_recursive(from, to)?;
+    if from.is_dir() {
+        fs::remove_dir_all(from)
+    } else {
+        fs::remove_file(from)
+    }
+}
+
+fn copy_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("trash-test-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_move_and_restore_session() {
+        let base = temp_dir("restore");
+        let trash = Trash::new(base.join("trash")).unwrap();
+        let session = base.join("project").join("abc.jsonl");
+        fs::create_dir_all(session.parent().unwrap()).unwrap();
+        fs::write(&session, "{}\n").unwrap();
+
+        let item = trash
+            .move_to_trash(TrashKind::Session, AiTool::ClaudeCode, "project", Some("abc"), "abc", &[session.clone()])
+            .unwrap();
+        assert!(!session.exists());
+        assert_eq!(trash.list().unwrap().len(), 1);
+
+        trash.restore(&item.id).unwrap();
+        assert_eq!(fs::read_to_string(&session).unwrap(), "{}\n");
+        assert!(trash.list().unwrap().is_empty());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_restore_refuses_to_overwrite() {
+        let base = temp_dir("conflict");
+        let trash = Trash::new(base.join("trash")).unwrap();
+        let project = base.join("project");
+        fs::create_dir_all(&project).unwrap();
+
+        let item = trash
+            .move_to_trash(TrashKind::Project, AiTool::Gemini, "project", None, "project", &[project.clone()])
+            .unwrap();
+        fs::create_dir_all(&project).unwrap();
+
+        assert!(trash.restore(&item.id).is_err());
+        assert_eq!(trash.list().unwrap().len(), 1);
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let base = temp_dir("purge");
+        let trash = Trash::new(base.join("trash")).unwrap();
+        let file = base.join("old.jsonl");
+        fs::write(&file, "").unwrap();
+        trash
+            .move_to_trash(TrashKind::Session, AiTool::ClaudeCode, "p", Some("old"), "old", &[file])
+            .unwrap();
+
+        assert_eq!(trash.purge_expired(30, Utc::now()).unwrap(), 0);
+        let later = Utc::now() + chrono::Duration::days(31);
+        assert_eq!(trash.purge_expired(30, later).unwrap(), 1);
+        assert!(trash.list().unwrap().is_empty());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_path_like_ids() {
+        let base = temp_dir("ids");
+        let trash = Trash::new(base.join("trash")).unwrap();
+        assert!(trash.purge("../project").is_err());
+        assert!(trash.get("a/b").is_err());
+        fs::remove_dir_all(&base).unwrap();
+    }
+}
@@ -18,3 +18,5 @@ pub mod handlers;
 pub mod history;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod cli;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod client;
@@ -8,9 +8,6 @@ use cli_session_hub_viewer::app::App;
 // Server entry point - NO #[tokio::main], dioxus::serve() creates its own runtime
 #[cfg(feature = "server")]
 fn main() {
-    // IMPORTANT: Use dioxus::server::axum, NOT axum directly
-    use dioxus::server::axum::{routing::{delete, get, post}, Extension, extract::DefaultBodyLimit};
-
     // Set panic hook to print full backtrace
     std::panic::set_hook(Box::new(|panic_info| {
         let backtrace = std::backtrace::Backtrace::force_capture();
@@ -30,27 +27,33 @@ fn main() {
 
     tracing::info!("Starting CLI Session Hub Viewer...");
 
-    use cli_session_hub_viewer::handlers::{
-        // Projects & Histories (no state required)
-        list_projects_handler,
-        list_histories_handler,
-        get_active_session_handler,
-        // Conversation details
-        get_conversation_handler,
-        // Chat
-        chat_handler,
-        chat_status_handler,
-        ChatHandlerState,
-        // Abort & Sessions
-        abort_handler,
-        active_processes_handler,
-        delete_session_handler,
-        // SSE for real-time sync
-        sse_session_handler,
+    use cli_session_hub_viewer::handlers::{build_router, ChatHandlerState, RouterOptions};
+    use cli_session_hub_viewer::config::init_config;
+    use cli_session_hub_viewer::infrastructure::auth::init_auth;
+    use cli_session_hub_viewer::infrastructure::database::start_sync_service;
+    use cli_session_hub_viewer::infrastructure::trash::start_trash_purger;
+
+    // Load config file + env overrides, fail fast on invalid values
+    let config = match init_config() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Invalid configuration: {}", e);
+            std::process::exit(1);
+        }
     };
 
-    // Image upload handlers
-    use cli_session_hub_viewer::infrastructure::api::upload::{upload_image, get_image, debug_upload};
+    // dioxus::serve binds to IP/PORT; `dx serve` sets them, otherwise use the config
+    if std::env::var("IP").is_err() {
+        // SAFETY: still single-threaded, before the runtime is started
+        unsafe { std::env::set_var("IP", config.server.host.to_string()) };
+    }
+    if std::env::var("PORT").is_err() {
+        // SAFETY: still single-threaded, before the runtime is started
+        unsafe { std::env::set_var("PORT", config.server.port.to_string()) };
+    }
+
+    // Load credentials early so a generated token shows up in the startup logs
+    init_auth();
 
     // NO #[tokio::main] - dioxus::serve creates its own runtime
     dioxus::serve(|| {
@@ -58,46 +61,22 @@ fn main() {
             // Async initialization inside the closure
             let chat_state_result = ChatHandlerState::new().await;
 
-            // Get the base Dioxus router
-            // NOTE: Axum 0.8 uses {param} syntax instead of :param
-            let mut router = dioxus::server::router(App)
-                // Projects & Histories routes (stateless)
-                .route("/api/projects", get(list_projects_handler))
-                .route("/api/projects/{encoded_name}/histories", get(list_histories_handler))
-                .route("/api/projects/{encoded_name}/active-session", get(get_active_session_handler))
-                // Conversation details (stateless)
-                .route("/api/projects/{encoded_name}/histories/{session_id}", get(get_conversation_handler))
-                // SSE endpoint for real-time CLI → Web sync
-                .route("/api/sse/{encoded_name}/{session_id}", get(sse_session_handler))
-                // Image upload routes - with increased body limit (10MB for images)
-                .route("/api/upload", post(upload_image))
-                .route("/api/upload-debug", post(debug_upload))  // Debug endpoint without Multipart extractor
-                .route("/api/images/{image_id}", get(get_image))
-                .layer(DefaultBodyLimit::max(10 * 1024 * 1024));  // 10MB = MAX_IMAGE_SIZE
+            // Keep SurrealDB in sync with history files (no-op if already running)
+            start_sync_service();
+
+            // Purge recycle bin items past their retention period
+            start_trash_purger();
 
-            // Add chat routes only if chat state was initialized successfully
-            match chat_state_result {
-                Ok(chat_state) => {
-                    tracing::info!("Chat handler state initialized successfully - chat features enabled");
-                    router = router
-                        // Chat routes (with state via Extension)
-                        .route("/api/chat/native", post(chat_handler))
-                        .route("/api/chat/status", get(chat_status_handler))
-                        .route("/api/abort/{request_id}", post(abort_handler))
-                        .route("/api/sessions/active", get(active_processes_handler))
-                        .route("/api/sessions/{session_id}", delete(delete_session_handler))
-                        // Add chat state as Extension
-                        .layer(Extension(chat_state));
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        "Failed to initialize chat state: {}. Chat features disabled, but history viewing works.",
-                        e
-                    );
-                }
-            }
+            // Chat routes answer 503 if the Claude CLI is missing; history viewing still works
+            let chat = chat_state_result
+                .map_err(|e| tracing::warn!("Failed to initialize chat state: {}", e))
+                .ok();
 
-            Ok(router)
+            // Same API as the standalone server, on top of the Dioxus app router
+            Ok(build_router(
+                dioxus::server::router(App),
+                RouterOptions { chat, ..RouterOptions::default() },
+            ))
         }
     });
 }
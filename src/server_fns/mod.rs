@@ -5,68 +5,7 @@ use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::domain::models::AiTool;
-
-// ============================================================
-// Session Cache (server-side only)
-// ============================================================
-mod cache {
-    use dashmap::DashMap;
-    use once_cell::sync::Lazy;
-    use std::time::{Duration, Instant};
-
-    /// Cached conversation with timestamp for TTL
-    pub struct CachedConversation {
-        pub conversation: crate::domain::models::Conversation,
-        pub cached_at: Instant,
-    }
-
-    /// Cache key: (encoded_project_name, session_id)
-    pub type CacheKey = (String, String);
-
-    /// Global cache for conversations (thread-safe)
-    /// TTL: 5 minutes - after that, re-read from disk to get updates
-    pub static CONVERSATION_CACHE: Lazy<DashMap<CacheKey, CachedConversation>> =
-        Lazy::new(DashMap::new);
-
-    /// Cache TTL: 5 minutes
-    pub const CACHE_TTL: Duration = Duration::from_secs(300);
-
-    /// Get from cache if not expired
-    #[allow(dead_code)]
-    pub fn get_cached(
-        encoded_name: &str,
-        session_id: &str,
-    ) -> Option<crate::domain::models::Conversation> {
-        let key = (encoded_name.to_string(), session_id.to_string());
-        if let Some(entry) = CONVERSATION_CACHE.get(&key) {
-            if entry.cached_at.elapsed() < CACHE_TTL {
-                return Some(entry.conversation.clone());
-            } else {
-                // Expired, remove from cache
-                drop(entry);
-                CONVERSATION_CACHE.remove(&key);
-            }
-        }
-        None
-    }
-
-    /// Insert into cache
-    #[allow(dead_code)]
-    pub fn set_cached(
-        encoded_name: &str,
-        session_id: &str,
-        conversation: crate::domain::models::Conversation,
-    ) {
-        let key = (encoded_name.to_string(), session_id.to_string());
-        CONVERSATION_CACHE.insert(
-            key,
-            CachedConversation {
-                conversation,
-                cached_at: Instant::now(),
-            },
-        );
-    }
-}
+use crate::domain::services::redaction::RedactionRule;
 
 /// Response type for project listing
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -77,6 +16,9 @@ pub struct ProjectResponse {
     pub ai_tool: AiTool,
     pub encoded_name: String,
     pub last_updated: String,
+    /// Labels of the history roots holding this project
+    #[serde(default)]
+    pub roots: Vec<String>,
 }
 
 /// Response type for conversation history
@@ -122,6 +64,12 @@ pub struct SessionSummaryResponse {
     pub message_count: usize,
     pub preview: String,
     pub updated_at: String,
+    /// Session this one was forked from
+    #[serde(default)]
+    pub parent_session_id: Option<String>,
+    /// Index of the parent message the fork starts from (0-based)
+    #[serde(default)]
+    pub parent_message: Option<usize>,
 }
 
 /// Get a single project by encoded_name
@@ -154,11 +102,62 @@ pub async fn get_project(encoded_name: String, tool_slug: Option<String>) -> Res
             ai_tool: p.ai_tool,
             encoded_name: p.encoded_name,
             last_updated: p.last_updated.to_rfc3339(),
+            roots: p.roots,
         });
 
     Ok(project)
 }
 
+/// Real filesystem path of a project, read from its session files (None = unknown)
+/// Used as working directory for the chat; the encoded name is never decoded.
+#[server]
+pub async fn get_project_working_directory(encoded_name: String, tool_slug: Option<String>) -> Result<Option<String>, ServerFnError> {
+    use crate::history::project_identity::{resolve_any_project_path, resolve_project_path};
+    use crate::domain::models::AiTool;
+
+    let target_tool = tool_slug.as_deref().and_then(|slug| match slug {
+        "claude" => Some(AiTool::ClaudeCode),
+        "opencode" => Some(AiTool::OpenCode),
+        "gemini" => Some(AiTool::Gemini),
+        _ => None,
+    });
+
+    Ok(match target_tool {
+        Some(tool) => resolve_project_path(&tool, &encoded_name),
+        None => resolve_any_project_path(&encoded_name).map(|(_, path)| path),
+    })
+}
+
+/// Known directory of a Gemini project hash (None = unresolved)
+#[server]
+pub async fn get_gemini_project_path(hash: String) -> Result<Option<String>, ServerFnError> {
+    use crate::history::path_registry::resolve_gemini_hash;
+
+    Ok(resolve_gemini_hash(&hash))
+}
+
+/// Map an unresolved Gemini project hash to a directory chosen by the user
+/// Returns the resolved project name.
+#[server]
+pub async fn map_gemini_project(hash: String, path: String) -> Result<String, ServerFnError> {
+    use crate::history::path_registry::map_gemini_hash;
+    use crate::history::path_utils::decode_gemini_hash;
+
+    tracing::info!("map_gemini_project called: hash='{}', path='{}'", hash, path);
+
+    map_gemini_hash(&hash, &path).map_err(ServerFnError::new)?;
+    let (_, name) = decode_gemini_hash(&hash);
+
+    Ok(name)
+}
+
+/// Custom redaction rules of the server configuration
+/// The built-in detectors are compiled into the client as well.
+#[server]
+pub async fn get_redaction_rules() -> Result<Vec<RedactionRule>, ServerFnError> {
+    Ok(crate::config::config().redaction.rules.clone())
+}
+
 /// List all projects from Claude history
 /// tool_filter: Optional tool slug ("claude", "opencode", "gemini") to filter projects
 #[server]
@@ -178,6 +177,7 @@ pub async fn get_projects(search: Option<String>, tool_filter: Option<String>) -
             ai_tool: p.ai_tool,
             encoded_name: p.encoded_name,
             last_updated: p.last_updated.to_rfc3339(),
+            roots: p.roots,
         })
         .collect();
 
@@ -217,6 +217,7 @@ pub async fn get_sessions_summaries(
     encoded_name: String,
     tool_slug: Option<String>,
 ) -> Result<Vec<SessionSummaryResponse>, ServerFnError> {
+    use crate::history::forks::fork_of;
     use crate::history::list_project_summaries_for_tool;
 
     println!(">>> DEBUG get_sessions_summaries called: encoded_name={}, tool_slug={:?}", encoded_name, tool_slug);
@@ -229,11 +230,16 @@ pub async fn get_sessions_summaries(
 
     let mut results: Vec<SessionSummaryResponse> = summaries
         .into_iter()
-        .map(|s| SessionSummaryResponse {
-            session_id: s.session_id,
-            message_count: s.message_count,
-            preview: s.last_message_preview,
-            updated_at: s.last_time,
+        .map(|s| {
+            let fork = fork_of(&s.session_id);
+            SessionSummaryResponse {
+                parent_session_id: fork.as_ref().map(|f| f.parent_session_id.clone()),
+                parent_message: fork.map(|f| f.parent_message),
+                session_id: s.session_id,
+                message_count: s.message_count,
+                preview: s.last_message_preview,
+                updated_at: s.last_time,
+            }
         })
         .collect();
 
@@ -246,71 +252,83 @@ pub async fn get_sessions_summaries(
 /// List conversation histories for a specific project
 #[server]
 pub async fn get_histories(encoded_name: String) -> Result<Vec<HistoryResponse>, ServerFnError> {
-    use crate::history::{list_project_histories, ContentBlock};
+    use crate::history::list_project_histories;
 
     let histories =
         list_project_histories(&encoded_name).map_err(|e| ServerFnError::new(e))?;
 
-    let results: Vec<HistoryResponse> = histories
-        .into_iter()
-        .map(|h| HistoryResponse {
-            session_id: h.session_id,
-            project_path: h.project_path,
-            project_name: h.project_name,
-            messages: h
-                .messages
-                .into_iter()
-                .map(|m| MessageResponse {
-                    role: m.role,
-                    content: m
-                        .content
-                        .into_iter()
-                        .map(|c| match c {
-                            ContentBlock::Text { text } => ContentBlockResponse::Text { text },
-                            ContentBlock::ToolUse {
-                                tool_type: _,
-                                name,
-                                input,
-                            } => ContentBlockResponse::ToolUse { name, input },
-                            ContentBlock::ToolResult {
-                                result_type: _,
-                                content,
-                            } => ContentBlockResponse::ToolResult { content },
-                        })
-                        .collect(),
-                    timestamp: m.timestamp,
-                })
-                .collect(),
-            created_at: h.created_at.to_rfc3339(),
-            updated_at: h.updated_at.to_rfc3339(),
-            message_count: h.message_count,
-        })
-        .collect();
-
-    Ok(results)
+    Ok(histories.into_iter().map(history_response).collect())
 }
 
 /// Get a single conversation history by session ID
+/// Parses only the requested session file (every provider, every history root)
 #[server]
 pub async fn get_history(
     encoded_name: String,
     session_id: String,
 ) -> Result<Option<HistoryResponse>, ServerFnError> {
-    let histories = get_histories(encoded_name).await?;
+    use crate::history::load_conversation;
+
+    match load_conversation(&encoded_name, &session_id) {
+        Ok(history) => Ok(Some(history_response(history))),
+        Err(e) => {
+            tracing::warn!("Session not found: {} - {}", session_id, e);
+            Ok(None)
+        }
+    }
+}
 
-    Ok(histories.into_iter().find(|h| h.session_id == session_id))
+/// Convert a parsed history into its API response
+#[cfg(feature = "server")]
+fn history_response(h: crate::history::ConversationHistory) -> HistoryResponse {
+    use crate::history::ContentBlock;
+
+    HistoryResponse {
+        session_id: h.session_id,
+        project_path: h.project_path,
+        project_name: h.project_name,
+        messages: h
+            .messages
+            .into_iter()
+            .map(|m| MessageResponse {
+                role: m.role,
+                content: m
+                    .content
+                    .into_iter()
+                    .map(|c| match c {
+                        ContentBlock::Text { text } => ContentBlockResponse::Text { text },
+                        ContentBlock::ToolUse {
+                            tool_type: _,
+                            name,
+                            input,
+                        } => ContentBlockResponse::ToolUse { name, input },
+                        ContentBlock::ToolResult {
+                            result_type: _,
+                            content,
+                        } => ContentBlockResponse::ToolResult { content },
+                    })
+                    .collect(),
+                timestamp: m.timestamp,
+            })
+            .collect(),
+        created_at: h.created_at.to_rfc3339(),
+        updated_at: h.updated_at.to_rfc3339(),
+        message_count: h.message_count,
+    }
 }
 
-/// Delete a session file from disk
-/// Supports all AI tools: Claude (.jsonl), OpenCode (ses_*.json), Gemini (chats/session-*.json)
+/// Move a session to the recycle bin (~/.claude-viewer/trash)
+/// Supports all AI tools: Claude (.jsonl), OpenCode (ses_*.json + message/part dirs), Gemini (chats/session-*.json)
+/// Every target is resolved through the path guard and must stay inside a history root of the provider.
+/// Copies in every configured root are trashed together; a copy in a read-only root refuses the whole deletion.
 #[server]
 pub async fn delete_session(
     encoded_name: String,
     session_id: String,
 ) -> Result<bool, ServerFnError> {
-    use crate::history::path_utils::get_projects_dir;
+    use crate::history::path_guard::{ensure_writable, resolve_in_history_roots, validate_segment};
     use crate::domain::models::AiTool;
-    use std::fs;
+    use crate::infrastructure::trash::{Trash, TrashKind};
 
     tracing::info!(
         "delete_session called: encoded_name='{}', session_id='{}'",
@@ -318,47 +336,64 @@ pub async fn delete_session(
         session_id
     );
 
-    // Try all AI tool directories to find and delete the session
+    validate_segment(&encoded_name)
+        .and_then(|_| validate_segment(&session_id))
+        .map_err(|e| ServerFnError::new(format!("Refused to delete session: {}", e)))?;
+
+    // Try all AI tool directories to find and trash the session
     let ai_tools = vec![AiTool::ClaudeCode, AiTool::OpenCode, AiTool::Gemini];
 
     for ai_tool in &ai_tools {
-        let projects_dir = match get_projects_dir(ai_tool) {
-            Ok(dir) => dir,
-            Err(_) => continue,
+        // Different file patterns per AI tool
+        let file_name = match ai_tool {
+            AiTool::ClaudeCode => format!("{}.jsonl", session_id),
+            AiTool::OpenCode => format!("ses_{}.json", session_id),
+            AiTool::Gemini => format!("session-{}.json", session_id),
+        };
+        let segments: Vec<&str> = match ai_tool {
+            AiTool::Gemini => vec![&encoded_name, "chats", &file_name],
+            _ => vec![&encoded_name, &file_name],
         };
 
-        let project_dir = projects_dir.join(&encoded_name);
-        if !project_dir.exists() {
+        let refused = |e: crate::history::path_guard::PathGuardError| {
+            ServerFnError::new(format!("Refused to delete session: {}", e))
+        };
+        let found = resolve_in_history_roots(ai_tool, &segments).map_err(refused)?;
+        if found.is_empty() {
             continue;
         }
+        for (root, _) in &found {
+            ensure_writable(root).map_err(refused)?;
+        }
 
-        // Different file patterns per AI tool
-        let file_path = match ai_tool {
-            AiTool::ClaudeCode => project_dir.join(format!("{}.jsonl", session_id)),
-            AiTool::OpenCode => project_dir.join(format!("ses_{}.json", session_id)),
-            AiTool::Gemini => project_dir.join("chats").join(format!("session-{}.json", session_id)),
-        };
+        let mut paths = Vec::new();
+        for (root, file_path) in found {
+            tracing::info!("Found session file to trash in root '{}': {}", root.label, file_path.display());
+            paths.push(file_path);
+            if matches!(ai_tool, AiTool::OpenCode) {
+                paths.extend(opencode_session_storage_paths(&root.path.join("storage"), &session_id));
+            }
+        }
 
-        if file_path.exists() {
-            tracing::info!("Found session file to delete: {}", file_path.display());
+        let short_id = &session_id[..8.min(session_id.len())];
+        let label = format!("Session {} ({})", short_id, encoded_name);
 
-            // Delete the file
-            match fs::remove_file(&file_path) {
-                Ok(_) => {
-                    tracing::info!("Successfully deleted session: {}", session_id);
+        let trash = Trash::open_default().map_err(ServerFnError::new)?;
+        return match trash.move_to_trash(TrashKind::Session, ai_tool.clone(), &encoded_name, Some(&session_id), &label, &paths) {
+            Ok(_) => {
+                tracing::info!("Successfully moved session to trash: {}", session_id);
 
-                    // Invalidate cache for this session
-                    let cache_key = (encoded_name.clone(), session_id.clone());
-                    cache::CONVERSATION_CACHE.remove(&cache_key);
+                // Invalidate cache for this session
+                crate::infrastructure::conversation_cache::conversation_cache()
+                    .invalidate_session(&encoded_name, &session_id);
 
-                    return Ok(true);
-                }
-                Err(e) => {
-                    tracing::error!("Failed to delete session file: {}", e);
-                    return Err(ServerFnError::new(format!("Failed to delete session: {}", e)));
-                }
+                Ok(true)
             }
-        }
+            Err(e) => {
+                tracing::error!("Failed to trash session file: {}", e);
+                Err(ServerFnError::new(format!("Failed to delete session: {}", e)))
+            }
+        };
     }
 
     // Session not found
@@ -366,43 +401,88 @@ pub async fn delete_session(
     Err(ServerFnError::new(format!("Session not found: {}", session_id)))
 }
 
-/// Delete a project (all sessions in the project directory)
+/// OpenCode keeps messages and parts outside the session file:
+/// storage/message/ses_{id}/ and storage/part/msg_{message_id}/
+/// Only paths that resolve inside the given storage root are returned.
+#[cfg(feature = "server")]
+fn opencode_session_storage_paths(storage: &std::path::Path, session_id: &str) -> Vec<std::path::PathBuf> {
+    use crate::history::path_guard::resolve_existing;
+
+    let Ok(message_dir) = resolve_existing(storage, &["message", &format!("ses_{}", session_id)]) else {
+        return Vec::new();
+    };
+
+    let mut paths = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(&message_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let part_dir = name
+                .strip_suffix(".json")
+                .filter(|n| n.starts_with("msg_"))
+                .and_then(|message_id| resolve_existing(storage, &["part", message_id]).ok());
+            paths.extend(part_dir);
+        }
+    }
+    paths.push(message_dir);
+
+    paths
+}
+
+/// Move a project (all sessions in the project directory) to the recycle bin
+/// The project directory is resolved through the path guard and must stay inside a history root of the provider.
+/// Copies in every configured root are trashed together; a copy in a read-only root refuses the whole deletion.
 #[server]
 pub async fn delete_project(encoded_name: String) -> Result<bool, ServerFnError> {
-    use crate::history::path_utils::get_projects_dir;
+    use crate::history::path_guard::{ensure_writable, resolve_in_history_roots, validate_segment};
     use crate::domain::models::AiTool;
-    use std::fs;
+    use crate::infrastructure::trash::{Trash, TrashKind};
 
     tracing::info!("delete_project called: encoded_name='{}'", encoded_name);
 
-    // Try all AI tool directories to find and delete the project
+    validate_segment(&encoded_name)
+        .map_err(|e| ServerFnError::new(format!("Refused to delete project: {}", e)))?;
+
+    // Try all AI tool directories to find and trash the project
     let ai_tools = vec![AiTool::ClaudeCode, AiTool::OpenCode, AiTool::Gemini];
     let mut deleted = false;
 
     for ai_tool in &ai_tools {
-        let projects_dir = match get_projects_dir(ai_tool) {
-            Ok(dir) => dir,
-            Err(_) => continue,
-        };
-
-        let project_dir = projects_dir.join(&encoded_name);
-        if !project_dir.exists() {
+        let found: Vec<_> = resolve_in_history_roots(ai_tool, &[&encoded_name])
+            .map_err(|e| ServerFnError::new(format!("Refused to delete project: {}", e)))?
+            .into_iter()
+            .filter(|(_, path)| path.is_dir())
+            .collect();
+        if found.is_empty() {
             continue;
         }
+        for (root, _) in &found {
+            ensure_writable(root)
+                .map_err(|e| ServerFnError::new(format!("Refused to delete project: {}", e)))?;
+        }
+
+        let project_dirs: Vec<_> = found
+            .into_iter()
+            .map(|(root, dir)| {
+                tracing::info!("Found project directory to trash in root '{}': {}", root.label, dir.display());
+                dir
+            })
+            .collect();
 
-        tracing::info!("Found project directory to delete: {}", project_dir.display());
+        let trash = Trash::open_default().map_err(ServerFnError::new)?;
+        let label = format!("Project {}", encoded_name);
 
-        // Delete the entire project directory
-        match fs::remove_dir_all(&project_dir) {
+        // Move the entire project directory
+        match trash.move_to_trash(TrashKind::Project, ai_tool.clone(), &encoded_name, None, &label, &project_dirs) {
             Ok(_) => {
-                tracing::info!("Successfully deleted project: {}", encoded_name);
+                tracing::info!("Successfully moved project to trash: {}", encoded_name);
                 deleted = true;
 
                 // Invalidate all cache entries for this project
-                cache::CONVERSATION_CACHE.retain(|key, _| key.0 != encoded_name);
+                crate::infrastructure::conversation_cache::conversation_cache()
+                    .invalidate_project(&encoded_name);
             }
             Err(e) => {
-                tracing::error!("Failed to delete project directory: {}", e);
+                tracing::error!("Failed to trash project directory: {}", e);
                 return Err(ServerFnError::new(format!("Failed to delete project: {}", e)));
             }
         }
@@ -416,18 +496,131 @@ pub async fn delete_project(encoded_name: String) -> Result<bool, ServerFnError>
     }
 }
 
+/// Response type for recycle bin items
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrashItemResponse {
+    pub id: String,
+    /// "session" or "project"
+    pub kind: String,
+    pub ai_tool: AiTool,
+    pub encoded_name: String,
+    pub session_id: Option<String>,
+    pub label: String,
+    pub original_paths: Vec<String>,
+    pub deleted_at: String,
+    /// When the item will be purged automatically (None = kept forever)
+    pub expires_at: Option<String>,
+}
+
+/// List items in the recycle bin (most recent first)
+#[server]
+pub async fn list_trash() -> Result<Vec<TrashItemResponse>, ServerFnError> {
+    use crate::infrastructure::trash::{retention_days, Trash, TrashKind};
+
+    let trash = Trash::open_default().map_err(ServerFnError::new)?;
+    let items = trash.list().map_err(ServerFnError::new)?;
+    let retention = retention_days();
+
+    Ok(items
+        .into_iter()
+        .map(|item| TrashItemResponse {
+            id: item.id,
+            kind: match item.kind {
+                TrashKind::Session => "session".to_string(),
+                TrashKind::Project => "project".to_string(),
+            },
+            ai_tool: item.ai_tool,
+            encoded_name: item.encoded_name,
+            session_id: item.session_id,
+            label: item.label,
+            original_paths: item.entries.into_iter().map(|e| e.original_path).collect(),
+            deleted_at: item.deleted_at.to_rfc3339(),
+            expires_at: retention.map(|days| (item.deleted_at + chrono::Duration::days(days)).to_rfc3339()),
+        })
+        .collect())
+}
+
+/// Restore a recycle bin item to its original location
+#[server]
+pub async fn restore_trash_item(id: String) -> Result<bool, ServerFnError> {
+    use crate::history::path_guard::{ensure_within, ensure_writable};
+    use crate::history::path_utils::get_history_roots;
+    use crate::domain::models::AiTool;
+    use crate::infrastructure::trash::Trash;
+
+    let trash = Trash::open_default().map_err(ServerFnError::new)?;
+
+    // Only write back inside a writable history root of the provider (OpenCode: its whole storage dir)
+    let pending = trash.get(&id).map_err(ServerFnError::new)?;
+    let roots = get_history_roots(&pending.ai_tool);
+    for entry in &pending.entries {
+        let original = std::path::Path::new(&entry.original_path);
+        let root = roots.iter().find(|root| {
+            let base = match pending.ai_tool {
+                AiTool::OpenCode => root.path.join("storage"),
+                _ => root.projects_dir(),
+            };
+            ensure_within(&base, original).is_ok()
+        });
+        match root {
+            Some(root) => ensure_writable(root)
+                .map_err(|e| ServerFnError::new(format!("Refused to restore: {}", e)))?,
+            None => {
+                tracing::warn!("Refused to restore {}: outside every history root", original.display());
+                return Err(ServerFnError::new(format!(
+                    "Refused to restore: {} is outside every history root",
+                    original.display()
+                )));
+            }
+        }
+    }
+
+    let item = trash.restore(&id).map_err(|e| {
+        tracing::error!("Failed to restore trash item {}: {}", id, e);
+        ServerFnError::new(e)
+    })?;
+
+    // Drop any stale cache entries for the restored project
+    crate::infrastructure::conversation_cache::conversation_cache()
+        .invalidate_project(&item.encoded_name);
+
+    Ok(true)
+}
+
+/// Permanently delete a recycle bin item
+#[server]
+pub async fn purge_trash_item(id: String) -> Result<bool, ServerFnError> {
+    use crate::infrastructure::trash::Trash;
+
+    let trash = Trash::open_default().map_err(ServerFnError::new)?;
+    trash.purge(&id).map_err(ServerFnError::new)?;
+
+    Ok(true)
+}
+
+/// Permanently delete everything in the recycle bin
+#[server]
+pub async fn empty_trash() -> Result<usize, ServerFnError> {
+    use crate::infrastructure::trash::Trash;
+
+    let trash = Trash::open_default().map_err(ServerFnError::new)?;
+    trash.purge_all().map_err(ServerFnError::new)
+}
+
 /// Get conversation for viewing (converts to domain Conversation type)
-/// OPTIMIZED: Uses load_conversation() to parse ONLY the requested session file
+/// OPTIMIZED: Uses load_conversation_file() to parse ONLY the requested session file
 /// instead of parsing ALL session files in the project
-/// CACHED: Returns cached result if available (TTL 5 min)
+/// CACHED: Served from the conversation LRU while the session file is unchanged
 #[server]
 pub async fn get_conversation(
     encoded_name: String,
     session_id: String,
 ) -> Result<Option<crate::domain::models::Conversation>, ServerFnError> {
     use crate::domain::models::{Conversation, Message};
-    use crate::history::{load_conversation, ContentBlock};
-    use chrono::{DateTime, Utc};
+    use crate::history::metadata_index::fingerprint;
+    use crate::history::{find_conversation_file, load_conversation_file};
+    use crate::infrastructure::conversation_cache::{conversation_cache, CacheKey};
+    use std::sync::Arc;
 
     tracing::debug!(
         "get_conversation called: encoded_name='{}', session_id='{}'",
@@ -435,17 +628,21 @@ pub async fn get_conversation(
         session_id
     );
 
-    // OPTIMIZATION 1: Check cache first (instant return if cached)
-    if let Some(cached) = cache::get_cached(&encoded_name, &session_id) {
-        tracing::info!("Cache hit for session: {}", session_id);
-        return Ok(Some(cached));
+    let Some((ai_tool, file_path)) = find_conversation_file(&encoded_name, &session_id) else {
+        tracing::warn!("Session not found: {}", session_id);
+        return Ok(None);
+    };
+
+    // Cache hits are checked against the file's current mtime and size
+    let key = CacheKey::new(&ai_tool, &encoded_name, &session_id);
+    if let Some(cached) = conversation_cache().get(&key, &file_path) {
+        tracing::debug!("Cache hit for session: {}", session_id);
+        return Ok(Some((*cached).clone()));
     }
 
-    // OPTIMIZATION 2: Load only the requested session file directly
-    // Previously: get_history() -> get_histories() -> parse ALL files -> find one
-    // Now: load_conversation() -> parse ONLY one file
-    tracing::info!("Loading conversation from disk...");
-    let history = match load_conversation(&encoded_name, &session_id) {
+    // Fingerprint taken before parsing: an append during the parse fails the next check
+    let file_fingerprint = fingerprint(&ai_tool, &file_path);
+    let history = match load_conversation_file(&ai_tool, &file_path, &session_id) {
         Ok(h) => {
             tracing::info!("Conversation loaded: {} messages", h.messages.len());
             h
@@ -456,64 +653,88 @@ pub async fn get_conversation(
         }
     };
 
-    let messages: Vec<Message> = history
-        .messages
-        .into_iter()
-        .map(|m| {
-            let timestamp = m
-                .timestamp
-                .as_ref()
-                .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(Utc::now);
-
-            let content_text = m
-                .content
-                .iter()
-                .filter_map(|c| match c {
-                    ContentBlock::Text { text } => Some(text.clone()),
-                    _ => None,
-                })
-                .collect::<Vec<_>>()
-                .join("\n");
-
-            match m.role.as_str() {
-                "user" | "human" => Message::User {
-                    content: content_text,
-                    timestamp,
-                    images: vec![],
-                    metadata: None,
-                },
-                "assistant" => Message::Assistant {
-                    content: content_text,
-                    timestamp,
-                    model: None,
-                    images: vec![],
-                    metadata: None,
-                },
-                _ => Message::System {
-                    content: content_text,
-                    timestamp,
-                    level: None,
-                    metadata: None,
-                },
-            }
-        })
-        .collect();
+    let messages: Vec<Message> = history.messages.into_iter().map(Message::from).collect();
 
     let conversation = Conversation {
         session_id: history.session_id,
         messages,
     };
 
-    tracing::info!(
-        "Conversation converted: {} messages in domain model",
-        conversation.messages.len()
+    if let Some(file_fingerprint) = file_fingerprint {
+        conversation_cache().insert(key, file_path, file_fingerprint, Arc::new(conversation.clone()));
+    }
+
+    Ok(Some(conversation))
+}
+
+/// Get one page of a conversation (for sessions too large to load at once)
+/// cursor: Latest on open, Before/After while scrolling, Around to jump to a message
+/// limit: number of messages (default 100, capped at 1000)
+#[server]
+pub async fn get_conversation_page(
+    encoded_name: String,
+    session_id: String,
+    cursor: crate::domain::models::PageCursor,
+    limit: Option<usize>,
+) -> Result<Option<crate::domain::models::ConversationPage>, ServerFnError> {
+    use crate::domain::models::{ConversationPage, Message};
+    use crate::history::message_index::{load_conversation_page, DEFAULT_PAGE_SIZE};
+
+    let page = match load_conversation_page(&encoded_name, &session_id, cursor, limit.unwrap_or(DEFAULT_PAGE_SIZE)) {
+        Ok(page) => page,
+        Err(e) => {
+            tracing::warn!("Session not found: {} - {}", session_id, e);
+            return Ok(None);
+        }
+    };
+
+    tracing::debug!(
+        "Conversation page {:?}: messages {}..{} of {}",
+        cursor,
+        page.start,
+        page.start + page.messages.len(),
+        page.total
     );
 
-    // OPTIMIZATION 3: Cache for next request
-    cache::set_cached(&encoded_name, &session_id, conversation.clone());
+    Ok(Some(ConversationPage {
+        session_id: page.session_id,
+        messages: page.messages.into_iter().map(Message::from).collect(),
+        start: page.start,
+        total: page.total,
+    }))
+}
 
-    tracing::info!("Returning conversation for session: {}", session_id);
-    Ok(Some(conversation))
+/// Freeze a redacted copy of a session (or of a message range) behind a share link
+#[server]
+pub async fn create_share(
+    encoded_name: String,
+    session_id: String,
+    request: crate::domain::models::CreateShareRequest,
+) -> Result<crate::domain::models::ShareSummary, ServerFnError> {
+    use crate::handlers::shares::share_session;
+
+    tokio::task::spawn_blocking(move || share_session(&encoded_name, &session_id, &request))
+        .await
+        .map_err(|e| ServerFnError::new(e.to_string()))?
+        .map_err(|(_, message)| ServerFnError::new(message))
+}
+
+/// Active share links, most recent first
+#[server]
+pub async fn list_shares() -> Result<Vec<crate::domain::models::ShareSummary>, ServerFnError> {
+    use crate::infrastructure::shares::ShareStore;
+
+    ShareStore::open_default()
+        .and_then(|store| store.list(chrono::Utc::now()))
+        .map_err(ServerFnError::new)
+}
+
+/// Revoke a share link
+#[server]
+pub async fn revoke_share(id: String) -> Result<(), ServerFnError> {
+    use crate::infrastructure::shares::ShareStore;
+
+    ShareStore::open_default()
+        .and_then(|store| store.revoke(&id))
+        .map_err(ServerFnError::new)
 }
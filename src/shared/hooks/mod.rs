@@ -6,6 +6,7 @@ pub mod use_abort_controller;
 pub mod use_chat_state;
 pub mod use_theme;
 pub mod use_image_upload;
+pub mod use_redaction;
 
 // API hooks temporarily disabled due to lifetime complexity
 pub use use_stream_parser::use_stream_parser;
@@ -14,3 +15,4 @@ pub use use_abort_controller::{use_abort_controller, UseAbortControllerReturn};
 pub use use_chat_state::{use_chat_state, ChatState};
 pub use use_theme::{use_theme, Theme, save_theme, save_default_theme};
 pub use use_image_upload::{use_image_upload, ImageUploadState, upload_file_to_server};
+pub use use_redaction::{use_redaction, use_redaction_provider, save_redaction_enabled, RedactionState};
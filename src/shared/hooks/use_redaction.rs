@@ -0,0 +1,72 @@
+//! Masked rendering of secrets in the conversation view
+//!
+//! Opt-in (persisted in localStorage): messages holding a secret show
+//! `[redacted:<kind>]` until the user reveals them. Exports are always redacted
+//! on the server, whatever this setting.
+
+use dioxus::prelude::*;
+
+use crate::domain::services::redaction::Redactor;
+
+/// localStorage key of the setting ("1" = secrets masked)
+const STORAGE_KEY: &str = "redact_secrets";
+
+/// Shared by the layout (provider), the settings panel and `MessageItem`
+#[derive(Clone, Copy)]
+pub struct RedactionState {
+    pub enabled: Signal<bool>,
+    /// Built-in detectors plus the server's `[[redaction.rules]]`
+    pub redactor: Signal<Redactor>,
+}
+
+/// Create the state, load the setting and the custom rules, and share it with children
+pub fn use_redaction_provider() -> RedactionState {
+    let mut enabled = use_signal(|| false);
+    let mut redactor = use_signal(Redactor::default);
+
+    use_effect(move || {
+        spawn(async move {
+            #[cfg(target_arch = "wasm32")]
+            {
+                if let Some(window) = web_sys::window() {
+                    if let Ok(Some(storage)) = window.local_storage() {
+                        if let Ok(Some(saved)) = storage.get_item(STORAGE_KEY) {
+                            enabled.set(saved == "1");
+                        }
+                    }
+                }
+            }
+
+            match crate::server_fns::get_redaction_rules().await {
+                Ok(rules) if !rules.is_empty() => match Redactor::new(&rules) {
+                    Ok(custom) => redactor.set(custom),
+                    Err(e) => tracing::warn!("Ignoring redaction rules: {}", e),
+                },
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to load redaction rules: {}", e),
+            }
+        });
+    });
+
+    use_context_provider(|| RedactionState { enabled, redactor })
+}
+
+/// State provided by the layout (None outside of it)
+pub fn use_redaction() -> Option<RedactionState> {
+    try_use_context::<RedactionState>()
+}
+
+/// Save the setting to localStorage
+#[cfg(target_arch = "wasm32")]
+pub async fn save_redaction_enabled(enabled: bool) {
+    if let Some(window) = web_sys::window() {
+        if let Ok(Some(storage)) = window.local_storage() {
+            let _ = storage.set_item(STORAGE_KEY, if enabled { "1" } else { "0" });
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn save_redaction_enabled(_enabled: bool) {
+    // No-op on server
+}
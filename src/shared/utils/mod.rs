@@ -5,4 +5,4 @@
 pub mod stream_parser;
 
 #[cfg(any(target_arch = "wasm32", target_arch = "wasm32"))]
-pub use stream_parser::{process_claude_sdk_message, process_stream_line};
+pub use stream_parser::{process_claude_sdk_message, process_stream_event, process_stream_line};
@@ -1,6 +1,6 @@
 use crate::domain::models::{
-    AssistantMessage, ContentItem, LogLevel, Message, MessageMetadata, SDKMessage, StreamResponse,
-    TodoItem,
+    AssistantMessage, ContentItem, LogLevel, Message, MessageMetadata, SDKMessage, StreamEvent,
+    StreamResponse, TodoItem,
 };
 use chrono::Utc;
 use dioxus::prelude::*;
@@ -33,6 +33,15 @@ pub fn process_stream_line(
             );
         }
 
+        StreamResponse::Event { event } => {
+            process_stream_event(
+                event,
+                messages,
+                current_assistant_message,
+                current_session_id,
+            );
+        }
+
         StreamResponse::Error { error } => {
             messages.write().push(Message::System {
                 content: format!("Error: {}", error),
@@ -152,29 +161,12 @@ pub fn process_claude_sdk_message(
 fn process_assistant_content(
     assistant_msg: AssistantMessage,
     mut messages: Signal<Vec<Message>>,
-    mut current_assistant_message: Signal<Option<Message>>,
+    current_assistant_message: Signal<Option<Message>>,
 ) {
     for item in assistant_msg.content {
         match item.item_type.as_str() {
             "text" => {
-                let text_content = item.text.unwrap_or_default();
-
-                // Accumulate text in current_assistant_message
-                if let Some(mut msg) = current_assistant_message() {
-                    if let Message::Assistant { content, .. } = &mut msg {
-                        content.push_str(&text_content);
-                    }
-                    current_assistant_message.set(Some(msg));
-                } else {
-                    // Create new assistant message
-                    current_assistant_message.set(Some(Message::Assistant {
-                        content: text_content,
-                        timestamp: Utc::now(),
-                        model: None,
-                        images: vec![],
-                        metadata: None,
-                    }));
-                }
+                append_assistant_text(&item.text.unwrap_or_default(), current_assistant_message);
             }
 
             "thinking" => {
@@ -188,64 +180,12 @@ fn process_assistant_content(
 
             "tool_use" => {
                 let tool_name = item.name.unwrap_or_default();
-                let tool_use_id = item.id.clone();
-
-                // Check for ExitPlanMode
-                if tool_name == "ExitPlanMode" {
-                    if let Some(input) = &item.input {
-                        if let Some(plan) = input.get("plan").and_then(|p| p.as_str()) {
-                            messages.write().push(Message::Plan {
-                                content: plan.to_string(),
-                                tool_use_id: tool_use_id.unwrap_or_default(),
-                                timestamp: Utc::now(),
-                                metadata: None,
-                            });
-                            return;
-                        }
-                    }
-                }
-
-                // Check for TodoWrite
-                if tool_name == "TodoWrite" {
-                    if let Some(input) = &item.input {
-                        if let Some(todos_array) = input.get("todos").and_then(|t| t.as_array()) {
-                            let todo_items: Vec<TodoItem> = todos_array
-                                .iter()
-                                .filter_map(|todo| {
-                                    Some(TodoItem {
-                                        content: todo.get("content")?.as_str()?.to_string(),
-                                        status: todo.get("status")?.as_str()?.to_string(),
-                                        active_form: todo.get("activeForm")?.as_str()?.to_string(),
-                                    })
-                                })
-                                .collect();
-
-                            messages.write().push(Message::Todo {
-                                items: todo_items,
-                                timestamp: Utc::now(),
-                                metadata: None,
-                            });
-                            return;
-                        }
-                    }
-                }
 
                 // Check for permission error
                 if let Some(error) = &item.error {
                     if error.error_type == "PermissionError" {
                         let patterns = error.patterns.clone().unwrap_or_default();
-                        let error_msg = format!(
-                            "🔐 Permission required for tool: {}\nPatterns: {}",
-                            tool_name,
-                            patterns.join(", ")
-                        );
-
-                        messages.write().push(Message::System {
-                            content: error_msg,
-                            timestamp: Utc::now(),
-                            level: Some(LogLevel::Warn),
-                            metadata: None,
-                        });
+                        push_permission_required(&tool_name, &patterns, messages);
 
                         // TODO: Trigger permission dialog
                         // context.onPermissionError(tool_name, patterns, tool_use_id)
@@ -254,20 +194,218 @@ fn process_assistant_content(
                     }
                 }
 
-                // Regular tool use message
-                messages.write().push(Message::Tool {
-                    name: tool_name,
-                    input: item.input.unwrap_or(serde_json::json!({})),
-                    output: None,
+                let input = item.input.unwrap_or(serde_json::json!({}));
+                if push_tool_call(tool_name, item.id, input, messages) {
+                    return;
+                }
+            }
+
+            _ => {
+                tracing::warn!("Unknown content type: {}", item.item_type);
+            }
+        }
+    }
+}
+
+/// Process a provider-neutral stream event (every provider renders the same way)
+pub fn process_stream_event(
+    event: StreamEvent,
+    mut messages: Signal<Vec<Message>>,
+    current_assistant_message: Signal<Option<Message>>,
+    mut current_session_id: Signal<Option<String>>,
+) {
+    match event {
+        StreamEvent::SessionStarted { session_id, cwd, model, tools } => {
+            current_session_id.set(Some(session_id));
+
+            let mut content_parts = vec![];
+            if let Some(cwd) = cwd {
+                content_parts.push(format!("Working directory: {}", cwd));
+            }
+            if let Some(model) = model {
+                content_parts.push(format!("Model: {}", model));
+            }
+            if !tools.is_empty() {
+                content_parts.push(format!("Available tools: {}", tools.join(", ")));
+            }
+
+            if !content_parts.is_empty() {
+                messages.write().push(Message::System {
+                    content: content_parts.join("\n"),
                     timestamp: Utc::now(),
-                    tool_call_id: tool_use_id,
+                    level: Some(LogLevel::Info),
                     metadata: None,
                 });
             }
+        }
 
-            _ => {
-                tracing::warn!("Unknown content type: {}", item.item_type);
+        StreamEvent::TextDelta { text } => {
+            append_assistant_text(&text, current_assistant_message);
+        }
+
+        StreamEvent::Thinking { text } => {
+            // Keep the order of the stream: text so far comes before
+            finish_assistant_message(messages, current_assistant_message);
+            messages.write().push(Message::Thinking {
+                content: text,
+                timestamp: Utc::now(),
+                metadata: None,
+            });
+        }
+
+        StreamEvent::ToolCall { id, name, input } => {
+            finish_assistant_message(messages, current_assistant_message);
+            push_tool_call(name, id, input, messages);
+        }
+
+        StreamEvent::ToolResult { id, output, is_error } => {
+            let output = if is_error { format!("Error: {}", output) } else { output };
+            let mut messages = messages.write();
+            // The matching call, or the latest one still waiting when there is no ID
+            let call = messages.iter_mut().rev().find_map(|message| match message {
+                Message::Tool { tool_call_id, output: call_output, .. }
+                    if call_output.is_none() && (id.is_none() || *tool_call_id == id) =>
+                {
+                    Some(call_output)
+                }
+                _ => None,
+            });
+            match call {
+                Some(call_output) => *call_output = Some(output),
+                None => tracing::warn!("Tool result without a matching call: {:?}", id),
             }
         }
+
+        StreamEvent::PermissionRequired { tool, patterns } => {
+            push_permission_required(&tool, &patterns, messages);
+        }
+
+        StreamEvent::Usage { input_tokens, output_tokens } => {
+            messages.write().push(Message::System {
+                content: format!("Tokens: {} in, {} out", input_tokens, output_tokens),
+                timestamp: Utc::now(),
+                level: Some(LogLevel::Info),
+                metadata: None,
+            });
+        }
+
+        StreamEvent::Result { success, message, duration_ms } => {
+            finish_assistant_message(messages, current_assistant_message);
+
+            let mut content = match (success, message) {
+                (true, _) => "✅ Execution completed successfully".to_string(),
+                (false, Some(message)) => format!("❌ Execution failed: {}", message),
+                (false, None) => "❌ Execution failed".to_string(),
+            };
+            if let Some(duration_ms) = duration_ms {
+                content.push_str(&format!(" ({:.1} s)", duration_ms as f64 / 1000.0));
+            }
+
+            messages.write().push(Message::System {
+                content,
+                timestamp: Utc::now(),
+                level: Some(if success { LogLevel::Info } else { LogLevel::Error }),
+                metadata: None,
+            });
+        }
+    }
+}
+
+/// Append text to the assistant message being streamed (created on first text)
+fn append_assistant_text(text: &str, mut current_assistant_message: Signal<Option<Message>>) {
+    if let Some(mut msg) = current_assistant_message() {
+        if let Message::Assistant { content, .. } = &mut msg {
+            content.push_str(text);
+        }
+        current_assistant_message.set(Some(msg));
+    } else {
+        // Create new assistant message
+        current_assistant_message.set(Some(Message::Assistant {
+            content: text.to_string(),
+            timestamp: Utc::now(),
+            model: None,
+            images: vec![],
+            metadata: None,
+        }));
+    }
+}
+
+/// Move the assistant message being streamed into the message list
+fn finish_assistant_message(
+    mut messages: Signal<Vec<Message>>,
+    mut current_assistant_message: Signal<Option<Message>>,
+) {
+    if let Some(msg) = current_assistant_message() {
+        messages.write().push(msg);
+        current_assistant_message.set(None);
     }
 }
+
+fn push_permission_required(tool_name: &str, patterns: &[String], mut messages: Signal<Vec<Message>>) {
+    messages.write().push(Message::System {
+        content: format!(
+            "🔐 Permission required for tool: {}\nPatterns: {}",
+            tool_name,
+            patterns.join(", ")
+        ),
+        timestamp: Utc::now(),
+        level: Some(LogLevel::Warn),
+        metadata: None,
+    });
+}
+
+/// Push a tool call: plans (ExitPlanMode) and todo lists (TodoWrite) get their own messages
+/// Returns true for those, which end the Claude content being processed.
+fn push_tool_call(
+    tool_name: String,
+    tool_use_id: Option<String>,
+    input: serde_json::Value,
+    mut messages: Signal<Vec<Message>>,
+) -> bool {
+    // Check for ExitPlanMode
+    if tool_name == "ExitPlanMode" {
+        if let Some(plan) = input.get("plan").and_then(|p| p.as_str()) {
+            messages.write().push(Message::Plan {
+                content: plan.to_string(),
+                tool_use_id: tool_use_id.unwrap_or_default(),
+                timestamp: Utc::now(),
+                metadata: None,
+            });
+            return true;
+        }
+    }
+
+    // Check for TodoWrite
+    if tool_name == "TodoWrite" {
+        if let Some(todos_array) = input.get("todos").and_then(|t| t.as_array()) {
+            let todo_items: Vec<TodoItem> = todos_array
+                .iter()
+                .filter_map(|todo| {
+                    Some(TodoItem {
+                        content: todo.get("content")?.as_str()?.to_string(),
+                        status: todo.get("status")?.as_str()?.to_string(),
+                        active_form: todo.get("activeForm")?.as_str()?.to_string(),
+                    })
+                })
+                .collect();
+
+            messages.write().push(Message::Todo {
+                items: todo_items,
+                timestamp: Utc::now(),
+                metadata: None,
+            });
+            return true;
+        }
+    }
+
+    // Regular tool use message
+    messages.write().push(Message::Tool {
+        name: tool_name,
+        input,
+        output: None,
+        timestamp: Utc::now(),
+        tool_call_id: tool_use_id,
+        metadata: None,
+    });
+    false
+}
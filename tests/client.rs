@@ -0,0 +1,111 @@
+//! Drives the typed client against the router served on a local port
+
+use std::path::Path;
+
+use axum::Router;
+
+use cli_session_hub_viewer::client::{ClientError, HubClient};
+use cli_session_hub_viewer::domain::models::{Message, PageCursor};
+use cli_session_hub_viewer::handlers::chat::ChatRequest;
+use cli_session_hub_viewer::history::archive::ArchiveFormat;
+use cli_session_hub_viewer::history::export::{ExportFormat, ExportedConversation};
+use cli_session_hub_viewer::handlers::{build_router, RouterOptions};
+
+const TOKEN: &str = "client-test-token";
+const PROJECT: &str = "-tmp-client-demo";
+
+fn claude_line(uuid: &str, role: &str, text: &str) -> String {
+    format!(
+        "{{\"type\":\"{}\",\"uuid\":\"{}\",\"sessionId\":\"s1\",\"cwd\":\"/tmp/client-demo\",\"timestamp\":\"2025-01-01T10:00:00Z\",\"message\":{{\"role\":\"{}\",\"content\":\"{}\"}}}}\n",
+        role, uuid, role, text
+    )
+}
+
+fn write_fixture(dir: &Path) {
+    let project = dir.join("claude").join(PROJECT);
+    for sub in [project.clone(), dir.join("opencode"), dir.join("gemini"), dir.join("data")] {
+        std::fs::create_dir_all(sub).unwrap();
+    }
+    let mut session = String::new();
+    for i in 0..5 {
+        let role = if i % 2 == 0 { "user" } else { "assistant" };
+        session.push_str(&claude_line(&format!("u{}", i), role, &format!("message {}", i)));
+    }
+    std::fs::write(project.join("s1.jsonl"), session).unwrap();
+
+    let config = format!(
+        "[providers]\nclaude_dir = \"{dir}/claude\"\nopencode_dir = \"{dir}/opencode\"\ngemini_dir = \"{dir}/gemini\"\n\n\
+         [paths]\ndata_dir = \"{dir}/data\"\n\n[auth]\ntoken = \"{TOKEN}\"\n",
+        dir = dir.display(),
+    );
+    let path = dir.join("config.toml");
+    std::fs::write(&path, config).unwrap();
+    // SAFETY: set before anything reads the configuration, single test in this binary
+    unsafe { std::env::set_var("CLAUDE_VIEWER_CONFIG", &path) };
+}
+
+#[tokio::test]
+async fn test_client_round_trip() {
+    let dir = std::env::temp_dir().join(format!("client-test-{}", uuid::Uuid::new_v4()));
+    write_fixture(&dir);
+    cli_session_hub_viewer::config::init_config().unwrap();
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = build_router(Router::new(), RouterOptions::default());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let client = HubClient::new(format!("http://{}", addr)).with_token(TOKEN);
+
+    let projects = client.projects().await.unwrap();
+    assert!(projects.iter().any(|p| p.encoded_name == PROJECT));
+
+    let sessions = client.histories(PROJECT).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].session_id, "s1");
+
+    let conversation = client.conversation(PROJECT, "s1").await.unwrap();
+    assert_eq!(conversation.messages.len(), 5);
+    assert!(matches!(&conversation.messages[1], Message::Assistant { content, .. } if content == "message 1"));
+
+    let page = client
+        .conversation_page(PROJECT, "s1", PageCursor::Before(4), Some(2))
+        .await
+        .unwrap();
+    assert_eq!((page.start, page.total, page.messages.len()), (2, 5, 2));
+
+    let markdown = client.export(PROJECT, "s1", ExportFormat::Markdown, None).await.unwrap();
+    assert!(markdown.contains("## Assistant · 2025-01-01 10:00:00 UTC\n\nmessage 1"));
+    let json = client.export(PROJECT, "s1", ExportFormat::Json, None).await.unwrap();
+    let exported: ExportedConversation = serde_json::from_str(&json).unwrap();
+    assert_eq!((exported.provider.as_str(), exported.messages.len()), ("claude", 5));
+    let html = client.export(PROJECT, "s1", ExportFormat::Html, Some("pistachio")).await.unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>") && html.contains("class=\"pistachio\""));
+
+    // The archive comes back as a read-only copy of the project
+    let archive = client.project_archive(PROJECT, Some("claude"), ArchiveFormat::Zip).await.unwrap();
+    let report = client.import_project_archive(archive).await.unwrap();
+    assert_eq!((report.sessions, report.files_written), (1, 1));
+    assert!(dir.join("data").join("imports").join("claude").join(PROJECT).join("s1.jsonl").is_file());
+    let projects = client.projects().await.unwrap();
+    let project = projects.iter().find(|p| p.encoded_name == PROJECT).unwrap();
+    assert_eq!(project.roots, vec!["local".to_string(), "imported".to_string()]);
+    assert_eq!(project.session_count, 1);
+    assert!(client.import_project_archive(b"garbage".to_vec()).await.is_err());
+
+    // No Claude CLI in the test environment
+    assert!(!client.chat_status().await.unwrap().available);
+    match client.chat(&ChatRequest::new("hello")).await {
+        Err(ClientError::Status { status, .. }) => assert_eq!(status, 503),
+        other => panic!("expected 503, got {:?}", other.map(|stream| stream.request_id)),
+    }
+
+    // Bad token
+    let anonymous = HubClient::new(format!("http://{}", addr)).with_token("wrong");
+    assert!(matches!(
+        anonymous.projects().await,
+        Err(ClientError::Status { status: 401, .. })
+    ));
+
+    std::fs::remove_dir_all(&dir).ok();
+}
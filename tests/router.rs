@@ -0,0 +1,210 @@
+//! Boots the shared router in-process and sends one request to every route
+
+use std::path::Path;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, Request},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::{self, Next},
+    response::Response,
+    Router,
+};
+use tower::ServiceExt;
+
+use cli_session_hub_viewer::handlers::{build_router, RouterOptions, API_LEGACY, API_V1};
+
+const TOKEN: &str = "router-test-token";
+
+/// Every API route, relative to its prefix: (method, request path, route template)
+const ROUTES: &[(&str, &str, &str)] = &[
+    ("GET", "/projects", "/projects"),
+    ("GET", "/projects/-tmp-demo/histories", "/projects/{encoded_name}/histories"),
+    ("GET", "/projects/-tmp-demo/archive?format=zip", "/projects/{encoded_name}/archive"),
+    ("POST", "/projects/import", "/projects/import"),
+    ("GET", "/projects/-tmp-demo/active-session", "/projects/{encoded_name}/active-session"),
+    ("GET", "/projects/-tmp-demo/histories/s1", "/projects/{encoded_name}/histories/{session_id}"),
+    (
+        "GET",
+        "/projects/-tmp-demo/histories/s1/messages?limit=10",
+        "/projects/{encoded_name}/histories/{session_id}/messages",
+    ),
+    (
+        "GET",
+        "/projects/-tmp-demo/histories/s1/export?format=html",
+        "/projects/{encoded_name}/histories/{session_id}/export",
+    ),
+    (
+        "POST",
+        "/projects/-tmp-demo/histories/s1/shares",
+        "/projects/{encoded_name}/histories/{session_id}/shares",
+    ),
+    ("GET", "/shares", "/shares"),
+    ("DELETE", "/shares/0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef", "/shares/{id}"),
+    ("GET", "/sse/-tmp-demo/s1", "/sse/{encoded_name}/{session_id}"),
+    ("GET", "/sync/status", "/sync/status"),
+    ("GET", "/cache/stats", "/cache/stats"),
+    ("GET", "/config", "/config"),
+    ("POST", "/auth/login", "/auth/login"),
+    ("POST", "/auth/logout", "/auth/logout"),
+    ("GET", "/auth/session", "/auth/session"),
+    ("POST", "/upload", "/upload"),
+    ("POST", "/upload-debug", "/upload-debug"),
+    ("GET", "/images/00000000-0000-0000-0000-000000000000", "/images/{image_id}"),
+    ("POST", "/abort/r1", "/abort/{request_id}"),
+    ("GET", "/sessions/active", "/sessions/active"),
+    ("DELETE", "/sessions/s1", "/sessions/{session_id}"),
+    ("POST", "/chat/native", "/chat/native"),
+    ("GET", "/chat/status", "/chat/status"),
+];
+
+/// Report which route handled the request
+async fn record_matched_path(request: Request, next: Next) -> Response {
+    let matched = request.extensions().get::<MatchedPath>().map(|m| m.as_str().to_string());
+    let mut response = next.run(request).await;
+    if let Some(matched) = matched {
+        response
+            .headers_mut()
+            .insert("x-matched-path", HeaderValue::from_str(&matched).unwrap());
+    }
+    response
+}
+
+fn write_config(dir: &Path) {
+    for sub in ["claude", "opencode", "gemini", "data", "uploads"] {
+        std::fs::create_dir_all(dir.join(sub)).unwrap();
+    }
+    let config = format!(
+        r#"
+[providers]
+claude_dir = "{dir}/claude"
+opencode_dir = "{dir}/opencode"
+gemini_dir = "{dir}/gemini"
+
+[paths]
+data_dir = "{dir}/data"
+upload_dir = "{dir}/uploads"
+
+[auth]
+token = "{token}"
+"#,
+        dir = dir.display(),
+        token = TOKEN,
+    );
+    let path = dir.join("config.toml");
+    std::fs::write(&path, config).unwrap();
+    // SAFETY: set before anything reads the configuration, single test in this binary
+    unsafe { std::env::set_var("CLAUDE_VIEWER_CONFIG", &path) };
+}
+
+fn request(method: &str, uri: &str, token: Option<&str>) -> axum::http::Request<Body> {
+    let mut builder = axum::http::Request::builder()
+        .method(Method::from_bytes(method.as_bytes()).unwrap())
+        .uri(uri)
+        // Same-origin, as required for the public POST routes
+        .header(header::HOST, "localhost")
+        .header(header::ORIGIN, "http://localhost");
+    if let Some(token) = token {
+        builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    if method == "POST" && uri.ends_with("/auth/login") {
+        builder = builder.header(header::CONTENT_TYPE, "application/x-www-form-urlencoded");
+        return builder.body(Body::from("secret=wrong&next=/")).unwrap();
+    }
+    if method == "POST" && uri.ends_with("/shares") {
+        builder = builder.header(header::CONTENT_TYPE, "application/json");
+        return builder.body(Body::from("{}")).unwrap();
+    }
+    if method == "POST" && uri.ends_with("/chat/native") {
+        builder = builder.header(header::CONTENT_TYPE, "application/json");
+        return builder.body(Body::from(r#"{"message":"hello"}"#)).unwrap();
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+async fn json_body(response: Response) -> serde_json::Value {
+    let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[tokio::test]
+async fn test_every_route_is_served() {
+    let dir = std::env::temp_dir().join(format!("router-test-{}", uuid::Uuid::new_v4()));
+    write_config(&dir);
+    cli_session_hub_viewer::config::init_config().unwrap();
+
+    let router = build_router(Router::new(), RouterOptions::default())
+        .route_layer(middleware::from_fn(record_matched_path));
+
+    for prefix in [API_V1, API_LEGACY] {
+        for &(method, path, template) in ROUTES {
+            let uri = format!("{}{}", prefix, path);
+            let response = router.clone().oneshot(request(method, &uri, Some(TOKEN))).await.unwrap();
+            let matched = response
+                .headers()
+                .get("x-matched-path")
+                .map(|v| v.to_str().unwrap().to_string());
+            let expected = format!("{}{}", prefix, template);
+            assert_eq!(matched.as_deref(), Some(expected.as_str()), "{} {} was not routed", method, uri);
+            assert_ne!(response.status(), StatusCode::METHOD_NOT_ALLOWED, "{} {}", method, uri);
+        }
+    }
+
+    // Every v1 route is documented
+    let response = router
+        .clone()
+        .oneshot(request("GET", "/api/v1/openapi.json", Some(TOKEN)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let openapi = json_body(response).await;
+    for &(method, _, template) in ROUTES {
+        if template == "/upload-debug" {
+            continue;
+        }
+        let operation = &openapi["paths"][template][method.to_lowercase()];
+        assert!(operation.is_object(), "{} {} missing from openapi.json", method, template);
+    }
+    assert!(openapi["components"]["schemas"]["StreamResponse"].is_object());
+
+    let response = router
+        .clone()
+        .oneshot(request("GET", "/api/v1/schema/stream-event.json", Some(TOKEN)))
+        .await
+        .unwrap();
+    let schema = json_body(response).await;
+    assert_eq!(schema["$schema"], "https://json-schema.org/draft/2020-12/schema");
+    assert!(schema.to_string().contains("claude_json"));
+
+    // Without the Claude CLI the chat routes stay, but report it
+    let response = router
+        .clone()
+        .oneshot(request("POST", "/api/chat/native", Some(TOKEN)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    // Share pages are public; unknown ids are not found
+    let response = router
+        .clone()
+        .oneshot(request("GET", "/share/0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // Authentication wraps every API route
+    let response = router
+        .clone()
+        .oneshot(request("GET", "/api/projects", None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = router
+        .oneshot(request("GET", "/api/unknown", Some(TOKEN)))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    std::fs::remove_dir_all(&dir).ok();
+}